@@ -0,0 +1,63 @@
+#![expect(clippy::unwrap_used, reason = "This is a benchmark")]
+
+use std::{hint::black_box, io::Cursor, path::Path};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use ragnarok_gat::{Cell, Gat};
+use ragnarok_grf::Grf;
+
+/// Loads `prontera.gat` from `data.grf`, or generates a map of the
+/// same size if the GRF is not available
+fn load_map() -> Gat {
+    if let Ok(grf) = Grf::new(Path::new("data.grf"))
+        && let Ok(content) = grf.read_file(Path::new("data/prontera.gat"))
+    {
+        return Gat::from_reader(&mut Cursor::new(&content)).unwrap();
+    }
+
+    const SIZE: u32 = 312;
+
+    let mut bytes = b"GRAT".to_vec();
+    bytes.extend_from_slice(&[1, 2]);
+    bytes.extend_from_slice(&SIZE.to_le_bytes());
+    bytes.extend_from_slice(&SIZE.to_le_bytes());
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            // Walls every 16 cells with gaps, forcing the path to wind around
+            let wall = (x % 16 == 8 && y % 64 != 0) || (y % 32 == 16 && x % 48 == 0);
+            bytes.extend_from_slice(&[0; 16]);
+            bytes.extend_from_slice(&[u8::from(wall), 0, 0, 0]);
+        }
+    }
+    Gat::from_reader(&mut bytes.as_slice()).unwrap()
+}
+
+/// Finds the walkable cell closest to `cell` along its row
+fn walkable_near(gat: &Gat, cell: Cell) -> Cell {
+    (cell.x..gat.width)
+        .map(|x| Cell::new(x, cell.y))
+        .find(|cell| gat.is_walkable(*cell))
+        .unwrap()
+}
+
+fn pathfinding(c: &mut Criterion) {
+    let gat = load_map();
+    let center = walkable_near(&gat, Cell::new(gat.width / 2, gat.height / 2));
+
+    let short = walkable_near(&gat, Cell::new(center.x + 10, center.y + 5));
+    c.bench_function("short path", |b| {
+        b.iter(|| black_box(&gat).find_path(black_box(center), black_box(short), 32))
+    });
+
+    let long = walkable_near(&gat, Cell::new(gat.width / 8, gat.height / 8));
+    c.bench_function("long path", |b| {
+        b.iter(|| black_box(&gat).find_path(black_box(center), black_box(long), u32::MAX))
+    });
+
+    c.bench_function("cutoff", |b| {
+        b.iter(|| black_box(&gat).find_path(black_box(center), black_box(long), 32))
+    });
+}
+
+criterion_group!(benches, pathfinding);
+criterion_main!(benches);
@@ -1,35 +1,45 @@
+//! Debug tool for Ragnarok Online altitude files.
+//!
+//! ## Usage
+//!
+//! * `gat_debug`: Reports warnings of all gats of `data.grf`.
+//! * `gat_debug export <output>`: Exports the walkability and heightmap of
+//!   all gats of `data.grf` as PNGs into the `output` folder.
+//!
+//! The rathena `map_cache.dat` needs the water level of the `.rsw` of each map,
+//! it is exported by `rsw_debug mapcache <output>`.
+
 #![expect(clippy::unwrap_used, reason = "This is a test")]
 
-use std::{io::Cursor, path::Path};
+use std::{
+    fs::File,
+    io::{BufWriter, Cursor},
+    path::Path,
+};
 
 use ragnarok_gat::Gat;
 use ragnarok_grf::Grf;
 use ragnarok_rebuild_common::warning::ReportWarning;
 
 fn main() {
-    let grf = Grf::new(Path::new("data.grf")).unwrap();
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
 
-    for gat_filename in grf
-        .iter_filenames()
-        .filter(|filename| match filename.extension() {
-            Some(ext) => {
-                matches!(ext.to_str(), Some("gat"))
-            }
-            None => false,
-        })
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
     {
-        let Ok(gat_content) = grf
-            .read_file(gat_filename)
-            .inspect_err(|err| println!("{gat_filename:?}: {err}"))
-        else {
-            continue;
-        };
-        let Ok(gat) = Gat::from_reader(&mut Cursor::new(&gat_content))
-            .inspect_err(|err| println!("{gat_filename:?}: {err}"))
-        else {
-            continue;
-        };
+        [] => report(),
+        ["export", output] => export(Path::new(output)),
+        _ => println!("Usage: gat_debug [export <output>]"),
+    }
+}
 
+fn report() {
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+
+    for (gat_filename, gat) in iter_gats(&grf) {
         let report = gat.report().to_string();
         if !report.is_empty() {
             println!("{:?}", gat_filename);
@@ -37,3 +47,56 @@ fn main() {
         }
     }
 }
+
+fn export(output: &Path) {
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+    std::fs::create_dir_all(output).unwrap();
+
+    let mut exported = 0;
+    for (gat_filename, gat) in iter_gats(&grf) {
+        let Some(name) = gat_filename.file_stem().and_then(|stem| stem.to_str()) else {
+            println!("{gat_filename:?}: Map name is not valid UTF-8.");
+            continue;
+        };
+
+        let mut writer =
+            BufWriter::new(File::create(output.join(format!("{name}_walkability.png"))).unwrap());
+        if let Err(err) = gat.to_walkability_png(&mut writer) {
+            println!("{gat_filename:?}: {err}");
+        }
+
+        let mut writer =
+            BufWriter::new(File::create(output.join(format!("{name}_heightmap.png"))).unwrap());
+        if let Err(err) = gat.to_heightmap_png(&mut writer) {
+            println!("{gat_filename:?}: {err}");
+        }
+
+        exported += 1;
+    }
+
+    println!("Exported {exported} maps.");
+}
+
+fn iter_gats(grf: &Grf) -> impl Iterator<Item = (&Path, Gat)> {
+    grf.iter_filenames()
+        .filter(|filename| match filename.extension() {
+            Some(ext) => {
+                matches!(ext.to_str(), Some("gat"))
+            }
+            None => false,
+        })
+        .filter_map(|gat_filename| {
+            let Ok(gat_content) = grf
+                .read_file(gat_filename)
+                .inspect_err(|err| println!("{gat_filename:?}: {err}"))
+            else {
+                return None;
+            };
+            let Ok(gat) = Gat::from_reader(&mut Cursor::new(&gat_content))
+                .inspect_err(|err| println!("{gat_filename:?}: {err}"))
+            else {
+                return None;
+            };
+            Some((gat_filename.as_path(), gat))
+        })
+}
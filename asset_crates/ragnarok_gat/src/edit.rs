@@ -0,0 +1,117 @@
+use crate::{Cell, Gat, Tile, TileType};
+
+impl Gat {
+    /// Returns the tile at `cell` if it is inside the map
+    pub fn tile(&self, cell: Cell) -> Option<&Tile> {
+        self.cell_index(cell).map(|index| &self.tiles[index])
+    }
+
+    /// Returns the tile at `cell` if it is inside the map
+    pub fn tile_mut(&mut self, cell: Cell) -> Option<&mut Tile> {
+        self.cell_index(cell).map(|index| &mut self.tiles[index])
+    }
+
+    /// Changes the type of the tile at `cell`, returns `false` if `cell` is outside of the map
+    pub fn set_tile_type(&mut self, cell: Cell, tile_type: TileType) -> bool {
+        self.tile_mut(cell)
+            .map(|tile| tile.set_tile_type(tile_type))
+            .is_some()
+    }
+
+    /// Changes the altitudes of the corners of the tile at `cell`, in the order
+    /// top left, top right, bottom left, bottom right.
+    ///
+    /// Returns `false` if `cell` is outside of the map.
+    pub fn set_altitudes(&mut self, cell: Cell, altitudes: [f32; 4]) -> bool {
+        let [top_left, top_right, bottom_left, bottom_right] = altitudes;
+        self.tile_mut(cell)
+            .map(|tile| tile.set_altitudes(top_left, top_right, bottom_left, bottom_right))
+            .is_some()
+    }
+
+    /// Changes the type of the tiles connected to `from` that have the same type as `from`,
+    /// moving in 4 directions.
+    ///
+    /// Returns the number of tiles that changed.
+    pub fn flood_fill(&mut self, from: Cell, tile_type: TileType) -> usize {
+        let Some(start) = self.cell_index(from) else {
+            return 0;
+        };
+        let target = self.tiles[start].raw_tile_type();
+        let replacement = u8::from(tile_type);
+        if target == replacement {
+            return 0;
+        }
+
+        let mut filled = 0;
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            if self.tiles[index].raw_tile_type() != target {
+                continue;
+            }
+            self.tiles[index].set_tile_type(tile_type);
+            filled += 1;
+
+            let cell = self.index_cell(index);
+            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                let Some(neighbor) = cell
+                    .x
+                    .checked_add_signed(dx)
+                    .zip(cell.y.checked_add_signed(dy))
+                    .and_then(|(x, y)| self.cell_index(Cell::new(x, y)))
+                else {
+                    continue;
+                };
+                if self.tiles[neighbor].raw_tile_type() == target {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        filled
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, TileType, test::gat_from_map};
+
+    #[test]
+    fn flood_fill() {
+        let mut gat = gat_from_map(&["..#..", "..#..", "###..", "....."]);
+
+        assert_eq!(gat.flood_fill(Cell::new(0, 0), TileType::WalkableWater), 4);
+        assert_eq!(
+            gat.tile(Cell::new(1, 1)).map(|tile| tile.tile_type()),
+            Some(TileType::WalkableWater)
+        );
+        assert_eq!(
+            gat.tile(Cell::new(0, 3)).map(|tile| tile.tile_type()),
+            Some(TileType::WalkableBlock)
+        );
+        assert_eq!(gat.flood_fill(Cell::new(0, 0), TileType::WalkableWater), 0);
+        assert_eq!(gat.flood_fill(Cell::new(9, 9), TileType::Cliff), 0);
+    }
+
+    #[test]
+    fn water_flag() {
+        let mut gat = gat_from_map(&["...", "..."]);
+        let is_water_tile =
+            |gat: &crate::Gat, cell| gat.tile(cell).map(|tile| tile.is_water_tile());
+
+        assert!(gat.set_tile_type(Cell::new(0, 0), TileType::WalkableWater));
+        assert_eq!(is_water_tile(&gat, Cell::new(0, 0)), Some(true));
+        assert_eq!(is_water_tile(&gat, Cell::new(1, 0)), Some(false));
+
+        assert!(gat.set_tile_type(Cell::new(0, 0), TileType::WalkableBlock));
+        assert_eq!(is_water_tile(&gat, Cell::new(0, 0)), Some(false));
+
+        assert_eq!(gat.flood_fill(Cell::new(2, 1), TileType::WalkableWater), 6);
+        assert_eq!(is_water_tile(&gat, Cell::new(0, 0)), Some(true));
+        assert_eq!(
+            gat.flood_fill(Cell::new(2, 1), TileType::NonWalkableBlock),
+            6
+        );
+        assert_eq!(is_water_tile(&gat, Cell::new(2, 1)), Some(false));
+    }
+}
@@ -7,6 +7,11 @@ pub enum Error {
     InvalidSignature([u8; 4]),
     UnknownVersion(Version),
     IncompleteRead(Version, usize),
+    WrongTileCount(u32, u32, usize),
+    #[cfg(feature = "mapcache")]
+    InvalidMapCacheEntry(String),
+    #[cfg(feature = "png")]
+    PngEncoding(png::EncodingError),
     Io(std::io::Error),
 }
 
@@ -27,6 +32,17 @@ impl Display for Error {
                 f,
                 "Could not read Gat to the end. Gat V{version} had {unread} unread bytes."
             ),
+            Self::WrongTileCount(width, height, tiles) => write!(
+                f,
+                "Gat of {width}x{height} should have {} tiles but had {tiles}.",
+                u64::from(*width) * u64::from(*height)
+            ),
+            #[cfg(feature = "mapcache")]
+            Self::InvalidMapCacheEntry(reason) => {
+                write!(f, "Could not add Gat to map cache, {reason}.")
+            }
+            #[cfg(feature = "png")]
+            Self::PngEncoding(err) => write!(f, "Could not encode Gat as a PNG. '{err}'"),
             Self::Io(err) => write!(f, "Could not read Gat file due to Io error. '{}'", err),
         }
     }
@@ -39,3 +55,10 @@ impl From<std::io::Error> for Error {
         Self::Io(value)
     }
 }
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for Error {
+    fn from(value: png::EncodingError) -> Self {
+        Self::PngEncoding(value)
+    }
+}
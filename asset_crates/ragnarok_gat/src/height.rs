@@ -0,0 +1,68 @@
+use crate::{Cell, Gat};
+
+impl Gat {
+    /// Altitude of the terrain at `(x, z)`, in cell units, where the cell at
+    /// `(0, 0)` spans from `(0., 0.)` to `(1., 1.)`.
+    ///
+    /// The cell is split in two triangles along the diagonal going from the
+    /// top right to the bottom left corner, the same way the ground mesh is
+    /// split, and the altitude is interpolated bilinearly inside the triangle
+    /// containing the point.
+    ///
+    /// Returns `None` if `(x, z)` is outside of the map.
+    pub fn height_at(&self, x: f32, z: f32) -> Option<f32> {
+        if !(x >= 0. && z >= 0.) {
+            return None;
+        }
+
+        let cell = Cell::new(x as u32, z as u32);
+        let tile = &self.tiles[self.cell_index(cell)?];
+
+        let u = x.fract();
+        let v = z.fract();
+
+        let height = if u + v <= 1. {
+            // Top left triangle
+            let top_left = tile.top_left_altitude();
+            top_left
+                + (tile.top_right_altitude() - top_left) * u
+                + (tile.bottom_left_altitude() - top_left) * v
+        } else {
+            // Bottom right triangle
+            let bottom_right = tile.bottom_right_altitude();
+            bottom_right
+                + (tile.bottom_left_altitude() - bottom_right) * (1. - u)
+                + (tile.top_right_altitude() - bottom_right) * (1. - v)
+        };
+
+        Some(height)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Gat;
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn triangle_split() {
+        let mut bytes = b"GRAT".to_vec();
+        bytes.extend_from_slice(&[1, 2]);
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for altitude in [0f32, 1., 2., 4.] {
+            bytes.extend_from_slice(&altitude.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0; 4]);
+        let gat = Gat::from_reader(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(gat.height_at(0., 0.), Some(0.));
+        assert_eq!(gat.height_at(0.5, 0.), Some(0.5));
+        assert_eq!(gat.height_at(0., 0.5), Some(1.));
+        // On the diagonal both triangles agree
+        assert_eq!(gat.height_at(0.5, 0.5), Some(1.5));
+        assert_eq!(gat.height_at(0.999, 0.999).map(|h| h > 3.9), Some(true));
+        assert_eq!(gat.height_at(1., 0.5), None);
+        assert_eq!(gat.height_at(-0.1, 0.5), None);
+    }
+}
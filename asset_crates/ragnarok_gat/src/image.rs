@@ -0,0 +1,93 @@
+//! Rendering of a [`Gat`] as PNG images, one pixel per cell.
+//!
+//! Images have north up, so the first row of pixels is the last row of cells.
+
+use std::io::Write;
+
+use crate::{Error, Gat, Tile, TileType};
+
+impl Gat {
+    /// Writes an RGB PNG with what each cell allows color-coded.
+    ///
+    /// Walkable cells are white, or light blue if they are water, cells
+    /// that can only be sniped over are brown and the others are black.
+    /// Unknown [`TileType`]s are magenta.
+    pub fn to_walkability_png(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        let pixels = self
+            .rows_north_up()
+            .flat_map(|tile| Self::walkability_color(tile.tile_type()))
+            .collect::<Vec<_>>();
+
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&pixels)?;
+        png_writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes a 16-bit grayscale PNG with the average altitude of each cell.
+    ///
+    /// Altitudes are normalized between the lowest and highest cells of the
+    /// map, the highest being white.
+    pub fn to_heightmap_png(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        let (lowest, highest) = self
+            .tiles
+            .iter()
+            .map(Self::average_altitude)
+            .fold((f32::MIN, f32::MAX), |(lowest, highest), altitude| {
+                (lowest.max(altitude), highest.min(altitude))
+            });
+        // Altitudes grow downwards
+        let range = (lowest - highest).max(f32::EPSILON);
+
+        let pixels = self
+            .rows_north_up()
+            .flat_map(|tile| {
+                let height = (lowest - Self::average_altitude(tile)) / range;
+                ((height * f32::from(u16::MAX)).round() as u16).to_be_bytes()
+            })
+            .collect::<Vec<_>>();
+
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Sixteen);
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&pixels)?;
+        png_writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Iterates the tiles starting from the northmost row
+    fn rows_north_up(&self) -> impl Iterator<Item = &Tile> {
+        self.tiles
+            .chunks_exact(self.width.max(1) as usize)
+            .rev()
+            .flatten()
+    }
+
+    fn average_altitude(tile: &Tile) -> f32 {
+        (tile.top_left_altitude()
+            + tile.top_right_altitude()
+            + tile.bottom_left_altitude()
+            + tile.bottom_right_altitude())
+            / 4.
+    }
+
+    fn walkability_color(tile_type: TileType) -> [u8; 3] {
+        if let TileType::Unknown(_) = tile_type {
+            [255, 0, 255]
+        } else if tile_type.is_walkable() && tile_type.is_water() {
+            [96, 160, 255]
+        } else if tile_type.is_walkable() {
+            [255, 255, 255]
+        } else if tile_type.is_snipeable() {
+            [192, 128, 64]
+        } else {
+            [0, 0, 0]
+        }
+    }
+}
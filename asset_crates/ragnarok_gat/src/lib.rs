@@ -1,14 +1,26 @@
+mod edit;
 mod error;
+mod height;
+#[cfg(feature = "png")]
+mod image;
+#[cfg(feature = "mapcache")]
+mod mapcache;
+mod pathfinding;
+mod raycast;
+mod sight;
 mod tile;
 #[cfg(feature = "warning")]
 pub mod warnings;
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
+#[cfg(feature = "mapcache")]
+pub use self::mapcache::MapCache;
 pub use self::{
     error::Error,
+    pathfinding::{Cell, MOVE_COST, MOVE_DIAGONAL_COST},
     tile::{Tile, TileType},
 };
 
@@ -46,6 +58,33 @@ impl Gat {
         })
     }
 
+    /// Writes the [`Gat`], only versions `1.2` and `1.3` can be written
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        let Version(major, minor, 0) = self.version else {
+            return Err(Error::UnknownVersion(self.version));
+        };
+        if !matches!((major, minor), (1, 2) | (1, 3)) {
+            return Err(Error::UnknownVersion(self.version));
+        }
+        if self.tiles.len() as u64 != u64::from(self.width) * u64::from(self.height) {
+            return Err(Error::WrongTileCount(
+                self.width,
+                self.height,
+                self.tiles.len(),
+            ));
+        }
+
+        writer.write_all(&self.signature)?;
+        writer.write_all(&[major, minor])?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        for tile in self.tiles.iter() {
+            tile.to_writer(writer)?;
+        }
+
+        Ok(())
+    }
+
     fn read_signature(mut reader: &mut dyn Read) -> Result<[u8; 4], Error> {
         let signature = reader.read_array()?;
 
@@ -74,3 +113,56 @@ impl Gat {
             .collect::<Result<Box<[_]>, Error>>()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::Gat;
+
+    /// Builds a [`Gat`] from a map where `#` are non-walkable cells,
+    /// `~` are snipeable cliffs, and any other character is a walkable cell
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    pub(crate) fn gat_from_map(map: &[&str]) -> Gat {
+        let width = map[0].len() as u32;
+        let height = map.len() as u32;
+
+        let mut bytes = b"GRAT".to_vec();
+        bytes.extend_from_slice(&[1, 2]);
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        for row in map {
+            for cell in row.chars() {
+                let tile_type = match cell {
+                    '#' => 1,
+                    '~' => 5,
+                    _ => 0,
+                };
+                bytes.extend_from_slice(&[0; 16]);
+                bytes.extend_from_slice(&[tile_type, 0, 0, 0]);
+            }
+        }
+
+        Gat::from_reader(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn write_round_trip() {
+        let mut bytes = b"GRAT".to_vec();
+        bytes.extend_from_slice(&[1, 3]);
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for (altitude, flags) in [(-1f32, [0, 0xAB, 0xCD, 0x80]), (2.5, [1, 0, 0, 0])] {
+            for _ in 0..4 {
+                bytes.extend_from_slice(&altitude.to_le_bytes());
+            }
+            bytes.extend_from_slice(&flags);
+        }
+
+        let gat = Gat::from_reader(&mut bytes.as_slice()).unwrap();
+        assert!(gat.tiles[0].is_water_tile());
+
+        let mut written = vec![];
+        gat.to_writer(&mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+}
@@ -0,0 +1,190 @@
+//! rathena's map cache, the walkability of every map of the server
+//! in a single file, usually `db/map_cache.dat`.
+
+use std::io::Write;
+
+use flate2::{Compression, write::ZlibEncoder};
+
+use crate::{Error, Gat, TileType};
+
+/// Longest map name a [`MapCache`] can hold, without the null terminator
+const MAP_NAME_LENGTH: usize = 11;
+
+/// A map cache with the cells of multiple [`Gat`]s
+#[derive(Debug, Default)]
+pub struct MapCache {
+    maps: Vec<MapCacheEntry>,
+}
+
+#[derive(Debug)]
+struct MapCacheEntry {
+    name: String,
+    width: i16,
+    height: i16,
+    cells: Box<[u8]>,
+}
+
+impl MapCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a map to the cache, `name` is the name of the map without extension.
+    ///
+    /// Like rathena's `mapcache` tool, walkable cells whose first altitude is
+    /// under `water_height` become [`TileType::WalkableWater`], `water_height`
+    /// being the `water_level` of the water plane in the `.rsw` of the map.
+    pub fn push(&mut self, name: &str, gat: &Gat, water_height: Option<f32>) -> Result<(), Error> {
+        if name.len() > MAP_NAME_LENGTH {
+            return Err(Error::InvalidMapCacheEntry(format!(
+                "map name '{name}' is longer than {MAP_NAME_LENGTH} bytes"
+            )));
+        }
+        let (Ok(width), Ok(height)) = (i16::try_from(gat.width), i16::try_from(gat.height)) else {
+            return Err(Error::InvalidMapCacheEntry(format!(
+                "map '{name}' is too big ({}x{})",
+                gat.width, gat.height
+            )));
+        };
+
+        let cells = gat
+            .tiles
+            .iter()
+            .map(|tile| match water_height {
+                // rathena compares all 4 bytes of the type, and only the
+                // first altitude. Altitudes grow downwards, so the cell is under the water
+                Some(water_height)
+                    if tile.raw_cell_flags() == 0 && tile.top_left_altitude() > water_height =>
+                {
+                    u8::from(TileType::WalkableWater)
+                }
+                _ => tile.raw_tile_type(),
+            })
+            .collect();
+
+        self.maps.push(MapCacheEntry {
+            name: name.to_owned(),
+            width,
+            height,
+            cells,
+        });
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.maps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        let Ok(map_count) = u16::try_from(self.maps.len()) else {
+            return Err(Error::InvalidMapCacheEntry(format!(
+                "map cache has too many maps ({})",
+                self.maps.len()
+            )));
+        };
+
+        let mut body = vec![];
+        for map in self.maps.iter() {
+            let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+            encoder.write_all(&map.cells)?;
+            let compressed = encoder.finish()?;
+
+            let mut name = [0u8; MAP_NAME_LENGTH + 1];
+            name[..map.name.len()].copy_from_slice(map.name.as_bytes());
+            body.extend_from_slice(&name);
+            body.extend_from_slice(&map.width.to_le_bytes());
+            body.extend_from_slice(&map.height.to_le_bytes());
+            body.extend_from_slice(&(compressed.len() as i32).to_le_bytes());
+            body.extend_from_slice(&compressed);
+        }
+
+        // The header is padded to 8 bytes
+        let file_size = 8 + body.len() as u32;
+        writer.write_all(&file_size.to_le_bytes())?;
+        writer.write_all(&map_count.to_le_bytes())?;
+        writer.write_all(&[0, 0])?;
+        writer.write_all(&body)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::MapCache;
+    use crate::{Gat, test::gat_from_map};
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn write_map_cache() {
+        let gat = gat_from_map(&[".#", "~."]);
+
+        let mut map_cache = MapCache::new();
+        map_cache.push("test", &gat, None).unwrap();
+        assert!(map_cache.push("a_very_long_name", &gat, None).is_err());
+
+        let mut bytes = vec![];
+        map_cache.to_writer(&mut bytes).unwrap();
+
+        assert_eq!(&bytes[0..4], &(bytes.len() as u32).to_le_bytes());
+        assert_eq!(&bytes[4..6], &1u16.to_le_bytes());
+        assert_eq!(&bytes[8..13], b"test\0");
+        assert_eq!(&bytes[20..22], &2i16.to_le_bytes());
+        assert_eq!(&bytes[22..24], &2i16.to_le_bytes());
+        assert_eq!(&bytes[24..28], &(bytes.len() as i32 - 28).to_le_bytes());
+
+        let mut cells = vec![];
+        ZlibDecoder::new(&bytes[28..])
+            .read_to_end(&mut cells)
+            .unwrap();
+        assert_eq!(cells, [0, 1, 5, 0]);
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn water_cells() {
+        let mut bytes = b"GRAT".to_vec();
+        bytes.extend_from_slice(&[1, 2]);
+        bytes.extend_from_slice(&5u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for (altitudes, flags) in [
+            // Under the water
+            ([5f32, -5., -5., -5.], [0, 0, 0, 0]),
+            // Only the first altitude is checked
+            ([-5., 5., 5., 5.], [0, 0, 0, 0]),
+            // Only walkable cells become water
+            ([5., 5., 5., 5.], [1, 0, 0, 0]),
+            ([5., 5., 5., 5.], [0, 0, 0, 0x80]),
+            // On the water level
+            ([2., 2., 2., 2.], [0, 0, 0, 0]),
+        ] {
+            for altitude in altitudes {
+                bytes.extend_from_slice(&altitude.to_le_bytes());
+            }
+            bytes.extend_from_slice(&flags);
+        }
+        let gat = Gat::from_reader(&mut bytes.as_slice()).unwrap();
+
+        let mut map_cache = MapCache::new();
+        map_cache.push("prt_fild08", &gat, Some(2.)).unwrap();
+        let mut bytes = vec![];
+        map_cache.to_writer(&mut bytes).unwrap();
+
+        // What rathena's `mapcache` writes for these tiles
+        assert_eq!(&bytes[8..20], b"prt_fild08\0\0");
+        assert_eq!(&bytes[20..24], &[5, 0, 1, 0]);
+        let mut cells = vec![];
+        ZlibDecoder::new(&bytes[28..])
+            .read_to_end(&mut cells)
+            .unwrap();
+        assert_eq!(cells, [3, 0, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,246 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Gat;
+
+/// Cost of moving to an orthogonal neighbor
+pub const MOVE_COST: u32 = 10;
+/// Cost of moving to a diagonal neighbor
+pub const MOVE_DIAGONAL_COST: u32 = 14;
+
+/// Position of a [`Tile`](crate::Tile) on a [`Gat`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cell {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Cell {
+    pub const fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+
+    /// Number of steps between two cells when moving in 8 directions
+    pub fn distance(&self, other: Cell) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+/// A node on the open list of the pathfinder
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Node {
+    /// Estimated cost of the path going through this node
+    f_cost: Reverse<u32>,
+    /// Estimated cost from this node to the goal, used to
+    /// break ties towards nodes that are closer to the goal
+    h_cost: Reverse<u32>,
+    index: usize,
+}
+
+impl Gat {
+    /// Returns the index of the tile at `cell` if it is inside the map
+    pub fn cell_index(&self, cell: Cell) -> Option<usize> {
+        if cell.x < self.width && cell.y < self.height {
+            Some(cell.y as usize * self.width as usize + cell.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell of the tile at `index`
+    pub fn index_cell(&self, index: usize) -> Cell {
+        let Ok(index) = u32::try_from(index) else {
+            unreachable!("Gat tiles should always be indexable by a u32.");
+        };
+        Cell::new(index % self.width, index / self.width)
+    }
+
+    /// Returns `true` if `cell` is inside the map and can be walked on
+    pub fn is_walkable(&self, cell: Cell) -> bool {
+        self.cell_index(cell)
+            .is_some_and(|index| self.tiles[index].tile_type().is_walkable())
+    }
+
+    /// Finds a path from `from` to `to` using A* with 8 direction movement.
+    ///
+    /// Follows the movement rules of Ragnarok Online, straight moves
+    /// cost [`MOVE_COST`], diagonal moves cost [`MOVE_DIAGONAL_COST`],
+    /// and diagonal moves can't cut through the corner of non-walkable cells.
+    ///
+    /// The search gives up on paths that are longer than `max_distance` steps.
+    ///
+    /// The returned path starts at `from` and ends at `to`.
+    pub fn find_path(&self, from: Cell, to: Cell, max_distance: u32) -> Option<Box<[Cell]>> {
+        if !self.is_walkable(from) || !self.is_walkable(to) || from.distance(to) > max_distance {
+            return None;
+        }
+
+        let (Some(start), Some(goal)) = (self.cell_index(from), self.cell_index(to)) else {
+            unreachable!("Walkable cells are always inside the map.");
+        };
+        if start == goal {
+            return Some(Box::new([from]));
+        }
+
+        let mut g_costs = vec![u32::MAX; self.tiles.len()];
+        let mut steps = vec![0u32; self.tiles.len()];
+        let mut parents = vec![usize::MAX; self.tiles.len()];
+        let mut open = BinaryHeap::new();
+
+        g_costs[start] = 0;
+        open.push(Node {
+            f_cost: Reverse(Self::heuristic(from, to)),
+            h_cost: Reverse(Self::heuristic(from, to)),
+            index: start,
+        });
+
+        while let Some(Node {
+            f_cost: Reverse(f_cost),
+            h_cost: Reverse(h_cost),
+            index,
+        }) = open.pop()
+        {
+            if index == goal {
+                return Some(self.build_path(&parents, goal));
+            }
+            // Skip stale entries of nodes that were reached through a cheaper path
+            if f_cost - h_cost > g_costs[index] {
+                continue;
+            }
+            if steps[index] >= max_distance {
+                continue;
+            }
+
+            let cell = self.index_cell(index);
+            for (dx, dy) in [
+                (0, 1),
+                (1, 0),
+                (0, -1),
+                (-1, 0),
+                (1, 1),
+                (1, -1),
+                (-1, -1),
+                (-1, 1),
+            ] {
+                let Some(neighbor) = Self::offset(cell, dx, dy) else {
+                    continue;
+                };
+                if !self.is_walkable(neighbor) {
+                    continue;
+                }
+
+                let move_cost = if dx != 0 && dy != 0 {
+                    // No cutting corners, both orthogonal cells must be walkable
+                    let corners_walkable = Self::offset(cell, dx, 0)
+                        .is_some_and(|corner| self.is_walkable(corner))
+                        && Self::offset(cell, 0, dy).is_some_and(|corner| self.is_walkable(corner));
+                    if !corners_walkable {
+                        continue;
+                    }
+                    MOVE_DIAGONAL_COST
+                } else {
+                    MOVE_COST
+                };
+
+                let Some(neighbor_index) = self.cell_index(neighbor) else {
+                    unreachable!("Walkable cells are always inside the map.");
+                };
+                let g_cost = g_costs[index] + move_cost;
+                if g_cost < g_costs[neighbor_index] {
+                    g_costs[neighbor_index] = g_cost;
+                    steps[neighbor_index] = steps[index] + 1;
+                    parents[neighbor_index] = index;
+
+                    let h_cost = Self::heuristic(neighbor, to);
+                    open.push(Node {
+                        f_cost: Reverse(g_cost + h_cost),
+                        h_cost: Reverse(h_cost),
+                        index: neighbor_index,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Octile distance between two cells
+    fn heuristic(from: Cell, to: Cell) -> u32 {
+        let dx = from.x.abs_diff(to.x);
+        let dy = from.y.abs_diff(to.y);
+        MOVE_COST * dx.max(dy) + (MOVE_DIAGONAL_COST - MOVE_COST) * dx.min(dy)
+    }
+
+    fn offset(cell: Cell, dx: i32, dy: i32) -> Option<Cell> {
+        Some(Cell::new(
+            cell.x.checked_add_signed(dx)?,
+            cell.y.checked_add_signed(dy)?,
+        ))
+    }
+
+    fn build_path(&self, parents: &[usize], goal: usize) -> Box<[Cell]> {
+        let mut path = vec![self.index_cell(goal)];
+        let mut current = goal;
+        while parents[current] != usize::MAX {
+            current = parents[current];
+            path.push(self.index_cell(current));
+        }
+        path.reverse();
+        path.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::gat_from_map;
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn straight_and_diagonal() {
+        let gat = gat_from_map(&["....", "....", "....", "...."]);
+
+        let path = gat.find_path(Cell::new(0, 0), Cell::new(3, 0), 10).unwrap();
+        assert_eq!(path.len(), 4);
+
+        let path = gat.find_path(Cell::new(0, 0), Cell::new(3, 3), 10).unwrap();
+        assert_eq!(
+            path.as_ref(),
+            &[
+                Cell::new(0, 0),
+                Cell::new(1, 1),
+                Cell::new(2, 2),
+                Cell::new(3, 3)
+            ]
+        );
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn no_corner_cutting() {
+        let gat = gat_from_map(&["..", "#."]);
+
+        let path = gat.find_path(Cell::new(0, 0), Cell::new(1, 1), 10).unwrap();
+        assert_eq!(
+            path.as_ref(),
+            &[Cell::new(0, 0), Cell::new(1, 0), Cell::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn unreachable_and_cutoff() {
+        let gat = gat_from_map(&["..#..", "..#..", "..#.."]);
+        assert!(
+            gat.find_path(Cell::new(0, 0), Cell::new(4, 0), 20)
+                .is_none()
+        );
+
+        let gat = gat_from_map(&[".....", "####.", "....."]);
+        assert!(
+            gat.find_path(Cell::new(0, 0), Cell::new(0, 2), 10)
+                .is_some()
+        );
+        assert!(gat.find_path(Cell::new(0, 0), Cell::new(0, 2), 9).is_none());
+    }
+}
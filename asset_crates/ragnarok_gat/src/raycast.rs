@@ -0,0 +1,198 @@
+use crate::{Cell, Gat, Tile};
+
+impl Gat {
+    /// Intersects the ray starting at `origin` going towards `direction` with
+    /// the terrain, in the same cell units as [`Gat::height_at`].
+    ///
+    /// The cells crossed by the ray are walked in order using a DDA, testing
+    /// only the two triangles of each cell. Returns the distance along the
+    /// ray, in multiples of `direction`, and the cell of the first hit, or
+    /// `None` if the ray misses the terrain.
+    pub fn raycast(&self, origin: [f32; 3], direction: [f32; 3]) -> Option<(f32, Cell)> {
+        let [ox, oy, oz] = origin;
+        let [dx, _, dz] = direction;
+
+        // Part of the ray above the map
+        let (x_enter, x_exit) = slab(ox, dx, self.width as f32)?;
+        let (z_enter, z_exit) = slab(oz, dz, self.height as f32)?;
+        let mut t = x_enter.max(z_enter).max(0.);
+        let t_end = x_exit.min(z_exit);
+        if t > t_end {
+            return None;
+        }
+
+        let step = |d: f32| if d < 0. { -1 } else { 1 };
+        let next_boundary = |o: f32, d: f32, cell: i64| {
+            if d == 0. {
+                f32::INFINITY
+            } else {
+                let boundary = if d > 0. { cell + 1 } else { cell } as f32;
+                (boundary - o) / d
+            }
+        };
+
+        let max_x = i64::from(self.width) - 1;
+        let max_z = i64::from(self.height) - 1;
+        let mut x = ((ox + dx * t).floor() as i64).clamp(0, max_x);
+        let mut z = ((oz + dz * t).floor() as i64).clamp(0, max_z);
+        let mut t_next_x = next_boundary(ox, dx, x);
+        let mut t_next_z = next_boundary(oz, dz, z);
+        let t_delta_x = (1. / dx).abs();
+        let t_delta_z = (1. / dz).abs();
+
+        loop {
+            let t_exit = t_next_x.min(t_next_z).min(t_end);
+
+            let cell = Cell::new(x as u32, z as u32);
+            if let Some(index) = self.cell_index(cell) {
+                let origin = [ox - x as f32, oy, oz - z as f32];
+                if let Some(hit) = intersect_tile(&self.tiles[index], origin, direction, t, t_exit)
+                {
+                    return Some((hit, cell));
+                }
+            }
+
+            if t_exit >= t_end {
+                return None;
+            }
+            t = t_exit;
+            if t_next_x < t_next_z {
+                x += step(dx);
+                t_next_x += t_delta_x;
+            } else {
+                z += step(dz);
+                t_next_z += t_delta_z;
+            }
+            if !(0..=max_x).contains(&x) || !(0..=max_z).contains(&z) {
+                return None;
+            }
+        }
+    }
+}
+
+/// Range of the ray between `0.` and `size` on one axis
+fn slab(origin: f32, direction: f32, size: f32) -> Option<(f32, f32)> {
+    if direction == 0. {
+        return (0. ..=size)
+            .contains(&origin)
+            .then_some((f32::NEG_INFINITY, f32::INFINITY));
+    }
+
+    let a = -origin / direction;
+    let b = (size - origin) / direction;
+    Some((a.min(b), a.max(b)))
+}
+
+/// First intersection between `t_min` and `t_max` of the ray with the triangles
+/// of `tile`, with `origin` relative to the corner of the cell.
+///
+/// The triangles are the same as [`Gat::height_at`], each is a plane
+/// `altitude = c + a * u + b * v` over its half of the cell.
+fn intersect_tile(
+    tile: &Tile,
+    origin: [f32; 3],
+    direction: [f32; 3],
+    t_min: f32,
+    t_max: f32,
+) -> Option<f32> {
+    const EPSILON: f32 = 1e-4;
+
+    let top_left = tile.top_left_altitude();
+    let top_right = tile.top_right_altitude();
+    let bottom_left = tile.bottom_left_altitude();
+    let bottom_right = tile.bottom_right_altitude();
+
+    let [ou, oy, ov] = origin;
+    let [du, dy, dv] = direction;
+
+    [
+        // Top left triangle, where u + v <= 1
+        (top_left, top_right - top_left, bottom_left - top_left, true),
+        // Bottom right triangle, where u + v >= 1
+        (
+            bottom_left + top_right - bottom_right,
+            bottom_right - bottom_left,
+            bottom_right - top_right,
+            false,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(c, a, b, top_left_half)| {
+        // oy + dy * t = c + a * (ou + du * t) + b * (ov + dv * t)
+        let denominator = dy - a * du - b * dv;
+        if denominator == 0. {
+            return None;
+        }
+        let t = (c + a * ou + b * ov - oy) / denominator;
+        if t < t_min - EPSILON || t > t_max + EPSILON {
+            return None;
+        }
+
+        let u = ou + du * t;
+        let v = ov + dv * t;
+        let in_cell =
+            (-EPSILON..=1. + EPSILON).contains(&u) && (-EPSILON..=1. + EPSILON).contains(&v);
+        let in_half = if top_left_half {
+            u + v <= 1. + EPSILON
+        } else {
+            u + v >= 1. - EPSILON
+        };
+        (in_cell && in_half).then_some(t)
+    })
+    .min_by(f32::total_cmp)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, Gat};
+
+    fn sloped_gat() -> Gat {
+        let mut bytes = b"GRAT".to_vec();
+        bytes.extend_from_slice(&[1, 2]);
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        for altitudes in [[0f32, 0., 0., 0.], [0., 2., 0., 2.], [0.; 4], [0.; 4]] {
+            for altitude in altitudes {
+                bytes.extend_from_slice(&altitude.to_le_bytes());
+            }
+            bytes.extend_from_slice(&[0; 4]);
+        }
+        let Ok(gat) = Gat::from_reader(&mut bytes.as_slice()) else {
+            unreachable!("Gat must be valid.");
+        };
+        gat
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn raycast_agrees_with_height() {
+        let gat = sloped_gat();
+
+        for (x, z) in [(0.5, 0.5), (1.25, 0.25), (1.75, 0.75), (1.5, 1.5)] {
+            let origin = [x - 0.1, -10., z + 0.1];
+            let direction = [0.1, 10., -0.1];
+            let (t, cell) = gat.raycast(origin, direction).unwrap();
+            let hit = [0, 1, 2].map(|i| origin[i] + direction[i] * t);
+
+            assert_eq!(cell, Cell::new(x as u32, z as u32));
+            let height = gat.height_at(hit[0], hit[2]).unwrap();
+            assert!((hit[1] - height).abs() < 1e-4, "{hit:?} {height}");
+        }
+    }
+
+    #[test]
+    fn raycast_misses() {
+        let gat = sloped_gat();
+
+        // Parallel to the ground
+        assert_eq!(gat.raycast([-1., -1., 0.5], [1., 0., 0.]), None);
+        // Pointing away from the map
+        assert_eq!(gat.raycast([0.5, -1., 0.5], [0., -1., 0.]), None);
+        assert_eq!(gat.raycast([-1., -1., 0.5], [-1., 1., 0.]), None);
+        // Straight down
+        assert_eq!(
+            gat.raycast([0.5, -1., 1.5], [0., 1., 0.]),
+            Some((1., Cell::new(0, 1)))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Cell, Gat};
+
+impl Gat {
+    /// Returns `true` if no cell on the straight line from `from` to `to`
+    /// is a wall.
+    ///
+    /// Like rathena's `path_search_long` with `CELL_CHKWALL`, only cells that
+    /// are neither walkable nor snipeable block the sight, snipeable cliffs do not.
+    pub fn has_line_of_sight(&self, from: Cell, to: Cell) -> bool {
+        self.line_of_cells(from, to, |tile_type| tile_type.is_snipeable())
+    }
+
+    /// Returns `true` if a projectile can travel from `from` and land on `to`.
+    ///
+    /// The line must be clear as for [`Gat::has_line_of_sight`], and `to`
+    /// must be walkable like rathena's `CELL_CHKNOREACH`, so snipeable cliffs
+    /// can be shot over but not at.
+    pub fn can_shoot(&self, from: Cell, to: Cell) -> bool {
+        self.is_walkable(to) && self.has_line_of_sight(from, to)
+    }
+
+    /// Walks the cells between `from` and `to`, checking if all of them pass `predicate`.
+    ///
+    /// Follows the same stepping as rathena's `path_search_long`, the line is always
+    /// walked from the cell with lowest `x`, and cells outside of the map
+    /// never pass the `predicate`.
+    fn line_of_cells(
+        &self,
+        from: Cell,
+        to: Cell,
+        predicate: impl Fn(crate::TileType) -> bool,
+    ) -> bool {
+        let passes = |cell: Cell| {
+            self.cell_index(cell)
+                .is_some_and(|index| predicate(self.tiles[index].tile_type()))
+        };
+
+        if !passes(to) {
+            return false;
+        }
+        if from == to {
+            return true;
+        }
+
+        let (mut current, end) = if from.x <= to.x {
+            (from, to)
+        } else {
+            (to, from)
+        };
+
+        let dx = i64::from(end.x) - i64::from(current.x);
+        let dy = i64::from(end.y) - i64::from(current.y);
+        let weight = dx.max(dy.abs());
+
+        let mut wx = 0;
+        let mut wy = 0;
+        while current != end {
+            if !passes(current) {
+                return false;
+            }
+
+            wx += dx;
+            wy += dy;
+            if wx >= weight {
+                wx -= weight;
+                current.x += 1;
+            }
+            if wy >= weight {
+                wy -= weight;
+                current.y += 1;
+            } else if wy < 0 {
+                wy += weight;
+                current.y -= 1;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, test::gat_from_map};
+
+    #[test]
+    fn line_of_sight() {
+        let gat = gat_from_map(&[".....", "..~..", "..#..", "....."]);
+
+        assert!(gat.can_shoot(Cell::new(0, 1), Cell::new(4, 1)));
+        assert!(gat.has_line_of_sight(Cell::new(0, 1), Cell::new(4, 1)));
+        assert!(!gat.has_line_of_sight(Cell::new(0, 2), Cell::new(4, 2)));
+
+        assert!(!gat.can_shoot(Cell::new(0, 2), Cell::new(4, 2)));
+        assert!(!gat.can_shoot(Cell::new(4, 2), Cell::new(0, 2)));
+
+        assert!(gat.can_shoot(Cell::new(0, 0), Cell::new(4, 0)));
+        assert!(gat.has_line_of_sight(Cell::new(0, 3), Cell::new(4, 3)));
+        assert!(!gat.can_shoot(Cell::new(0, 0), Cell::new(2, 2)));
+    }
+
+    #[test]
+    fn sight_over_snipeable_cell() {
+        let gat = gat_from_map(&[".~."]);
+
+        assert!(gat.has_line_of_sight(Cell::new(0, 0), Cell::new(2, 0)));
+        assert!(gat.has_line_of_sight(Cell::new(2, 0), Cell::new(0, 0)));
+        assert!(gat.can_shoot(Cell::new(0, 0), Cell::new(2, 0)));
+        assert!(!gat.is_walkable(Cell::new(1, 0)));
+    }
+
+    #[test]
+    fn shoot_at_snipeable_cell() {
+        let gat = gat_from_map(&[".~."]);
+
+        assert!(gat.has_line_of_sight(Cell::new(0, 0), Cell::new(1, 0)));
+        assert!(!gat.can_shoot(Cell::new(0, 0), Cell::new(1, 0)));
+        assert!(!gat.can_shoot(Cell::new(1, 0), Cell::new(1, 0)));
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 use serde::{Deserialize, Serialize};
@@ -10,7 +10,9 @@ pub struct Tile {
     bottom_left_altitude: f32,
     bottom_right_altitude: f32,
     tile_type: u8,
-    is_water_tile: bool,
+    /// Unused bytes after the tile type, kept to write them back as they were read
+    padding: [u8; 2],
+    water_flag: u8,
 }
 
 impl Tile {
@@ -21,9 +23,8 @@ impl Tile {
         let bottom_right_altitude = reader.read_le_f32()?;
 
         let tile_type = reader.read_u8()?;
-        let _ = reader.read_u8()?;
-        let _ = reader.read_u8()?;
-        let is_water_tile = reader.read_u8()? == 0x80;
+        let padding = reader.read_array()?;
+        let water_flag = reader.read_u8()?;
 
         Ok(Self {
             top_left_altitude,
@@ -31,10 +32,24 @@ impl Tile {
             bottom_left_altitude,
             bottom_right_altitude,
             tile_type,
-            is_water_tile,
+            padding,
+            water_flag,
         })
     }
 
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), super::Error> {
+        writer.write_all(&self.top_left_altitude.to_le_bytes())?;
+        writer.write_all(&self.top_right_altitude.to_le_bytes())?;
+        writer.write_all(&self.bottom_left_altitude.to_le_bytes())?;
+        writer.write_all(&self.bottom_right_altitude.to_le_bytes())?;
+
+        writer.write_all(&[self.tile_type])?;
+        writer.write_all(&self.padding)?;
+        writer.write_all(&[self.water_flag])?;
+
+        Ok(())
+    }
+
     pub fn top_left_altitude(&self) -> f32 {
         self.top_left_altitude
     }
@@ -51,24 +66,162 @@ impl Tile {
         self.bottom_right_altitude
     }
 
-    pub fn tile_type(&self) -> u8 {
+    pub fn tile_type(&self) -> TileType {
+        TileType::from(self.tile_type)
+    }
+
+    /// The raw byte of the tile type, as stored in the [`Gat`](crate::Gat)
+    pub fn raw_tile_type(&self) -> u8 {
         self.tile_type
     }
 
+    /// The four bytes following the altitudes as a single value, which is
+    /// how rathena's `mapcache` reads the tile type
+    #[cfg(feature = "mapcache")]
+    pub(crate) fn raw_cell_flags(&self) -> u32 {
+        u32::from_le_bytes([
+            self.tile_type,
+            self.padding[0],
+            self.padding[1],
+            self.water_flag,
+        ])
+    }
+
     pub fn is_water_tile(&self) -> bool {
-        self.is_water_tile
+        self.water_flag == 0x80
+    }
+
+    /// Changes the type of the tile, setting the water flag if the new type is water
+    pub fn set_tile_type(&mut self, tile_type: TileType) {
+        self.tile_type = tile_type.into();
+        self.water_flag = if tile_type.is_water() { 0x80 } else { 0 };
+    }
+
+    /// Sets the altitudes of the four corners of the tile
+    pub fn set_altitudes(
+        &mut self,
+        top_left: f32,
+        top_right: f32,
+        bottom_left: f32,
+        bottom_right: f32,
+    ) {
+        self.top_left_altitude = top_left;
+        self.top_right_altitude = top_right;
+        self.bottom_left_altitude = bottom_left;
+        self.bottom_right_altitude = bottom_right;
     }
 }
 
-// TODO change type of member `tile_type` on `Tile` after Bevy implements
-// foreign type reflection
+/// Type of a [`Tile`], defining if it can be walked on and if projectiles
+/// can go through it.
+///
+/// The names are the ones used by map editors, what each type allows follows
+/// rathena's `map_gat2cell` so that the client and the server agree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TileType {
+    /// `0`, walkable and snipeable
     WalkableBlock,
+    /// `1`, neither walkable nor snipeable
     NonWalkableBlock,
+    /// `2`, walkable and snipeable. Despite its name, rathena handles it
+    /// like [`TileType::WalkableBlock`], it is neither blocked nor water
     NonWalkableWater,
+    /// `3`, walkable, snipeable and water
     WalkableWater,
+    /// `4`, walkable and snipeable. Despite its name, rathena handles it
+    /// like [`TileType::WalkableBlock`], it is neither blocked nor water
     SnipeableNonWalkableWater,
+    /// `5`, snipeable
     SnipeableCliff,
+    /// `6`, walkable and snipeable. Despite its name, rathena handles it
+    /// like [`TileType::WalkableBlock`], it does not block anything
     Cliff,
-    Unknown,
+    /// Any other value, neither walkable nor snipeable
+    Unknown(u8),
+}
+
+impl TileType {
+    /// Actors can stand on and move through the tile
+    pub fn is_walkable(&self) -> bool {
+        matches!(
+            self,
+            Self::WalkableBlock
+                | Self::NonWalkableWater
+                | Self::WalkableWater
+                | Self::SnipeableNonWalkableWater
+                | Self::Cliff
+        )
+    }
+
+    /// Projectiles can go through the tile
+    pub fn is_snipeable(&self) -> bool {
+        self.is_walkable() || matches!(self, Self::SnipeableCliff)
+    }
+
+    /// The tile is covered by water
+    pub fn is_water(&self) -> bool {
+        matches!(self, Self::WalkableWater)
+    }
+}
+
+impl From<u8> for TileType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::WalkableBlock,
+            1 => Self::NonWalkableBlock,
+            2 => Self::NonWalkableWater,
+            3 => Self::WalkableWater,
+            4 => Self::SnipeableNonWalkableWater,
+            5 => Self::SnipeableCliff,
+            6 => Self::Cliff,
+            unknown => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<TileType> for u8 {
+    fn from(value: TileType) -> Self {
+        match value {
+            TileType::WalkableBlock => 0,
+            TileType::NonWalkableBlock => 1,
+            TileType::NonWalkableWater => 2,
+            TileType::WalkableWater => 3,
+            TileType::SnipeableNonWalkableWater => 4,
+            TileType::SnipeableCliff => 5,
+            TileType::Cliff => 6,
+            TileType::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TileType;
+
+    fn cell(raw: u8) -> (bool, bool, bool) {
+        let tile_type = TileType::from(raw);
+        (
+            tile_type.is_walkable(),
+            tile_type.is_snipeable(),
+            tile_type.is_water(),
+        )
+    }
+
+    #[test]
+    fn tile_types() {
+        // (walkable, snipeable, water) as set by rathena's `map_gat2cell`
+        let expected = [
+            (0, (true, true, false)),
+            (1, (false, false, false)),
+            (2, (true, true, false)),
+            (3, (true, true, true)),
+            (4, (true, true, false)),
+            (5, (false, true, false)),
+            (6, (true, true, false)),
+            (7, (false, false, false)),
+        ];
+        for (raw, cell_flags) in expected {
+            assert_eq!(cell(raw), cell_flags, "Tile type {raw}");
+        }
+    }
 }
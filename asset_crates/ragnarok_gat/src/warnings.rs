@@ -1,8 +1,8 @@
-use std::{fmt::Display, ops::Deref};
+use std::{collections::BTreeMap, fmt::Display, ops::Deref};
 
 use ragnarok_rebuild_common::warning::ReportWarning;
 
-use crate::Gat;
+use crate::{Gat, TileType};
 
 const AXIS_LIMIT: u32 = 416;
 
@@ -25,11 +25,26 @@ impl GatReport<'_> {
         }
         Ok(())
     }
+
+    fn report_unknown_tile_types(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut unknown_tile_types = BTreeMap::new();
+        for tile in self.tiles.iter() {
+            if let TileType::Unknown(tile_type) = tile.tile_type() {
+                *unknown_tile_types.entry(tile_type).or_insert(0usize) += 1;
+            }
+        }
+
+        for (tile_type, count) in unknown_tile_types {
+            writeln!(f, "has {count} tiles with unknown type {tile_type}.")?;
+        }
+        Ok(())
+    }
 }
 
 impl Display for GatReport<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.report_axis(f)?;
+        self.report_unknown_tile_types(f)?;
         Ok(())
     }
 }
@@ -0,0 +1,122 @@
+//! Debug tool for the glTF export of Ragnarok Online maps, and the import
+//! of glTF models.
+//!
+//! ## Usage
+//!
+//! * `gltf_debug <map> <output>`: Exports the map `map` of `data.grf`, i.e. `prontera`,
+//!   to the binary glTF `output`.
+//! * `gltf_debug rsm <gltf> <output> <1.4|2.3>`: Imports the glTF `gltf` as the Rsm
+//!   `output` of the given version, its textures are written in `texture/` next to `output`.
+
+#![expect(clippy::unwrap_used, reason = "This is a test")]
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufWriter, Cursor},
+    path::Path,
+};
+
+use ragnarok_gltf::{Map, RsmImport, RsmVersion};
+use ragnarok_gnd::Gnd;
+use ragnarok_grf::Grf;
+use ragnarok_rsm::Rsm;
+use ragnarok_rsw::Rsw;
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["rsm", gltf, output, "1.4"] => {
+            import(Path::new(gltf), Path::new(output), RsmVersion::V1_4)
+        }
+        ["rsm", gltf, output, "2.3"] => {
+            import(Path::new(gltf), Path::new(output), RsmVersion::V2_3)
+        }
+        [map, output] => export(map, Path::new(output)),
+        _ => {
+            println!("Usage: gltf_debug <map> <output>");
+            println!("       gltf_debug rsm <gltf> <output> <1.4|2.3>");
+        }
+    }
+}
+
+fn export(map: &str, output: &Path) {
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+
+    let rsw_content = grf
+        .read_file(Path::new(&format!("data/{map}.rsw")))
+        .unwrap();
+    let rsw = Rsw::from_reader(&mut Cursor::new(&rsw_content)).unwrap();
+    let gnd_content = grf
+        .read_file(Path::new(&format!("data/{}", rsw.gnd_file)))
+        .unwrap();
+    let gnd = Gnd::from_reader(&mut Cursor::new(&gnd_content)).unwrap();
+
+    let mut rsms = HashMap::new();
+    for model in rsw.models.iter() {
+        if rsms.contains_key(&model.filename) {
+            continue;
+        }
+        let rsm_filename = format!("data/model/{}", model.filename);
+        let Ok(rsm_content) = grf
+            .read_file(Path::new(&rsm_filename))
+            .inspect_err(|err| println!("{rsm_filename:?}: {err}"))
+        else {
+            continue;
+        };
+        let Ok(rsm) = Rsm::from_reader(&mut Cursor::new(&rsm_content))
+            .inspect_err(|err| println!("{rsm_filename:?}: {err}"))
+        else {
+            continue;
+        };
+        rsms.insert(model.filename.clone(), rsm);
+    }
+
+    let mut texture_source = |path: &str| {
+        grf.read_file(Path::new(&format!("data/texture/{path}")))
+            .inspect_err(|err| println!("data/texture/{path:?}: {err}"))
+            .ok()
+            .map(Vec::from)
+    };
+
+    let mut writer = BufWriter::new(File::create(output).unwrap());
+    Map {
+        rsw: &rsw,
+        gnd: &gnd,
+        rsms: &rsms,
+    }
+    .to_glb(&mut texture_source, &mut writer)
+    .unwrap();
+}
+
+fn import(gltf: &Path, output: &Path, version: RsmVersion) {
+    let content = fs::read(gltf).unwrap();
+    let gltf_directory = gltf.parent().unwrap_or(Path::new(""));
+    let mut uri_source = |uri: &str| {
+        fs::read(gltf_directory.join(uri))
+            .inspect_err(|err| println!("{uri:?}: {err}"))
+            .ok()
+    };
+
+    let imported = RsmImport { version, scale: 1. }
+        .import(&content, &mut uri_source)
+        .unwrap();
+
+    let mut writer = BufWriter::new(File::create(output).unwrap());
+    imported.rsm.to_writer(&mut writer).unwrap();
+
+    let texture_directory = output.parent().unwrap_or(Path::new("")).join("texture");
+    for (path, bmp) in imported.textures {
+        let texture_path = texture_directory.join(&*path);
+        if let Some(parent) = texture_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(texture_path, bmp).unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+use std::io::Write;
+
+use serde_json::{Map, Value, json};
+
+use crate::Error;
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_VERSION: u32 = 2;
+const GLB_HEADER_SIZE: usize = 12;
+const CHUNK_HEADER_SIZE: usize = 8;
+const JSON_CHUNK: u32 = 0x4E4F_534A;
+const BIN_CHUNK: u32 = 0x004E_4942;
+
+/// `componentType` of the accessors
+pub(crate) const UNSIGNED_SHORT: u32 = 5123;
+pub(crate) const UNSIGNED_INT: u32 = 5125;
+pub(crate) const FLOAT: u32 = 5126;
+
+/// `target` of the buffer views
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Accumulates the objects of a glTF and the content of its single binary buffer
+#[derive(Debug, Default)]
+pub(crate) struct GltfBuilder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    images: Vec<Value>,
+    samplers: Vec<Value>,
+    textures: Vec<Value>,
+    materials: Vec<Value>,
+    meshes: Vec<Value>,
+    nodes: Vec<Value>,
+    animations: Vec<Value>,
+    lights: Vec<Value>,
+}
+
+impl GltfBuilder {
+    fn push_buffer_view(&mut self, data: &[u8], target: Option<u32>) -> usize {
+        // Accessors require their data to be aligned to their component size
+        self.buffer.resize(self.buffer.len().next_multiple_of(4), 0);
+
+        let mut buffer_view = json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": data.len(),
+        });
+        if let Some(target) = target {
+            buffer_view["target"] = json!(target);
+        }
+        self.buffer.extend_from_slice(data);
+
+        self.buffer_views.push(buffer_view);
+        self.buffer_views.len() - 1
+    }
+
+    fn push_accessor(
+        &mut self,
+        data: &[u8],
+        target: Option<u32>,
+        component_type: u32,
+        count: usize,
+        kind: &str,
+    ) -> usize {
+        let buffer_view = self.push_buffer_view(data, target);
+        self.accessors.push(json!({
+            "bufferView": buffer_view,
+            "componentType": component_type,
+            "count": count,
+            "type": kind,
+        }));
+        self.accessors.len() - 1
+    }
+
+    /// Pushes positions, their accessor includes the bounds required by glTF
+    pub fn push_positions(&mut self, positions: &[[f32; 3]]) -> usize {
+        let accessor = self.push_vertex_attribute(positions);
+
+        let (min, max) =
+            positions
+                .iter()
+                .fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), position| {
+                    (
+                        std::array::from_fn(|i| min[i].min(position[i])),
+                        std::array::from_fn(|i| max[i].max(position[i])),
+                    )
+                });
+        self.accessors[accessor]["min"] = json!(min);
+        self.accessors[accessor]["max"] = json!(max);
+
+        accessor
+    }
+
+    pub fn push_vertex_attribute<const N: usize>(&mut self, values: &[[f32; N]]) -> usize {
+        let data = values
+            .iter()
+            .flatten()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.push_accessor(
+            &data,
+            Some(ARRAY_BUFFER),
+            FLOAT,
+            values.len(),
+            Self::vector_type(N),
+        )
+    }
+
+    pub fn push_indices_u16(&mut self, indices: &[u16]) -> usize {
+        let data = indices
+            .iter()
+            .flat_map(|index| index.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.push_accessor(
+            &data,
+            Some(ELEMENT_ARRAY_BUFFER),
+            UNSIGNED_SHORT,
+            indices.len(),
+            "SCALAR",
+        )
+    }
+
+    pub fn push_indices_u32(&mut self, indices: &[u32]) -> usize {
+        let data = indices
+            .iter()
+            .flat_map(|index| index.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.push_accessor(
+            &data,
+            Some(ELEMENT_ARRAY_BUFFER),
+            UNSIGNED_INT,
+            indices.len(),
+            "SCALAR",
+        )
+    }
+
+    /// Pushes the key frame times of an animation sampler, their accessor
+    /// includes the bounds required by glTF
+    pub fn push_key_frame_times(&mut self, times: &[f32]) -> usize {
+        let data = times
+            .iter()
+            .flat_map(|time| time.to_le_bytes())
+            .collect::<Vec<_>>();
+        let accessor = self.push_accessor(&data, None, FLOAT, times.len(), "SCALAR");
+
+        let min = times.iter().copied().fold(f32::MAX, f32::min);
+        let max = times.iter().copied().fold(f32::MIN, f32::max);
+        self.accessors[accessor]["min"] = json!([min]);
+        self.accessors[accessor]["max"] = json!([max]);
+
+        accessor
+    }
+
+    pub fn push_key_frame_values<const N: usize>(&mut self, values: &[[f32; N]]) -> usize {
+        let data = values
+            .iter()
+            .flatten()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.push_accessor(&data, None, FLOAT, values.len(), Self::vector_type(N))
+    }
+
+    pub fn push_image(&mut self, data: &[u8], mime_type: &str) -> usize {
+        let buffer_view = self.push_buffer_view(data, None);
+        self.images.push(json!({
+            "bufferView": buffer_view,
+            "mimeType": mime_type,
+        }));
+        self.images.len() - 1
+    }
+
+    /// Pushes a texture sampling `image` with a repeating sampler
+    pub fn push_texture(&mut self, image: usize) -> usize {
+        if self.samplers.is_empty() {
+            // Linear filtering with mipmaps, repeating in both directions
+            self.samplers.push(json!({
+                "magFilter": 9729,
+                "minFilter": 9987,
+                "wrapS": 10497,
+                "wrapT": 10497,
+            }));
+        }
+        self.textures.push(json!({
+            "sampler": 0,
+            "source": image,
+        }));
+        self.textures.len() - 1
+    }
+
+    pub fn push_material(&mut self, material: Value) -> usize {
+        self.materials.push(material);
+        self.materials.len() - 1
+    }
+
+    pub fn push_mesh(&mut self, mesh: Value) -> usize {
+        self.meshes.push(mesh);
+        self.meshes.len() - 1
+    }
+
+    pub fn push_node(&mut self, node: Value) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Sets the children of a node pushed earlier
+    pub fn set_children(&mut self, node: usize, children: Vec<usize>) {
+        if !children.is_empty() {
+            self.nodes[node]["children"] = json!(children);
+        }
+    }
+
+    pub fn push_animation(&mut self, animation: Value) -> usize {
+        self.animations.push(animation);
+        self.animations.len() - 1
+    }
+
+    /// Pushes a light of the `KHR_lights_punctual` extension
+    pub fn push_light(&mut self, light: Value) -> usize {
+        self.lights.push(light);
+        self.lights.len() - 1
+    }
+
+    /// Writes the glTF as a binary glTF with a single scene made of `roots`
+    pub fn write_glb(self, roots: &[usize], writer: &mut dyn Write) -> Result<(), Error> {
+        let mut root = Map::new();
+        root.insert(
+            "asset".to_owned(),
+            json!({
+                "version": "2.0",
+                "generator": concat!("ragnarok_gltf ", env!("CARGO_PKG_VERSION")),
+            }),
+        );
+        root.insert("scene".to_owned(), json!(0));
+        root.insert("scenes".to_owned(), json!([{ "nodes": roots }]));
+
+        for (name, values) in [
+            ("bufferViews", self.buffer_views),
+            ("accessors", self.accessors),
+            ("images", self.images),
+            ("samplers", self.samplers),
+            ("textures", self.textures),
+            ("materials", self.materials),
+            ("meshes", self.meshes),
+            ("nodes", self.nodes),
+            ("animations", self.animations),
+        ] {
+            // Top level arrays can't be empty
+            if !values.is_empty() {
+                root.insert(name.to_owned(), Value::Array(values));
+            }
+        }
+        if !self.lights.is_empty() {
+            root.insert("extensionsUsed".to_owned(), json!(["KHR_lights_punctual"]));
+            root.insert(
+                "extensions".to_owned(),
+                json!({ "KHR_lights_punctual": { "lights": self.lights } }),
+            );
+        }
+
+        let mut buffer = self.buffer;
+        if !buffer.is_empty() {
+            root.insert(
+                "buffers".to_owned(),
+                json!([{ "byteLength": buffer.len() }]),
+            );
+        }
+
+        let mut json = serde_json::to_vec(&Value::Object(root))?;
+        json.resize(json.len().next_multiple_of(4), b' ');
+        buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+        let mut length = GLB_HEADER_SIZE + CHUNK_HEADER_SIZE + json.len();
+        if !buffer.is_empty() {
+            length += CHUNK_HEADER_SIZE + buffer.len();
+        }
+        let Ok(length) = u32::try_from(length) else {
+            return Err(Error::TooLarge(length));
+        };
+
+        writer.write_all(GLB_MAGIC)?;
+        writer.write_all(&GLB_VERSION.to_le_bytes())?;
+        writer.write_all(&length.to_le_bytes())?;
+
+        // Chunk lengths are bounded by the total length
+        writer.write_all(&(json.len() as u32).to_le_bytes())?;
+        writer.write_all(&JSON_CHUNK.to_le_bytes())?;
+        writer.write_all(&json)?;
+
+        if !buffer.is_empty() {
+            writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+            writer.write_all(&BIN_CHUNK.to_le_bytes())?;
+            writer.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn vector_type(components: usize) -> &'static str {
+        match components {
+            1 => "SCALAR",
+            2 => "VEC2",
+            3 => "VEC3",
+            4 => "VEC4",
+            _ => unreachable!("glTF vertex attributes have between 1 and 4 components."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{Value, json};
+
+    use super::GltfBuilder;
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn glb_layout() {
+        let mut builder = GltfBuilder::default();
+        let positions = builder.push_positions(&[[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]]);
+        let indices = builder.push_indices_u16(&[0, 1, 2]);
+        let mesh = builder.push_mesh(json!({
+            "primitives": [{ "attributes": { "POSITION": positions }, "indices": indices }]
+        }));
+        let node = builder.push_node(json!({ "mesh": mesh }));
+
+        let mut glb = vec![];
+        builder.write_glb(&[node], &mut glb).unwrap();
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(
+            u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize,
+            glb.len()
+        );
+
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        assert_eq!(json_length % 4, 0);
+        let json: Value = serde_json::from_slice(&glb[20..(20 + json_length)]).unwrap();
+        assert_eq!(json["accessors"][0]["max"], json!([1., 0., 1.]));
+        // Indices start aligned after the 36 bytes of positions
+        assert_eq!(json["bufferViews"][1]["byteOffset"], json!(36));
+        assert_eq!(json["buffers"][0]["byteLength"], json!(42));
+
+        let bin_length = u32::from_le_bytes(
+            glb[(20 + json_length)..(24 + json_length)]
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(bin_length, 44);
+    }
+}
@@ -0,0 +1,55 @@
+use std::{fmt::Display, io};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+    TooLarge(usize),
+    Gltf(gltf::Error),
+    MissingBinaryChunk,
+    MissingFile(Box<str>),
+    MissingScene,
+    TooManyVertices(Box<str>),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<gltf::Error> for Error {
+    fn from(value: gltf::Error) -> Self {
+        Self::Gltf(value)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Could not write glTF due to IO error. '{err}'"),
+            Self::Json(err) => write!(f, "Could not serialize glTF. '{err}'"),
+            Self::TooLarge(size) => write!(
+                f,
+                "glTF of {size} bytes is too large to be written as a binary glTF."
+            ),
+            Self::Gltf(err) => write!(f, "Could not read glTF. '{err}'"),
+            Self::MissingBinaryChunk => {
+                write!(f, "Binary glTF does not have the binary chunk it uses.")
+            }
+            Self::MissingFile(uri) => write!(f, "Could not read {uri:?} used by the glTF."),
+            Self::MissingScene => write!(f, "glTF does not have a scene to import."),
+            Self::TooManyVertices(node) => {
+                write!(f, "Node {node:?} has too many vertices for a Rsm mesh.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
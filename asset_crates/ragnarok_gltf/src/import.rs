@@ -0,0 +1,778 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+};
+
+use glam::{Mat3, Quat, Vec3};
+use gltf::{
+    Gltf, Node, Primitive,
+    animation::{Interpolation, Property, util::ReadOutputs},
+    buffer, image as gltf_image,
+    mesh::Mode,
+};
+use image::{DynamicImage, ImageFormat, Rgb};
+use ragnarok_rebuild_common::Version;
+use ragnarok_rsm::{
+    AnimationDuration, Rsm, ShadeType,
+    mesh::{
+        Face, Mesh, PositionKeyFrame, RotationKeyFrame, ScaleKeyFrame, TextureUV,
+        Textures as MeshTextures, Transformation,
+    },
+};
+
+use crate::Error;
+
+/// Ragnarok Online has Y going down, which is a rotation of
+/// half a turn around X from glTF
+const Y_DOWN: Quat = Quat::from_xyzw(1., 0., 0., 0.);
+
+/// Reads the content of a file given the URI a glTF uses to reference it
+pub type UriSource<'a> = dyn FnMut(&str) -> Option<Vec<u8>> + 'a;
+
+/// Version of the [`Rsm`] created by a [`RsmImport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsmVersion {
+    /// Meshes keep the transform of their node, but only the root mesh can
+    /// be moved by the animation and meshes can't be scaled by it.
+    V1_4,
+    /// Meshes are only moved by their node, the rotation and scale of
+    /// nodes without rotation or scale animations are baked into the
+    /// transformation matrix of their mesh and the positions of their
+    /// children.
+    V2_3,
+}
+
+impl RsmVersion {
+    /// Frames per second of the animations of Rsm 2.x
+    const FRAMES_PER_SECOND: f32 = 60.;
+
+    fn version(self) -> Version {
+        match self {
+            Self::V1_4 => Version(1, 4, 0),
+            Self::V2_3 => Version(2, 3, 0),
+        }
+    }
+
+    fn frame(self, time: f32) -> i32 {
+        match self {
+            Self::V1_4 => (time * 1000.).round() as i32,
+            Self::V2_3 => (time * Self::FRAMES_PER_SECOND).round() as i32,
+        }
+    }
+
+    fn animation_duration(self, duration: f32) -> AnimationDuration {
+        match self {
+            Self::V1_4 => AnimationDuration::Simple(duration * 1000.),
+            Self::V2_3 => AnimationDuration::PerSecond(
+                (duration * Self::FRAMES_PER_SECOND).round(),
+                Self::FRAMES_PER_SECOND,
+            ),
+        }
+    }
+}
+
+/// Import of the default scene of a glTF as a [`Rsm`].
+///
+/// Each node becomes a [`Mesh`] named after it, and each primitive of its
+/// mesh needs a material with a base color texture. The first animation
+/// of the glTF animates the translation, rotation and scale of the meshes.
+pub struct RsmImport {
+    pub version: RsmVersion,
+    /// Scale applied to the scene, from the units of the glTF to the units
+    /// of Ragnarok Online
+    pub scale: f32,
+}
+
+/// A [`Rsm`] imported from a glTF
+#[derive(Debug)]
+pub struct ImportedRsm {
+    pub rsm: Rsm,
+    /// Content of the textures used by the [`Rsm`] converted to `.bmp`,
+    /// by their path relative to `data/texture/`
+    pub textures: Vec<(Box<str>, Vec<u8>)>,
+}
+
+impl RsmImport {
+    /// Converts the `.gltf` or `.glb` in `content`.
+    ///
+    /// External buffers and images are read through `uri_source`, data
+    /// URIs are not supported. Textures that can't be read are only
+    /// referenced by the [`Rsm`].
+    pub fn import(&self, content: &[u8], uri_source: &mut UriSource) -> Result<ImportedRsm, Error> {
+        let gltf = Gltf::from_slice(content)?;
+        let buffers = gltf
+            .buffers()
+            .map(|buffer| match buffer.source() {
+                buffer::Source::Bin => gltf.blob.clone().ok_or(Error::MissingBinaryChunk),
+                buffer::Source::Uri(uri) => {
+                    uri_source(uri).ok_or_else(|| Error::MissingFile(uri.into()))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(scene) = gltf.default_scene().or_else(|| gltf.scenes().next()) else {
+            return Err(Error::MissingScene);
+        };
+
+        let mut importer = Importer {
+            version: self.version,
+            scale: self.scale,
+            buffers: &buffers,
+            uri_source,
+            animations: HashMap::new(),
+            duration: 0.,
+            names: HashSet::new(),
+            images: HashMap::new(),
+            texture_paths: Vec::new(),
+            textures: Vec::new(),
+            meshes: Vec::new(),
+            position_key_frames: Vec::new(),
+        };
+        importer.read_animations(&gltf);
+
+        let roots = scene.nodes().collect::<Vec<_>>();
+        let root_meshes = match (self.version, roots.as_slice()) {
+            (RsmVersion::V1_4, [root]) => {
+                vec![importer.push_node(root, "", Mat3::IDENTITY, true)?]
+            }
+            // Rsm 1.x have a single root mesh
+            (RsmVersion::V1_4, roots) => {
+                let name = importer.unique_name(scene.name().unwrap_or("root"));
+                importer.meshes.push(Mesh {
+                    name: name.clone(),
+                    parent_name: "".into(),
+                    textures: MeshTextures::Indexes([].into()),
+                    transformation_matrix: Mat3::IDENTITY.to_cols_array(),
+                    transformation: Transformation::Complete {
+                        offset: [0.; 3],
+                        position: [0.; 3],
+                        rotation_angle: 0.,
+                        rotation_axis: [0.; 3],
+                        scale: [1.; 3],
+                    },
+                    vertices: [].into(),
+                    uvs: [].into(),
+                    faces: [].into(),
+                    scale_key_frames: [].into(),
+                    rotation_key_frames: [].into(),
+                    position_key_frames: [].into(),
+                    texture_animations: [].into(),
+                });
+                for root in roots {
+                    importer.push_node(root, &name, Mat3::IDENTITY, false)?;
+                }
+                vec![name]
+            }
+            (RsmVersion::V2_3, roots) => roots
+                .iter()
+                .map(|root| importer.push_node(root, "", Mat3::IDENTITY, false))
+                .collect::<Result<_, _>>()?,
+        };
+        for node in importer.animations.keys() {
+            log::warn!("Animation of node {node} is not on the imported scene, it was dropped.");
+        }
+
+        // Rsm 2.x reference the paths of their textures in their meshes
+        let textures = match self.version {
+            RsmVersion::V1_4 => importer.texture_paths.into(),
+            RsmVersion::V2_3 => [].into(),
+        };
+        Ok(ImportedRsm {
+            rsm: Rsm {
+                signature: "GRSM".into(),
+                version: self.version.version(),
+                animation_duration: self.version.animation_duration(importer.duration),
+                shade_type: ShadeType::Flat,
+                alpha: 0xff,
+                textures,
+                root_meshes: root_meshes.into(),
+                meshes: importer.meshes.into(),
+                position_key_frames: importer.position_key_frames.into(),
+                volume_boxes: Some([].into()),
+            },
+            textures: importer.textures,
+        })
+    }
+}
+
+/// Translation, rotation and scale key frames of a node, in the
+/// space of Ragnarok Online
+#[derive(Debug, Default)]
+struct NodeAnimation {
+    translations: Vec<(f32, Vec3)>,
+    rotations: Vec<(f32, Quat)>,
+    scales: Vec<(f32, Vec3)>,
+}
+
+/// Vertices and faces of the primitives of a node
+#[derive(Debug, Default)]
+struct Geometry {
+    vertices: Vec<[f32; 3]>,
+    uvs: Vec<TextureUV>,
+    faces: Vec<Face>,
+    /// Indexes of the textures used by the faces, in [`Importer::texture_paths`]
+    textures: Vec<usize>,
+}
+
+struct Importer<'a, 'b, 'c> {
+    version: RsmVersion,
+    scale: f32,
+    buffers: &'a [Vec<u8>],
+    uri_source: &'b mut UriSource<'c>,
+    animations: HashMap<usize, NodeAnimation>,
+    duration: f32,
+    names: HashSet<Box<str>>,
+    /// Index of the texture created for a glTF image
+    images: HashMap<usize, usize>,
+    texture_paths: Vec<Box<str>>,
+    textures: Vec<(Box<str>, Vec<u8>)>,
+    meshes: Vec<Mesh>,
+    position_key_frames: Vec<PositionKeyFrame>,
+}
+
+impl Importer<'_, '_, '_> {
+    fn read_animations(&mut self, gltf: &Gltf) {
+        let mut animations = gltf.animations();
+        let Some(animation) = animations.next() else {
+            return;
+        };
+        if animations.next().is_some() {
+            log::warn!(
+                "glTF has more than one animation, only {:?} is imported.",
+                animation.name().unwrap_or_default()
+            );
+        }
+
+        let buffers = self.buffers;
+        let scale = self.scale;
+        for channel in animation.channels() {
+            let reader = channel.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+            let (Some(times), Some(outputs)) = (reader.read_inputs(), reader.read_outputs()) else {
+                continue;
+            };
+            let times = times.collect::<Vec<_>>();
+            self.duration = times.iter().copied().fold(self.duration, f32::max);
+
+            let interpolation = channel.sampler().interpolation();
+            let node_animation = self
+                .animations
+                .entry(channel.target().node().index())
+                .or_default();
+            match (channel.target().property(), outputs) {
+                (Property::Translation, ReadOutputs::Translations(translations)) => {
+                    node_animation.translations = key_frames(
+                        &times,
+                        translations
+                            .map(|translation| Y_DOWN * Vec3::from_array(translation) * scale),
+                        interpolation,
+                    );
+                }
+                (Property::Rotation, ReadOutputs::Rotations(rotations)) => {
+                    node_animation.rotations = key_frames(
+                        &times,
+                        rotations
+                            .into_f32()
+                            .map(|rotation| to_ragnarok_rotation(Quat::from_array(rotation))),
+                        interpolation,
+                    );
+                }
+                (Property::Scale, ReadOutputs::Scales(scales)) => {
+                    node_animation.scales =
+                        key_frames(&times, scales.map(Vec3::from_array), interpolation);
+                }
+                _ => log::warn!("Rsm can't be animated by morph targets, they were dropped."),
+            }
+        }
+    }
+
+    /// Name of a mesh that isn't used by an other mesh, Rsm meshes are
+    /// linked to their parent by name
+    fn unique_name(&mut self, name: &str) -> Box<str> {
+        let mut unique_name: Box<str> = name.into();
+        let mut i = 1;
+        while self.names.contains(&unique_name) {
+            unique_name = format!("{name}_{i}").into();
+            i += 1;
+        }
+        self.names.insert(unique_name.clone());
+        unique_name
+    }
+
+    /// Pushes the mesh of `node` and of its children, `parent_linear` is
+    /// the rotation and scale baked from the ancestors of `node`.
+    ///
+    /// Returns the name of the mesh of `node`.
+    fn push_node(
+        &mut self,
+        node: &Node,
+        parent_name: &str,
+        parent_linear: Mat3,
+        is_root: bool,
+    ) -> Result<Box<str>, Error> {
+        let name = self.unique_name(
+            node.name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("node{}", node.index()))
+                .as_str(),
+        );
+
+        let (translation, rotation, scale) = node.transform().decomposed();
+        let translation = Y_DOWN * Vec3::from_array(translation) * self.scale;
+        let rotation = to_ragnarok_rotation(Quat::from_array(rotation));
+        let scale = Vec3::from_array(scale);
+
+        let mut geometry = Geometry::default();
+        if let Some(mesh) = node.mesh() {
+            for primitive in mesh.primitives() {
+                self.push_primitive(&name, &primitive, &mut geometry)?;
+            }
+        }
+
+        let animation = self.animations.remove(&node.index()).unwrap_or_default();
+        let version = self.version;
+        let frame = |time: f32| version.frame(time);
+        let (transformation, linear, key_frames) = match self.version {
+            RsmVersion::V1_4 => {
+                if !animation.scales.is_empty() {
+                    log::warn!("Rsm 1.4 can't animate the scale of {name}, it was dropped.");
+                }
+                if is_root {
+                    self.position_key_frames = dedup_frames(
+                        animation
+                            .translations
+                            .iter()
+                            .map(|(time, position)| PositionKeyFrame {
+                                frame: frame(*time),
+                                position: position.to_array(),
+                                data: 0.,
+                            }),
+                        |key_frame| key_frame.frame,
+                    );
+                } else if !animation.translations.is_empty() {
+                    log::warn!("Rsm 1.4 can only move its root mesh, {name} was not moved.");
+                }
+
+                let (rotation_axis, rotation_angle) = rotation.to_axis_angle();
+                (
+                    Transformation::Complete {
+                        offset: [0.; 3],
+                        position: translation.to_array(),
+                        rotation_angle,
+                        rotation_axis: rotation_axis.to_array(),
+                        scale: scale.to_array(),
+                    },
+                    Mat3::IDENTITY,
+                    (animation.rotations, Vec::new(), Vec::new()),
+                )
+            }
+            RsmVersion::V2_3 => {
+                let positions: Vec<(f32, Vec3)> = animation
+                    .translations
+                    .iter()
+                    .map(|(time, position)| (*time, parent_linear * *position))
+                    .collect();
+                if animation.rotations.is_empty() && animation.scales.is_empty() {
+                    let linear =
+                        parent_linear * Mat3::from_quat(rotation) * Mat3::from_diagonal(scale);
+                    (
+                        Transformation::Simple((parent_linear * translation).to_array()),
+                        linear,
+                        (Vec::new(), Vec::new(), positions),
+                    )
+                } else {
+                    // Animated rotations and scales replace the ones of the
+                    // node, so both need key frames
+                    let rotations = if animation.rotations.is_empty() {
+                        vec![(0., rotation)]
+                    } else {
+                        animation.rotations
+                    };
+                    let scales = if animation.scales.is_empty() {
+                        vec![(0., scale)]
+                    } else {
+                        animation.scales
+                    };
+                    (
+                        Transformation::Simple((parent_linear * translation).to_array()),
+                        parent_linear,
+                        (rotations, scales, positions),
+                    )
+                }
+            }
+        };
+        let (rotations, scales, positions) = key_frames;
+
+        let textures = match self.version {
+            RsmVersion::V1_4 => MeshTextures::Indexes(
+                geometry
+                    .textures
+                    .iter()
+                    .map(|texture| *texture as i32)
+                    .collect(),
+            ),
+            RsmVersion::V2_3 => MeshTextures::Paths(
+                geometry
+                    .textures
+                    .iter()
+                    .map(|texture| self.texture_paths[*texture].clone())
+                    .collect(),
+            ),
+        };
+        self.meshes.push(Mesh {
+            name: name.clone(),
+            parent_name: parent_name.into(),
+            textures,
+            transformation_matrix: linear.to_cols_array(),
+            transformation,
+            vertices: geometry.vertices.into(),
+            uvs: geometry.uvs.into(),
+            faces: geometry.faces.into(),
+            scale_key_frames: dedup_frames(
+                scales.iter().map(|(time, scale)| ScaleKeyFrame {
+                    frame: frame(*time),
+                    scale: scale.to_array(),
+                    data: 0.,
+                }),
+                |key_frame| key_frame.frame,
+            )
+            .into(),
+            rotation_key_frames: dedup_frames(
+                rotations.iter().map(|(time, rotation)| RotationKeyFrame {
+                    frame: frame(*time),
+                    quaternion: rotation.to_array(),
+                }),
+                |key_frame| key_frame.frame,
+            )
+            .into(),
+            position_key_frames: dedup_frames(
+                positions.iter().map(|(time, position)| PositionKeyFrame {
+                    frame: frame(*time),
+                    position: position.to_array(),
+                    data: 0.,
+                }),
+                |key_frame| key_frame.frame,
+            )
+            .into(),
+            texture_animations: [].into(),
+        });
+
+        for child in node.children() {
+            self.push_node(&child, &name, linear, false)?;
+        }
+
+        Ok(name)
+    }
+
+    fn push_primitive(
+        &mut self,
+        name: &str,
+        primitive: &Primitive,
+        geometry: &mut Geometry,
+    ) -> Result<(), Error> {
+        if primitive.mode() != Mode::Triangles {
+            log::warn!("A primitive of {name} is not made of triangles, it was dropped.");
+            return Ok(());
+        }
+        let material = primitive.material();
+        let Some(texture_info) = material.pbr_metallic_roughness().base_color_texture() else {
+            log::warn!("A primitive of {name} does not have a base color texture, it was dropped.");
+            return Ok(());
+        };
+
+        let buffers = self.buffers;
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+        let Some(positions) = reader.read_positions() else {
+            log::warn!("A primitive of {name} does not have positions, it was dropped.");
+            return Ok(());
+        };
+        let positions = positions
+            .map(|position| (Y_DOWN * Vec3::from_array(position) * self.scale).to_array())
+            .collect::<Vec<_>>();
+        let mut uvs = reader
+            .read_tex_coords(texture_info.tex_coord())
+            .map(|uvs| uvs.into_f32().collect::<Vec<_>>())
+            .unwrap_or_default();
+        uvs.resize(positions.len(), [0.; 2]);
+        let mut colors = reader
+            .read_colors(0)
+            .map(|colors| colors.into_rgba_u8().collect::<Vec<_>>())
+            .unwrap_or_default();
+        colors.resize(positions.len(), [0xff; 4]);
+        let indices = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+            None => (0..positions.len() as u32).collect(),
+        };
+
+        let first = geometry.vertices.len();
+        if first + positions.len() > usize::from(u16::MAX) + 1 {
+            return Err(Error::TooManyVertices(name.into()));
+        }
+        let texture = self.texture(&texture_info.texture().source());
+        let texture_id = match geometry.textures.iter().position(|used| *used == texture) {
+            Some(texture_id) => texture_id,
+            None => {
+                geometry.textures.push(texture);
+                geometry.textures.len() - 1
+            }
+        } as u16;
+
+        geometry.vertices.extend(positions);
+        geometry.uvs.extend(
+            colors
+                .into_iter()
+                .zip(uvs)
+                .map(|(color, uv)| TextureUV { color, uv }),
+        );
+        geometry.faces.extend(indices.chunks_exact(3).map(|face| {
+            let vertices = [face[0], face[1], face[2]].map(|index| (first + index as usize) as u16);
+            Face {
+                vertices,
+                uv: vertices,
+                texture_id,
+                two_side: u8::from(material.double_sided()),
+                smoothing_group: [0].into(),
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Index of the texture of `image` in [`Importer::texture_paths`],
+    /// converting it to `.bmp` on its first use
+    fn texture(&mut self, image: &gltf_image::Image) -> usize {
+        if let Some(texture) = self.images.get(&image.index()) {
+            return *texture;
+        }
+
+        let (stem, content) = match image.source() {
+            gltf_image::Source::View { view, .. } => (
+                image.name().map(str::to_owned),
+                self.buffers
+                    .get(view.buffer().index())
+                    .and_then(|buffer| buffer.get(view.offset()..(view.offset() + view.length())))
+                    .map(<[u8]>::to_vec),
+            ),
+            gltf_image::Source::Uri { uri, .. } => {
+                let file_name = uri.rsplit(['/', '\\']).next().unwrap_or(uri);
+                (
+                    Some(
+                        file_name
+                            .rsplit_once('.')
+                            .map_or(file_name, |(stem, _)| stem)
+                            .to_owned(),
+                    ),
+                    (self.uri_source)(uri),
+                )
+            }
+        };
+        let stem = stem
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or_else(|| format!("texture{}", image.index()));
+
+        let mut path: Box<str> = format!("{stem}.bmp").into();
+        let mut i = 1;
+        while self.texture_paths.contains(&path) {
+            path = format!("{stem}_{i}.bmp").into();
+            i += 1;
+        }
+
+        match content.and_then(|content| to_bmp(&path, &content)) {
+            Some(bmp) => self.textures.push((path.clone(), bmp)),
+            None => log::warn!("Texture {path} could not be read, it is only referenced."),
+        }
+        self.texture_paths.push(path);
+        self.images
+            .insert(image.index(), self.texture_paths.len() - 1);
+        self.texture_paths.len() - 1
+    }
+}
+
+fn to_ragnarok_rotation(rotation: Quat) -> Quat {
+    Y_DOWN * rotation * Y_DOWN.conjugate()
+}
+
+/// Pairs `times` with `values`, keeping only the values of the key frames
+/// of cubic splines as Rsm interpolates linearly
+fn key_frames<T>(
+    times: &[f32],
+    values: impl Iterator<Item = T>,
+    interpolation: Interpolation,
+) -> Vec<(f32, T)> {
+    match interpolation {
+        Interpolation::CubicSpline => times
+            .iter()
+            .copied()
+            .zip(values.skip(1).step_by(3))
+            .collect(),
+        Interpolation::Linear | Interpolation::Step => times.iter().copied().zip(values).collect(),
+    }
+}
+
+/// Drops key frames that fall on the same frame as the previous one
+fn dedup_frames<T>(key_frames: impl Iterator<Item = T>, frame: impl Fn(&T) -> i32) -> Vec<T> {
+    let mut deduped: Vec<T> = Vec::new();
+    for key_frame in key_frames {
+        if deduped
+            .last()
+            .is_some_and(|last| frame(last) >= frame(&key_frame))
+        {
+            continue;
+        }
+        deduped.push(key_frame);
+    }
+    deduped
+}
+
+/// Converts an image to a `.bmp`, Ragnarok Online uses magenta for
+/// transparent pixels
+fn to_bmp(path: &str, content: &[u8]) -> Option<Vec<u8>> {
+    let image = image::load_from_memory(content)
+        .inspect_err(|err| log::warn!("Could not decode texture {path}. '{err}'"))
+        .ok()?
+        .into_rgba8();
+
+    let mut rgb = DynamicImage::ImageRgba8(image.clone()).into_rgb8();
+    for (pixel, rgba) in rgb.pixels_mut().zip(image.pixels()) {
+        if rgba[3] < 0x80 {
+            *pixel = Rgb([0xff, 0, 0xff]);
+        }
+    }
+
+    let mut bmp = Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(rgb)
+        .write_to(&mut bmp, ImageFormat::Bmp)
+        .inspect_err(|err| log::warn!("Could not encode texture {path}. '{err}'"))
+        .ok()?;
+    Some(bmp.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+    use ragnarok_rsm::{Rsm, mesh::Transformation};
+    use serde_json::json;
+
+    use super::{RsmImport, RsmVersion};
+
+    /// A textured triangle with a child triangle rotated by the animation
+    fn gltf() -> (Vec<u8>, Vec<u8>) {
+        let mut bin = Vec::new();
+        for value in [0., 0., 0., 1., 0., 0., 0., 1., 0.] {
+            bin.extend(f32::to_le_bytes(value));
+        }
+        for value in [0., 0., 1., 0., 0., 1.] {
+            bin.extend(f32::to_le_bytes(value));
+        }
+        for index in [0u16, 1, 2, 0] {
+            bin.extend(index.to_le_bytes());
+        }
+        for value in [0., 1., 0., 0., 0., 1., 0., 1., 0., 0.] {
+            bin.extend(f32::to_le_bytes(value));
+        }
+
+        let gltf = json!({
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [
+                { "name": "Base", "mesh": 0, "children": [1] },
+                { "name": "Lid", "mesh": 0, "translation": [0., 1., 0.] },
+            ],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": { "POSITION": 0, "TEXCOORD_0": 1 },
+                    "indices": 2,
+                    "material": 0,
+                }],
+            }],
+            "materials": [{
+                "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } },
+                "doubleSided": true,
+            }],
+            "textures": [{ "source": 0 }],
+            "images": [{ "uri": "textures/wood.png" }],
+            "animations": [{
+                "channels": [{ "sampler": 0, "target": { "node": 1, "path": "rotation" } }],
+                "samplers": [{ "input": 3, "output": 4 }],
+            }],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0., 0., 0.], "max": [1., 1., 0.],
+                },
+                { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC2" },
+                { "bufferView": 2, "componentType": 5123, "count": 3, "type": "SCALAR" },
+                {
+                    "bufferView": 3, "componentType": 5126, "count": 2, "type": "SCALAR",
+                    "min": [0.], "max": [1.],
+                },
+                { "bufferView": 4, "componentType": 5126, "count": 2, "type": "VEC4" },
+            ],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+                { "buffer": 0, "byteOffset": 36, "byteLength": 24 },
+                { "buffer": 0, "byteOffset": 60, "byteLength": 6 },
+                { "buffer": 0, "byteOffset": 68, "byteLength": 8 },
+                { "buffer": 0, "byteOffset": 76, "byteLength": 32 },
+            ],
+            "buffers": [{ "uri": "model.bin", "byteLength": bin.len() }],
+        });
+
+        (gltf.to_string().into_bytes(), bin)
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn imports_hierarchy() {
+        let (gltf, bin) = gltf();
+        let mut png = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(2, 2, Rgba([0xff, 0, 0, 0xff])))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        let png = png.into_inner();
+
+        for (version, last_frame) in [(RsmVersion::V1_4, 1000), (RsmVersion::V2_3, 60)] {
+            let mut uri_source = |uri: &str| match uri {
+                "model.bin" => Some(bin.clone()),
+                "textures/wood.png" => Some(png.clone()),
+                _ => None,
+            };
+            let imported = RsmImport { version, scale: 2. }
+                .import(&gltf, &mut uri_source)
+                .unwrap();
+            let rsm = &imported.rsm;
+
+            assert_eq!(imported.textures.len(), 1);
+            assert_eq!(&*imported.textures[0].0, "wood.bmp");
+            assert_eq!(&imported.textures[0].1[0..2], b"BM");
+
+            assert_eq!(&*rsm.root_meshes, &["Base".into()]);
+            let [base, lid] = &*rsm.meshes else {
+                unreachable!("Each node should be a mesh.");
+            };
+            assert_eq!(&*lid.parent_name, "Base");
+            // Y goes down in Ragnarok Online
+            assert_eq!(base.vertices[2], [0., -2., 0.]);
+            assert_eq!(base.faces[0].vertices, [0, 1, 2]);
+            assert_eq!(base.faces[0].two_side, 1);
+            assert_eq!(
+                lid.rotation_key_frames
+                    .iter()
+                    .map(|key_frame| key_frame.frame)
+                    .collect::<Vec<_>>(),
+                [0, last_frame]
+            );
+            match lid.transformation {
+                Transformation::Complete { position, .. } | Transformation::Simple(position) => {
+                    assert_eq!(position, [0., -2., 0.]);
+                }
+            }
+
+            let mut written = Vec::new();
+            rsm.to_writer(&mut written).unwrap();
+            let read = Rsm::from_reader(&mut written.as_slice()).unwrap();
+            assert_eq!(read.meshes.len(), 2);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Export of Ragnarok Online maps to binary glTF, and import of glTF models.
+//!
+//! A [`Map`] is exported with its ground, water planes, models with their
+//! animations, and lights as a single `.glb`.
+//!
+//! A glTF scene is imported as a Rsm with [`RsmImport`], to author models
+//! with tools that export glTF.
+
+mod builder;
+mod error;
+mod import;
+mod map;
+mod model;
+mod texture;
+
+pub use self::{
+    error::Error,
+    import::{ImportedRsm, RsmImport, RsmVersion, UriSource},
+    map::Map,
+    texture::TextureSource,
+};
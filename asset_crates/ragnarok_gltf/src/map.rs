@@ -0,0 +1,458 @@
+use std::{collections::BTreeMap, collections::HashMap, io::Write};
+
+use ragnarok_gnd::Gnd;
+use ragnarok_rsm::Rsm;
+use ragnarok_rsw::Rsw;
+use ragnarok_water_plane::WaterPlane;
+use serde_json::json;
+
+use crate::{
+    Error,
+    builder::GltfBuilder,
+    model::Models,
+    texture::{TextureSource, Textures},
+};
+
+/// Index of the vertices of the two triangles of a face, the vertices
+/// are ordered `bottom left, bottom right, top left, top right`
+const FACE_INDICES: [u32; 6] = [0, 1, 2, 1, 3, 2];
+
+/// A map made of its [`Rsw`], [`Gnd`], and the [`Rsm`]s of its models
+pub struct Map<'a> {
+    pub rsw: &'a Rsw,
+    pub gnd: &'a Gnd,
+    /// Models used by the [`Rsw`], by their [`ragnarok_rsw::Model::filename`]
+    pub rsms: &'a HashMap<Box<str>, Rsm>,
+}
+
+/// Vertices of the ground using the same texture
+#[derive(Debug, Default)]
+struct GroundPrimitive {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl Map<'_> {
+    /// Intensity in candela of the point lights of the map
+    const POINT_LIGHT_INTENSITY: f32 = 100.;
+    /// Number of cells covered by one repetition of the water texture
+    const WATER_TEXTURE_CELLS: f32 = 4.;
+
+    /// Writes the map as a binary glTF.
+    ///
+    /// The map keeps the units of Ragnarok Online, its root node turns it
+    /// to glTF's Y up. Textures are read through `texture_source`, and
+    /// textures that can't be read are left out of their materials.
+    pub fn to_glb(
+        &self,
+        texture_source: &mut TextureSource,
+        writer: &mut dyn Write,
+    ) -> Result<(), Error> {
+        let mut builder = GltfBuilder::default();
+        let mut textures = Textures::new(texture_source);
+
+        let children = [
+            self.ground(&mut builder, &mut textures),
+            self.water(&mut builder, &mut textures),
+            self.models(&mut builder, &mut textures),
+            self.lights(&mut builder),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let name = self
+            .rsw
+            .gnd_file
+            .strip_suffix(".gnd")
+            .unwrap_or(&self.rsw.gnd_file);
+        // Ragnarok Online has Y going down, which is a rotation of
+        // half a turn around X from glTF
+        let root = builder.push_node(json!({
+            "name": name,
+            "rotation": [1., 0., 0., 0.],
+        }));
+        builder.set_children(root, children);
+
+        builder.write_glb(&[root], writer)
+    }
+
+    fn ground(&self, builder: &mut GltfBuilder, textures: &mut Textures) -> Option<usize> {
+        let gnd = self.gnd;
+        let width = gnd.width as usize;
+        let height = gnd.height as usize;
+
+        let mut primitives: BTreeMap<u16, GroundPrimitive> = BTreeMap::new();
+        let mut push_face = |surface_id: i32, positions: [[f32; 3]; 4], normals: [[f32; 3]; 4]| {
+            let Some(surface) = usize::try_from(surface_id)
+                .ok()
+                .and_then(|surface_id| gnd.surfaces.get(surface_id))
+            else {
+                return;
+            };
+            let primitive = primitives.entry(surface.texture_id).or_default();
+
+            let first = primitive.positions.len() as u32;
+            primitive.positions.extend(positions);
+            primitive.normals.extend(normals);
+            primitive.uvs.extend([
+                surface.bottom_left,
+                surface.bottom_right,
+                surface.top_left,
+                surface.top_right,
+            ]);
+            let [r, g, b, _] = surface
+                .bottom_left_vertex_color
+                .map(|channel| (f32::from(channel) / 255.).powf(2.2));
+            primitive.colors.extend([[r, g, b, 1.]; 4]);
+            primitive
+                .indices
+                .extend(FACE_INDICES.map(|index| first + index));
+        };
+
+        for z in 0..height {
+            for x in 0..width {
+                let cube = &gnd.ground_mesh_cubes[x + z * width];
+                let x0 = (x as f32 - width as f32 / 2.) * gnd.scale;
+                let x1 = x0 + gnd.scale;
+                let z0 = (z as f32 - height as f32 / 2.) * gnd.scale;
+                let z1 = z0 + gnd.scale;
+
+                if let (Some(heights), Some(normals)) =
+                    (gnd.get_top_face_heights(x, z), gnd.calculate_normals(x, z))
+                {
+                    push_face(
+                        cube.upwards_facing_surface,
+                        [
+                            [x0, heights[0], z0],
+                            [x1, heights[1], z0],
+                            [x0, heights[2], z1],
+                            [x1, heights[3], z1],
+                        ],
+                        normals,
+                    );
+                }
+
+                if let Some(heights) = gnd.get_east_face_heights(x, z) {
+                    // Faces towards the lowest cube, heights grow downwards
+                    let normal = if heights[0] > heights[2] || heights[1] > heights[3] {
+                        [-1., 0., 0.]
+                    } else {
+                        [1., 0., 0.]
+                    };
+                    push_face(
+                        cube.east_facing_surface,
+                        [
+                            [x1, heights[0], z1],
+                            [x1, heights[1], z0],
+                            [x1, heights[2], z1],
+                            [x1, heights[3], z0],
+                        ],
+                        [normal; 4],
+                    );
+                }
+
+                if let Some(heights) = gnd.get_north_face_heights(x, z) {
+                    let normal = if heights[0] > heights[2] || heights[1] > heights[3] {
+                        [0., 0., -1.]
+                    } else {
+                        [0., 0., 1.]
+                    };
+                    push_face(
+                        cube.north_facing_surface,
+                        [
+                            [x0, heights[0], z1],
+                            [x1, heights[1], z1],
+                            [x0, heights[2], z1],
+                            [x1, heights[3], z1],
+                        ],
+                        [normal; 4],
+                    );
+                }
+            }
+        }
+
+        let primitives = primitives
+            .into_iter()
+            .filter_map(|(texture_id, primitive)| {
+                let Some(texture) = gnd.textures.get(usize::from(texture_id)) else {
+                    log::warn!("Ground uses missing texture {texture_id}.");
+                    return None;
+                };
+                // Faces of the ground are seen from both sides
+                let material = textures.material(builder, texture, true);
+                let position = builder.push_positions(&primitive.positions);
+                let normal = builder.push_vertex_attribute(&primitive.normals);
+                let uv = builder.push_vertex_attribute(&primitive.uvs);
+                let color = builder.push_vertex_attribute(&primitive.colors);
+                let indices = builder.push_indices_u32(&primitive.indices);
+                Some(json!({
+                    "attributes": {
+                        "POSITION": position,
+                        "NORMAL": normal,
+                        "TEXCOORD_0": uv,
+                        "COLOR_0": color,
+                    },
+                    "indices": indices,
+                    "material": material,
+                }))
+            })
+            .collect::<Vec<_>>();
+        if primitives.is_empty() {
+            return None;
+        }
+
+        let mesh = builder.push_mesh(json!({
+            "name": "Ground",
+            "primitives": primitives,
+        }));
+        Some(builder.push_node(json!({
+            "name": "Ground",
+            "mesh": mesh,
+        })))
+    }
+
+    /// Water planes of the [`Gnd`], or the one of the [`Rsw`] on older maps
+    fn water(&self, builder: &mut GltfBuilder, textures: &mut Textures) -> Option<usize> {
+        let water_planes = if self.gnd.water.is_some() {
+            self.gnd.water_planes()
+        } else {
+            self.rsw.water_configuration.into_iter().collect()
+        };
+        if water_planes.is_empty() {
+            return None;
+        }
+
+        // The cells on the border of the map never have water
+        let width = self.gnd.width.saturating_sub(2) as f32;
+        let height = self.gnd.height.saturating_sub(2) as f32;
+        let half_width = width / 2. * self.gnd.scale;
+        let half_height = height / 2. * self.gnd.scale;
+        let max_u = width / Self::WATER_TEXTURE_CELLS;
+        let max_v = height / Self::WATER_TEXTURE_CELLS;
+
+        let uv =
+            builder.push_vertex_attribute(&[[0., 0.], [max_u, 0.], [0., max_v], [max_u, max_v]]);
+        let normal = builder.push_vertex_attribute(&[[0., -1., 0.]; 4]);
+        let indices = builder.push_indices_u32(&FACE_INDICES);
+
+        let nodes = water_planes
+            .iter()
+            .enumerate()
+            .map(|(i, water_plane)| {
+                let WaterPlane {
+                    water_level,
+                    water_type,
+                    ..
+                } = *water_plane;
+                let position = builder.push_positions(&[
+                    [-half_width, water_level, -half_height],
+                    [half_width, water_level, -half_height],
+                    [-half_width, water_level, half_height],
+                    [half_width, water_level, half_height],
+                ]);
+                let material =
+                    textures.material(builder, &format!("워터/water{water_type}00.jpg"), true);
+                let mesh = builder.push_mesh(json!({
+                    "name": format!("WaterPlane{i}"),
+                    "primitives": [{
+                        "attributes": {
+                            "POSITION": position,
+                            "NORMAL": normal,
+                            "TEXCOORD_0": uv,
+                        },
+                        "indices": indices,
+                        "material": material,
+                    }],
+                }));
+                builder.push_node(json!({
+                    "name": format!("WaterPlane{i}"),
+                    "mesh": mesh,
+                }))
+            })
+            .collect();
+
+        let water = builder.push_node(json!({ "name": "Water" }));
+        builder.set_children(water, nodes);
+        Some(water)
+    }
+
+    fn models(&self, builder: &mut GltfBuilder, textures: &mut Textures) -> Option<usize> {
+        let mut models = Models::new(self.rsms);
+        let nodes = self
+            .rsw
+            .models
+            .iter()
+            .filter_map(|model| models.instance(builder, textures, model))
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            return None;
+        }
+
+        let node = builder.push_node(json!({ "name": "Models" }));
+        builder.set_children(node, nodes);
+        Some(node)
+    }
+
+    /// Point lights of the [`Rsw`], using the `KHR_lights_punctual` extension
+    fn lights(&self, builder: &mut GltfBuilder) -> Option<usize> {
+        let nodes = self
+            .rsw
+            .lights
+            .iter()
+            .map(|light| {
+                // Colors of the lights are in sRGB, glTF expects them linear
+                let color = light.color.map(|channel| channel.clamp(0., 1.).powf(2.2));
+                let gltf_light = builder.push_light(json!({
+                    "name": light.name,
+                    "type": "point",
+                    "color": color,
+                    "intensity": Self::POINT_LIGHT_INTENSITY,
+                    "range": light.range,
+                }));
+                builder.push_node(json!({
+                    "name": light.name,
+                    "translation": light.position,
+                    "extensions": { "KHR_lights_punctual": { "light": gltf_light } },
+                }))
+            })
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            return None;
+        }
+
+        let node = builder.push_node(json!({ "name": "Lights" }));
+        builder.set_children(node, nodes);
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use ragnarok_gnd::{Gnd, GroundMeshCube, Lightmap, Surface};
+    use ragnarok_rebuild_common::Version;
+    use ragnarok_rsw::{BoundingBox, Light, LightingParams, Rsw, quad_tree::QuadTree};
+    use ragnarok_water_plane::WaterPlane;
+
+    use super::Map;
+
+    fn surface(texture_id: u16) -> Surface {
+        Surface {
+            bottom_left: [0., 1.],
+            bottom_right: [1., 1.],
+            top_left: [0., 0.],
+            top_right: [1., 0.],
+            texture_id,
+            lightmap_id: 0,
+            bottom_left_vertex_color: [255; 4],
+        }
+    }
+
+    fn cube(upwards_facing_surface: i32) -> GroundMeshCube {
+        GroundMeshCube {
+            bottom_left_height: 0.,
+            bottom_right_height: 0.,
+            top_left_height: 0.,
+            top_right_height: 0.,
+            upwards_facing_surface,
+            north_facing_surface: -1,
+            east_facing_surface: -1,
+        }
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn small_map() {
+        let gnd = Gnd {
+            signature: "GRGN".into(),
+            version: Version(1, 7, 0),
+            width: 2,
+            height: 1,
+            scale: 10.,
+            texture_path_len: 80,
+            textures: Box::new(["a.bmp".into(), "b.bmp".into()]),
+            raw_textures: Box::default(),
+            lightmap: Lightmap {
+                pixel_format: 1,
+                width: 8,
+                height: 8,
+                shadow_map_pixels: Box::new([]),
+                light_map_pixels: Box::new([]),
+            },
+            surfaces: Box::new([surface(0), surface(1)]),
+            ground_mesh_cubes: Box::new([cube(0), cube(1)]),
+            water: None,
+        };
+        let rsw = Rsw {
+            signature: *b"GRSW",
+            version: Version(2, 1, 0),
+            flag: 0,
+            ini_file: "".into(),
+            gnd_file: "small.gnd".into(),
+            gat_file: "small.gat".into(),
+            source_file: "".into(),
+            water_configuration: Some(WaterPlane {
+                water_level: 0.,
+                water_type: 0,
+                wave_height: 1.,
+                wave_speed: 2.,
+                wave_pitch: 50.,
+                texture_cyclical_interval: 3,
+            }),
+            lighting_parameters: LightingParams {
+                longitude: 45,
+                latitude: 45,
+                diffuse_color: [1.; 3],
+                ambient_color: [0.3; 3],
+                shadow_map_alpha: 0.5,
+            },
+            map_boundaries: BoundingBox {
+                top: 0,
+                bottom: 0,
+                left: 0,
+                right: 0,
+            },
+            mystery_items: Box::new([]),
+            models: Box::new([]),
+            lights: Box::new([Light {
+                name: "light".into(),
+                position: [0., -10., 0.],
+                color: [1.; 3],
+                range: 20.,
+            }]),
+            sounds: Box::new([]),
+            effects: Box::new([]),
+            quad_tree: QuadTree {
+                ranges: Box::new([]),
+            },
+        };
+        let rsms = HashMap::new();
+
+        let mut glb = vec![];
+        Map {
+            rsw: &rsw,
+            gnd: &gnd,
+            rsms: &rsms,
+        }
+        .to_glb(&mut |_| None, &mut glb)
+        .unwrap();
+
+        let gltf = gltf::Gltf::from_slice(&glb).unwrap();
+        let node_names = gltf
+            .nodes()
+            .map(|node| node.name().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            node_names,
+            ["Ground", "WaterPlane0", "Water", "light", "Lights", "small"]
+        );
+        assert_eq!(gltf.meshes().count(), 2);
+        // One primitive per ground texture
+        assert_eq!(gltf.meshes().next().unwrap().primitives().count(), 2);
+    }
+}
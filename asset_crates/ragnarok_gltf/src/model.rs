@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use glam::{EulerRot, Mat3, Mat4, Quat, Vec3};
+use ragnarok_rsm::{
+    AnimationDuration, Rsm,
+    mesh::{Mesh, Primitive, Textures as MeshTextures, Transformation},
+};
+use ragnarok_rsw::Model;
+use serde_json::{Value, json};
+
+use crate::{builder::GltfBuilder, texture::Textures};
+
+/// Channels and samplers of the animation of a [`Model`]
+#[derive(Debug, Default)]
+struct Animation {
+    channels: Vec<Value>,
+    samplers: Vec<Value>,
+}
+
+impl Animation {
+    /// Adds a channel animating `path` of `node`, key frames that don't
+    /// come strictly after the previous one are dropped as glTF requires
+    fn push<const N: usize>(
+        &mut self,
+        builder: &mut GltfBuilder,
+        node: usize,
+        path: &str,
+        key_frames: impl Iterator<Item = (f32, [f32; N])>,
+    ) {
+        let mut times = Vec::new();
+        let mut values = Vec::new();
+        for (time, value) in key_frames {
+            if times.last().is_some_and(|last| time <= *last) {
+                continue;
+            }
+            times.push(time);
+            values.push(value);
+        }
+        if times.is_empty() {
+            return;
+        }
+
+        let input = builder.push_key_frame_times(&times);
+        let output = builder.push_key_frame_values(&values);
+        self.samplers.push(json!({
+            "input": input,
+            "output": output,
+            "interpolation": "LINEAR",
+        }));
+        self.channels.push(json!({
+            "sampler": self.samplers.len() - 1,
+            "target": { "node": node, "path": path },
+        }));
+    }
+}
+
+/// Builds the node hierarchies of the [`Model`]s of a map, the meshes of
+/// each [`Rsm`] are shared by all of its instances
+pub(crate) struct Models<'a> {
+    rsms: &'a HashMap<Box<str>, Rsm>,
+    meshes: HashMap<(&'a str, usize), Option<usize>>,
+}
+
+impl<'a> Models<'a> {
+    pub fn new(rsms: &'a HashMap<Box<str>, Rsm>) -> Self {
+        Self {
+            rsms,
+            meshes: HashMap::new(),
+        }
+    }
+
+    /// Pushes the nodes of an instance of a [`Model`] and its animation,
+    /// returns the root node of the instance
+    pub fn instance(
+        &mut self,
+        builder: &mut GltfBuilder,
+        textures: &mut Textures,
+        model: &Model,
+    ) -> Option<usize> {
+        let Some((filename, rsm)) = self.rsms.get_key_value(&model.filename) else {
+            log::warn!("Model {:?} uses missing {:?}.", model.name, model.filename);
+            return None;
+        };
+
+        let fix_up = if model.filename.ends_with("rsm2") {
+            Vec3::new(1., -1., 1.)
+        } else {
+            Vec3::ONE
+        };
+        let node = builder.push_node(trs_node(
+            &model.name,
+            Vec3::from_array(model.position),
+            Quat::from_euler(
+                EulerRot::ZXY,
+                model.rotation[2].to_radians(),
+                model.rotation[0].to_radians(),
+                model.rotation[1].to_radians(),
+            ),
+            Vec3::from_array(model.scale) * fix_up,
+        ));
+
+        let mut remaining = rsm
+            .meshes
+            .iter()
+            .enumerate()
+            .map(|(i, mesh)| (i, mesh.name.as_ref()))
+            .collect::<Vec<_>>();
+        let root_meshes = rsm
+            .root_meshes
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>();
+
+        let mut animation = Animation::default();
+        let children = self.mesh_nodes(
+            builder,
+            textures,
+            (filename, rsm),
+            &root_meshes,
+            &mut remaining,
+            &mut animation,
+        );
+        builder.set_children(node, children);
+
+        if !animation.channels.is_empty() {
+            builder.push_animation(json!({
+                "name": model.name,
+                "channels": animation.channels,
+                "samplers": animation.samplers,
+            }));
+        }
+
+        Some(node)
+    }
+
+    /// Pushes the nodes of the meshes in `to_build`, followed by their
+    /// children, meshes are only built once even if the hierarchy has cycles
+    fn mesh_nodes(
+        &mut self,
+        builder: &mut GltfBuilder,
+        textures: &mut Textures,
+        (filename, rsm): (&'a str, &'a Rsm),
+        to_build: &[&str],
+        remaining: &mut Vec<(usize, &'a str)>,
+        animation: &mut Animation,
+    ) -> Vec<usize> {
+        let mut nodes = Vec::new();
+
+        for mesh_to_build in to_build {
+            let Some(pos) = remaining
+                .iter()
+                .position(|(_, mesh)| *mesh == *mesh_to_build)
+            else {
+                continue;
+            };
+            let (mesh_index, _) = remaining.remove(pos);
+            let mesh = &rsm.meshes[mesh_index];
+
+            let transform = if mesh.parent_name.is_empty() {
+                let Some((min, max)) = bounds(mesh) else {
+                    log::warn!(
+                        "Mesh {} from model {filename:?} had no vertices.",
+                        mesh.name
+                    );
+                    continue;
+                };
+                let mut transform = mesh_transform(mesh);
+                if !matches!(mesh.transformation, Transformation::Simple(_)) {
+                    transform.w_axis -= recentering(min, max).extend(0.);
+                }
+                transform
+            } else {
+                mesh_transform(mesh)
+            };
+            let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+            let node = builder.push_node(trs_node(&mesh.name, translation, rotation, scale));
+
+            Self::push_mesh_animation(builder, rsm, mesh, node, animation);
+
+            let mut children = Vec::new();
+            if let Some(gltf_mesh) = self.mesh(builder, textures, (filename, rsm), mesh_index) {
+                children.push(builder.push_node(json!({
+                    "name": "Primitives",
+                    "mesh": gltf_mesh,
+                })));
+            }
+
+            let child_meshes = rsm
+                .meshes
+                .iter()
+                .enumerate()
+                .filter(|(i, child)| {
+                    remaining.iter().any(|(remaining, _)| remaining == i)
+                        && child.parent_name.as_ref() == *mesh_to_build
+                })
+                .map(|(_, child)| child.name.as_ref())
+                .collect::<Vec<_>>();
+            children.extend(self.mesh_nodes(
+                builder,
+                textures,
+                (filename, rsm),
+                &child_meshes,
+                remaining,
+                animation,
+            ));
+            builder.set_children(node, children);
+
+            if rsm.root_meshes.first() == Some(&mesh.name) {
+                Self::push_root_animation(builder, rsm, node, animation);
+            }
+
+            nodes.push(node);
+        }
+
+        nodes
+    }
+
+    fn push_mesh_animation(
+        builder: &mut GltfBuilder,
+        rsm: &Rsm,
+        mesh: &Mesh,
+        node: usize,
+        animation: &mut Animation,
+    ) {
+        let duration = rsm.animation_duration;
+        animation.push(
+            builder,
+            node,
+            "translation",
+            mesh.position_key_frames
+                .iter()
+                .map(|frame| (key_frame_time(duration, frame.frame), frame.position)),
+        );
+        animation.push(
+            builder,
+            node,
+            "rotation",
+            mesh.rotation_key_frames.iter().map(|frame| {
+                (
+                    key_frame_time(duration, frame.frame),
+                    Quat::from_array(frame.quaternion).normalize().to_array(),
+                )
+            }),
+        );
+        animation.push(
+            builder,
+            node,
+            "scale",
+            mesh.scale_key_frames
+                .iter()
+                .map(|frame| (key_frame_time(duration, frame.frame), frame.scale)),
+        );
+    }
+
+    /// Position key frames of versions before 1.6 move the first root mesh
+    fn push_root_animation(
+        builder: &mut GltfBuilder,
+        rsm: &Rsm,
+        node: usize,
+        animation: &mut Animation,
+    ) {
+        let Some((min, max)) = rsm
+            .meshes
+            .iter()
+            .find(|mesh| mesh.name == rsm.root_meshes[0])
+            .and_then(bounds)
+        else {
+            return;
+        };
+        let correction = recentering(min, max);
+
+        animation.push(
+            builder,
+            node,
+            "translation",
+            rsm.position_key_frames.iter().map(|frame| {
+                (
+                    key_frame_time(rsm.animation_duration, frame.frame),
+                    (Vec3::from_array(frame.position) - correction).to_array(),
+                )
+            }),
+        );
+    }
+
+    /// glTF mesh of a [`Mesh`] with its transformation matrix baked in,
+    /// `None` if it has no faces.
+    ///
+    /// glTF nodes can't shear, which the transformation matrix can.
+    fn mesh(
+        &mut self,
+        builder: &mut GltfBuilder,
+        textures: &mut Textures,
+        (filename, rsm): (&'a str, &'a Rsm),
+        mesh_index: usize,
+    ) -> Option<usize> {
+        if let Some(gltf_mesh) = self.meshes.get(&(filename, mesh_index)) {
+            return *gltf_mesh;
+        }
+
+        let mesh = &rsm.meshes[mesh_index];
+        let texture_paths = match &mesh.textures {
+            MeshTextures::Paths(paths) => paths,
+            MeshTextures::Indexes(_) => &rsm.textures,
+        };
+
+        let matrix = transformation_matrix(mesh);
+        let mut primitives = Vec::new();
+        for mut primitive in mesh.flat_mesh().primitives {
+            let Some(texture_path) = usize::try_from(primitive.texture_id)
+                .ok()
+                .and_then(|texture_id| texture_paths.get(texture_id))
+            else {
+                log::warn!(
+                    "Mesh {} from model {filename:?} uses missing texture {}.",
+                    mesh.name,
+                    primitive.texture_id
+                );
+                continue;
+            };
+
+            bake(&mut primitive, matrix);
+            let material = textures.material(builder, texture_path, primitive.double_sided);
+            let position = builder.push_positions(&primitive.vertices);
+            let normal = builder.push_vertex_attribute(&primitive.normals);
+            let uv = builder.push_vertex_attribute(&primitive.uv);
+            let color = builder.push_vertex_attribute(&primitive.color);
+            let indices = builder.push_indices_u16(&primitive.indices);
+            primitives.push(json!({
+                "attributes": {
+                    "POSITION": position,
+                    "NORMAL": normal,
+                    "TEXCOORD_0": uv,
+                    "COLOR_0": color,
+                },
+                "indices": indices,
+                "material": material,
+            }));
+        }
+
+        let gltf_mesh = (!primitives.is_empty()).then(|| {
+            builder.push_mesh(json!({
+                "name": format!("{filename}/{}", mesh.name),
+                "primitives": primitives,
+            }))
+        });
+        self.meshes.insert((filename, mesh_index), gltf_mesh);
+        gltf_mesh
+    }
+}
+
+fn trs_node(name: &str, translation: Vec3, rotation: Quat, scale: Vec3) -> Value {
+    json!({
+        "name": name,
+        "translation": translation.to_array(),
+        "rotation": rotation.to_array(),
+        "scale": scale.to_array(),
+    })
+}
+
+fn key_frame_time(duration: AnimationDuration, frame: i32) -> f32 {
+    duration.transform(frame as f32)
+}
+
+/// Offset that places the root of a model at the center of the bottom of its bounds
+fn recentering(min: Vec3, max: Vec3) -> Vec3 {
+    let center = (min + max) / 2.;
+    Vec3::new(center.x, max.y, center.z)
+}
+
+/// Matrix applied to the vertices of a [`Mesh`]
+fn transformation_matrix(mesh: &Mesh) -> Mat4 {
+    let offset = match mesh.transformation {
+        Transformation::Complete { offset, .. } => Vec3::from_array(offset),
+        Transformation::Simple(_) => Vec3::ZERO,
+    };
+    Mat4::from_cols(
+        Vec3::from_slice(&mesh.transformation_matrix[0..3]).extend(0.),
+        Vec3::from_slice(&mesh.transformation_matrix[3..6]).extend(0.),
+        Vec3::from_slice(&mesh.transformation_matrix[6..9]).extend(0.),
+        offset.extend(1.),
+    )
+}
+
+/// Applies `matrix` to the vertices and normals of `primitive`, the
+/// triangles are flipped if it mirrors them
+fn bake(primitive: &mut Primitive, matrix: Mat4) {
+    let linear = Mat3::from_mat4(matrix);
+    let normal_matrix = if linear.determinant().abs() > f32::EPSILON {
+        linear.inverse().transpose()
+    } else {
+        linear
+    };
+
+    for vertex in primitive.vertices.iter_mut() {
+        *vertex = matrix
+            .transform_point3(Vec3::from_array(*vertex))
+            .to_array();
+    }
+    for normal in primitive.normals.iter_mut() {
+        *normal = (normal_matrix * Vec3::from_array(*normal))
+            .normalize_or_zero()
+            .to_array();
+    }
+    if linear.determinant() < 0. {
+        for triangle in primitive.indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+}
+
+/// Transform of the node of a [`Mesh`] relative to its parent
+fn mesh_transform(mesh: &Mesh) -> Mat4 {
+    match mesh.transformation {
+        Transformation::Complete {
+            position,
+            rotation_angle,
+            rotation_axis,
+            scale,
+            ..
+        } => {
+            let rotation_axis = Vec3::from_array(rotation_axis);
+            let rotation = if rotation_axis.length() <= 0. {
+                Quat::IDENTITY
+            } else {
+                Quat::from_axis_angle(rotation_axis.normalize(), rotation_angle)
+            };
+            Mat4::from_scale_rotation_translation(
+                Vec3::from_array(scale),
+                rotation,
+                Vec3::from_array(position),
+            )
+        }
+        Transformation::Simple(position) => Mat4::from_translation(Vec3::from_array(position)),
+    }
+}
+
+/// Bounds of the vertices of a [`Mesh`] after being transformed
+fn bounds(mesh: &Mesh) -> Option<(Vec3, Vec3)> {
+    let transform = mesh_transform(mesh) * transformation_matrix(mesh);
+    mesh.vertices
+        .iter()
+        .map(|vertex| transform.transform_point3(Vec3::from_array(*vertex)))
+        .fold(None, |bounds, vertex| match bounds {
+            None => Some((vertex, vertex)),
+            Some((min, max)) => Some((min.min(vertex), max.max(vertex))),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use glam::{Mat4, Vec3};
+    use ragnarok_rsm::mesh::Primitive;
+
+    use super::bake;
+
+    fn triangle() -> Primitive {
+        Primitive {
+            vertices: vec![[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]],
+            normals: vec![[0., 1., 0.]; 3],
+            indices: vec![0, 1, 2],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bake_shear() {
+        // Moves X along Y
+        let shear = Mat4::from_cols_array(&[
+            1., 1., 0., 0., //
+            0., 1., 0., 0., //
+            0., 0., 1., 0., //
+            0., 0., 2., 1., //
+        ]);
+        let mut primitive = triangle();
+        bake(&mut primitive, shear);
+
+        assert_eq!(
+            primitive.vertices,
+            vec![[0., 0., 2.], [1., 1., 2.], [0., 0., 3.]]
+        );
+        // The normal stays perpendicular to the sheared triangle
+        let normal = Vec3::from_array(primitive.normals[0]);
+        assert!(normal.dot(Vec3::new(1., 1., 0.)).abs() < 1e-6);
+        assert!((normal.length() - 1.).abs() < 1e-6);
+        assert_eq!(primitive.indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn bake_mirror() {
+        let mut primitive = triangle();
+        bake(&mut primitive, Mat4::from_scale(Vec3::new(-1., 1., 1.)));
+
+        assert_eq!(primitive.vertices[1], [-1., 0., 0.]);
+        assert_eq!(primitive.normals[0], [0., 1., 0.]);
+        assert_eq!(primitive.indices, vec![0, 2, 1]);
+    }
+}
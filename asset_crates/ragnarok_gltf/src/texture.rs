@@ -0,0 +1,142 @@
+use std::{collections::HashMap, io::Cursor};
+
+use image::{DynamicImage, ImageFormat};
+use serde_json::json;
+
+use crate::builder::GltfBuilder;
+
+/// Reads the content of a texture given its path relative to `data/texture/`
+pub type TextureSource<'a> = dyn FnMut(&str) -> Option<Vec<u8>> + 'a;
+
+/// How the alpha of a texture should be treated by its materials
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlphaMode {
+    Opaque,
+    Mask,
+    Blend,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Texture {
+    index: usize,
+    alpha_mode: AlphaMode,
+}
+
+/// Converts the textures of the map to images glTF can embed, and creates
+/// the materials using them
+pub(crate) struct Textures<'a, 'b> {
+    source: &'a mut TextureSource<'b>,
+    textures: HashMap<Box<str>, Option<Texture>>,
+    materials: HashMap<(Box<str>, bool), usize>,
+}
+
+impl<'a, 'b> Textures<'a, 'b> {
+    pub fn new(source: &'a mut TextureSource<'b>) -> Self {
+        Self {
+            source,
+            textures: HashMap::new(),
+            materials: HashMap::new(),
+        }
+    }
+
+    /// Material using the texture at `path`, when the texture can't be read
+    /// the material is left untextured
+    pub fn material(&mut self, builder: &mut GltfBuilder, path: &str, double_sided: bool) -> usize {
+        if let Some(material) = self.materials.get(&(path.into(), double_sided)) {
+            return *material;
+        }
+
+        let mut material = json!({
+            "name": path,
+            "pbrMetallicRoughness": {
+                "metallicFactor": 0.,
+                "roughnessFactor": 1.,
+            },
+            "doubleSided": double_sided,
+        });
+        if let Some(texture) = self.texture(builder, path) {
+            material["pbrMetallicRoughness"]["baseColorTexture"] =
+                json!({ "index": texture.index });
+            match texture.alpha_mode {
+                AlphaMode::Opaque => (),
+                AlphaMode::Mask => material["alphaMode"] = json!("MASK"),
+                AlphaMode::Blend => material["alphaMode"] = json!("BLEND"),
+            }
+        }
+
+        let material = builder.push_material(material);
+        self.materials.insert((path.into(), double_sided), material);
+        material
+    }
+
+    fn texture(&mut self, builder: &mut GltfBuilder, path: &str) -> Option<Texture> {
+        if let Some(texture) = self.textures.get(path) {
+            return *texture;
+        }
+
+        let texture = (self.source)(path)
+            .or_else(|| {
+                log::warn!("Texture {path:?} could not be read.");
+                None
+            })
+            .and_then(|data| Self::convert(path, data))
+            .map(|(data, mime_type, alpha_mode)| {
+                let image = builder.push_image(&data, mime_type);
+                Texture {
+                    index: builder.push_texture(image),
+                    alpha_mode,
+                }
+            });
+
+        self.textures.insert(path.into(), texture);
+        texture
+    }
+
+    /// Converts a texture to a format supported by glTF, `bmp`s get the
+    /// magenta pixels the client treats as transparent removed
+    fn convert(path: &str, data: Vec<u8>) -> Option<(Vec<u8>, &'static str, AlphaMode)> {
+        let extension = path.rsplit_once('.').map(|(_, extension)| extension);
+        let (image, alpha_mode) = match extension {
+            Some("jpg") | Some("jpeg") => return Some((data, "image/jpeg", AlphaMode::Opaque)),
+            Some("png") => return Some((data, "image/png", AlphaMode::Blend)),
+            Some("bmp") => {
+                let mut image = Self::decode(path, &data, ImageFormat::Bmp)?.into_rgba8();
+                let mut keyed = false;
+                for pixel in image.pixels_mut() {
+                    let [r, g, b, _] = pixel.0;
+                    if r > 0xf0 && g < 0x10 && b > 0xf0 {
+                        pixel.0 = [0; 4];
+                        keyed = true;
+                    }
+                }
+                let alpha_mode = if keyed {
+                    AlphaMode::Mask
+                } else {
+                    AlphaMode::Opaque
+                };
+                (DynamicImage::ImageRgba8(image), alpha_mode)
+            }
+            Some("tga") => (
+                Self::decode(path, &data, ImageFormat::Tga)?,
+                AlphaMode::Blend,
+            ),
+            _ => {
+                log::warn!("Texture {path:?} has an unsupported format.");
+                return None;
+            }
+        };
+
+        let mut png = Cursor::new(vec![]);
+        if let Err(err) = image.write_to(&mut png, ImageFormat::Png) {
+            log::warn!("Texture {path:?} could not be converted to png. '{err}'");
+            return None;
+        }
+        Some((png.into_inner(), "image/png", alpha_mode))
+    }
+
+    fn decode(path: &str, data: &[u8], format: ImageFormat) -> Option<DynamicImage> {
+        image::load_from_memory_with_format(data, format)
+            .inspect_err(|err| log::warn!("Texture {path:?} could not be decoded. '{err}'"))
+            .ok()
+    }
+}
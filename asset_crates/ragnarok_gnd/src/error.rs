@@ -8,6 +8,15 @@ pub enum Error {
     UnknownVersion(Version),
     Io(io::Error),
     IncompleteRead(Version, usize),
+    TooManyElements(&'static str),
+    WrongCubeCount(u32, u32, usize),
+    WrongWaterPlaneCount(i32, i32, usize),
+    MismatchedLightmaps(usize, usize),
+    WrongLightmapSize(u32, u32),
+    UnrepresentableSurface(Version, i32),
+    MismatchedWater(Version),
+    MissingSurface(usize),
+    MissingTexture(u16),
 }
 
 impl From<io::Error> for Error {
@@ -30,6 +39,42 @@ impl std::fmt::Display for Error {
                 f,
                 "Could not read GND to the end. GND V{version} had {unread} unread bytes."
             ),
+            Self::TooManyElements(elements) => {
+                write!(f, "GND had too many {elements} to be written.")
+            }
+            Self::WrongCubeCount(width, height, cubes) => write!(
+                f,
+                "GND of {width}x{height} should have {} cubes but had {cubes}.",
+                u64::from(*width) * u64::from(*height)
+            ),
+            Self::WrongWaterPlaneCount(horizontal, vertical, planes) => write!(
+                f,
+                "GND water grid of {horizontal}x{vertical} should have {} planes but had {planes}.",
+                i64::from(*horizontal) * i64::from(*vertical)
+            ),
+            Self::MismatchedLightmaps(shadow_maps, light_maps) => write!(
+                f,
+                "GND had {shadow_maps} shadowmaps but {light_maps} lightmaps."
+            ),
+            Self::WrongLightmapSize(width, height) => {
+                write!(f, "GND had a lightmap that was not {width}x{height}.")
+            }
+            Self::UnrepresentableSurface(version, surface) => {
+                write!(f, "Surface {surface} can't be written on GND V{version}.")
+            }
+            Self::MismatchedWater(version) => {
+                if version < &Version(1, 8, 0) {
+                    write!(f, "GND V{version} can't have water.")
+                } else {
+                    write!(f, "GND V{version} must have water.")
+                }
+            }
+            Self::MissingSurface(surface) => {
+                write!(f, "GND had a cube using the missing surface {surface}.")
+            }
+            Self::MissingTexture(texture) => {
+                write!(f, "GND had a surface using the missing texture {texture}.")
+            }
         }
     }
 }
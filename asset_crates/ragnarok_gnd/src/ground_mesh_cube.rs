@@ -1,6 +1,6 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
-use ragnarok_rebuild_common::reader_ext::ReaderExt;
+use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
 use crate::triangle_normal;
 
@@ -16,14 +16,14 @@ pub struct GroundMeshCube {
 }
 
 impl GroundMeshCube {
-    pub fn from_reader(mut reader: &mut dyn Read) -> Result<Self, super::Error> {
+    pub fn from_reader(mut reader: &mut dyn Read, version: &Version) -> Result<Self, super::Error> {
         let bottom_left_height = reader.read_le_f32()?;
         let bottom_right_height = reader.read_le_f32()?;
         let top_left_height = reader.read_le_f32()?;
         let top_right_height = reader.read_le_f32()?;
-        let upwards_facing_surface = reader.read_le_i32()?;
-        let north_facing_surface = reader.read_le_i32()?;
-        let east_facing_surface = reader.read_le_i32()?;
+        let upwards_facing_surface = Self::read_surface_id(reader, version)?;
+        let north_facing_surface = Self::read_surface_id(reader, version)?;
+        let east_facing_surface = Self::read_surface_id(reader, version)?;
 
         Ok(Self {
             bottom_left_height,
@@ -36,6 +36,55 @@ impl GroundMeshCube {
         })
     }
 
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), super::Error> {
+        writer.write_all(&self.bottom_left_height.to_le_bytes())?;
+        writer.write_all(&self.bottom_right_height.to_le_bytes())?;
+        writer.write_all(&self.top_left_height.to_le_bytes())?;
+        writer.write_all(&self.top_right_height.to_le_bytes())?;
+        for surface in [
+            self.upwards_facing_surface,
+            self.north_facing_surface,
+            self.east_facing_surface,
+        ] {
+            Self::write_surface_id(writer, version, surface)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the id of a surface of the cube, versions before 1.6 stored them
+    /// as `u16`, with `u16::MAX` meaning no surface.
+    fn read_surface_id(mut reader: &mut dyn Read, version: &Version) -> Result<i32, super::Error> {
+        if version >= &Version(1, 6, 0) {
+            Ok(reader.read_le_i32()?)
+        } else {
+            match reader.read_le_u16()? {
+                u16::MAX => Ok(-1),
+                id => Ok(i32::from(id)),
+            }
+        }
+    }
+
+    fn write_surface_id(
+        writer: &mut dyn Write,
+        version: &Version,
+        surface: i32,
+    ) -> Result<(), super::Error> {
+        if version >= &Version(1, 6, 0) {
+            writer.write_all(&surface.to_le_bytes())?;
+        } else {
+            let id = match surface {
+                -1 => u16::MAX,
+                id => u16::try_from(id)
+                    .ok()
+                    .filter(|id| *id != u16::MAX)
+                    .ok_or(super::Error::UnrepresentableSurface(*version, surface))?,
+            };
+            writer.write_all(&id.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
     /// Return the normals of the top face of the cube.
     ///
     /// The order is
@@ -4,14 +4,20 @@ mod lightmap;
 mod surface;
 #[cfg(feature = "warning")]
 pub mod warnings;
+mod water;
 
-use std::io::Read;
+use std::io::{Read, Write};
 
-use ragnarok_rebuild_common::{Version, euc_kr::read_n_euc_kr_strings, reader_ext::ReaderExt};
+use ragnarok_rebuild_common::{
+    Version,
+    euc_kr::{read_euc_kr_string, write_euc_kr_string},
+    reader_ext::ReaderExt,
+};
 use ragnarok_water_plane::WaterPlane;
 
 pub use self::{
     error::Error, ground_mesh_cube::GroundMeshCube, lightmap::Lightmap, surface::Surface,
+    water::WaterGrid,
 };
 
 const NORMALIZED_THRESHOLD: f32 = f32::EPSILON * 2.;
@@ -23,19 +29,29 @@ pub struct Gnd {
     pub width: u32,
     pub height: u32,
     pub scale: f32,
+    /// Length of the fixed size strings holding the texture paths
+    pub texture_path_len: u32,
     pub textures: Box<[Box<str>]>,
+    /// Texture paths as stored in the file, [`Gnd::textures`] that were
+    /// not changed are written back from them
+    pub raw_textures: Box<[Box<[u8]>]>,
     pub lightmap: Lightmap,
     pub surfaces: Box<[Surface]>,
     pub ground_mesh_cubes: Box<[GroundMeshCube]>,
-    pub water_planes: Box<[WaterPlane]>,
+    pub water: Option<WaterGrid>,
 }
 
 impl Gnd {
+    /// Reads a [`Gnd`] of version 1.9 or older.
+    ///
+    /// The water layout of versions from 1.10 is not known, they are
+    /// rejected with [`Error::UnknownVersion`].
     pub fn from_reader(mut reader: &mut dyn Read) -> Result<Self, Error> {
         let signature = Self::read_signature(reader)?;
         let version = Self::read_version(reader)?;
 
-        if version < Version(1, 7, 0) || version >= Version(1, 10, 0) {
+        // Versions past 1.9 are unknown
+        if version.0 != 1 || version >= Version(1, 10, 0) {
             return Err(Error::UnknownVersion(version));
         }
 
@@ -45,10 +61,19 @@ impl Gnd {
 
         let texture_count = reader.read_le_u32()?;
         let texture_path_len = reader.read_le_u32()?;
-        let textures =
-            read_n_euc_kr_strings(reader, texture_count, Some(texture_path_len as usize))?;
+        let raw_textures = (0..texture_count)
+            .map(|_| {
+                reader
+                    .read_vec(texture_path_len as usize)
+                    .map(Vec::into_boxed_slice)
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+        let textures = raw_textures
+            .iter()
+            .map(|raw_texture| read_euc_kr_string(&mut raw_texture.as_ref(), raw_texture.len()))
+            .collect::<Result<Box<[_]>, _>>()?;
 
-        let lightmap = lightmap::Lightmap::from_reader(reader)?;
+        let lightmap = lightmap::Lightmap::from_reader(reader, &version)?;
 
         let surface_count = reader.read_le_u32()?;
         let surfaces = (0..surface_count)
@@ -56,10 +81,10 @@ impl Gnd {
             .collect::<Result<Box<[_]>, Error>>()?;
 
         let ground_mesh_cubes = (0..(width * height))
-            .map(|_| ground_mesh_cube::GroundMeshCube::from_reader(reader))
+            .map(|_| ground_mesh_cube::GroundMeshCube::from_reader(reader, &version))
             .collect::<Result<Box<[_]>, Error>>()?;
 
-        let water_planes = Self::read_water_planes(reader, &version)?;
+        let water = WaterGrid::from_reader(reader, &version)?;
 
         let mut rest = vec![];
         reader.read_to_end(&mut rest)?;
@@ -73,14 +98,95 @@ impl Gnd {
             width,
             height,
             scale,
+            texture_path_len,
             textures,
+            raw_textures,
             lightmap,
             surfaces,
             ground_mesh_cubes,
-            water_planes,
+            water,
         })
     }
 
+    /// Writes the [`Gnd`] using the layout of its version.
+    ///
+    /// Texture paths that were not changed are written from
+    /// [`Gnd::raw_textures`], keeping their case, separators and any bytes
+    /// after their end. Changed ones are written with `\` as separator.
+    /// Water planes of version 1.8 are written only with their level.
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        let Version(major, minor, 0) = self.version else {
+            return Err(Error::UnknownVersion(self.version));
+        };
+        if major != 1 || minor >= 10 {
+            return Err(Error::UnknownVersion(self.version));
+        }
+        if self.ground_mesh_cubes.len() as u64 != u64::from(self.width) * u64::from(self.height) {
+            return Err(Error::WrongCubeCount(
+                self.width,
+                self.height,
+                self.ground_mesh_cubes.len(),
+            ));
+        }
+        let Ok(texture_count) = u32::try_from(self.textures.len()) else {
+            return Err(Error::TooManyElements("textures"));
+        };
+        let Ok(surface_count) = u32::try_from(self.surfaces.len()) else {
+            return Err(Error::TooManyElements("surfaces"));
+        };
+
+        writer.write_all(self.signature.as_bytes())?;
+        writer.write_all(&[major, minor])?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        writer.write_all(&self.scale.to_le_bytes())?;
+
+        writer.write_all(&texture_count.to_le_bytes())?;
+        writer.write_all(&self.texture_path_len.to_le_bytes())?;
+        for (index, texture) in self.textures.iter().enumerate() {
+            match self.raw_textures.get(index) {
+                Some(raw_texture)
+                    if raw_texture.len() == self.texture_path_len as usize
+                        && read_euc_kr_string(&mut raw_texture.as_ref(), raw_texture.len())
+                            .is_ok_and(|raw_texture| raw_texture == *texture) =>
+                {
+                    writer.write_all(raw_texture)?
+                }
+                _ => write_euc_kr_string(writer, texture, self.texture_path_len as usize)?,
+            }
+        }
+
+        self.lightmap.to_writer(writer, &self.version)?;
+
+        writer.write_all(&surface_count.to_le_bytes())?;
+        for surface in self.surfaces.iter() {
+            surface.to_writer(writer)?;
+        }
+
+        for ground_mesh_cube in self.ground_mesh_cubes.iter() {
+            ground_mesh_cube.to_writer(writer, &self.version)?;
+        }
+
+        match &self.water {
+            Some(water) if self.version >= Version(1, 8, 0) => {
+                water.to_writer(writer, &self.version)?
+            }
+            None if self.version < Version(1, 8, 0) => (),
+            _ => return Err(Error::MismatchedWater(self.version)),
+        }
+
+        Ok(())
+    }
+
+    /// Water planes of the map, the base plane followed by every plane of
+    /// the grid with a different level
+    pub fn water_planes(&self) -> Box<[WaterPlane]> {
+        self.water
+            .as_ref()
+            .map(WaterGrid::distinct_planes)
+            .unwrap_or_default()
+    }
+
     fn read_signature(mut reader: &mut dyn Read) -> Result<Box<str>, error::Error> {
         let signature = {
             let buffer: [u8; 4] = reader.read_array()?;
@@ -103,52 +209,6 @@ impl Gnd {
         Ok(Version(major, minor, 0))
     }
 
-    fn read_water_planes(
-        mut reader: &mut dyn Read,
-        version: &Version,
-    ) -> Result<Box<[WaterPlane]>, Error> {
-        if version < &Version(1, 8, 0) {
-            Ok(Box::new([]))
-        } else if version < &Version(1, 9, 0) {
-            let base_water_plane = WaterPlane::from_reader(reader)?;
-            let horizontal = reader.read_le_i32()?;
-            let vertical = reader.read_le_i32()?;
-            let extras = (0..(horizontal * vertical))
-                .map(|_| {
-                    let level = reader.read_le_f32()?;
-                    let mut water_plane = base_water_plane;
-                    water_plane.water_level = level;
-                    Ok(water_plane)
-                })
-                .collect::<Result<Vec<WaterPlane>, Error>>()?;
-            let base_water_level = base_water_plane.water_level;
-            Ok([base_water_plane]
-                .into_iter()
-                .chain(
-                    extras.into_iter().filter(|plane| {
-                        (plane.water_level - base_water_level).abs() > f32::EPSILON
-                    }),
-                )
-                .collect())
-        } else {
-            let base_water_plane = WaterPlane::from_reader(reader)?;
-            let horizontal = reader.read_le_i32()?;
-            let vertical = reader.read_le_i32()?;
-            let extras = (0..(horizontal * vertical))
-                .map(|_| WaterPlane::from_reader(reader).map_err(Error::from))
-                .collect::<Result<Vec<WaterPlane>, Error>>()?;
-            let base_water_level = base_water_plane.water_level;
-            Ok([base_water_plane]
-                .into_iter()
-                .chain(
-                    extras.into_iter().filter(|plane| {
-                        (plane.water_level - base_water_level).abs() > f32::EPSILON
-                    }),
-                )
-                .collect())
-        }
-    }
-
     /// Get the cube for given coordinate
     pub fn get_cube(&self, x: usize, z: usize) -> Option<&GroundMeshCube> {
         let Ok(width) = usize::try_from(self.width) else {
@@ -469,3 +529,174 @@ fn normalize(vector: [f32; 3]) -> [f32; 3] {
     let length = crate::length(vector);
     [vector[0] / length, vector[1] / length, vector[2] / length]
 }
+
+#[cfg(test)]
+mod test {
+    use ragnarok_rebuild_common::Version;
+
+    use crate::Gnd;
+
+    /// A 1x1 GND with two lightmaps, an upwards surface, and a water grid
+    /// of two planes at the same level on versions that store water
+    fn gnd_bytes(minor: u8) -> Vec<u8> {
+        let mut bytes = b"GRGN".to_vec();
+        bytes.extend_from_slice(&[1, minor]);
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&10f32.to_le_bytes());
+
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&80u32.to_le_bytes());
+        let mut texture = b"data\\texture.bmp".to_vec();
+        texture.resize(80, 0);
+        bytes.extend_from_slice(&texture);
+
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        if minor >= 5 {
+            bytes.extend_from_slice(&1i32.to_le_bytes());
+        }
+        for lightmap in 0..2u8 {
+            bytes.extend_from_slice(&[lightmap; 64]);
+            bytes.extend_from_slice(&[lightmap + 10; 192]);
+        }
+
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for uv in [0f32, 1., 0., 1., 0., 0., 1., 1.] {
+            bytes.extend_from_slice(&uv.to_le_bytes());
+        }
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&1i16.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3, 4]);
+
+        for height in [0f32, 1., 2., 3.] {
+            bytes.extend_from_slice(&height.to_le_bytes());
+        }
+        if minor >= 6 {
+            for surface in [0i32, -1, -1] {
+                bytes.extend_from_slice(&surface.to_le_bytes());
+            }
+        } else {
+            for surface in [0u16, u16::MAX, u16::MAX] {
+                bytes.extend_from_slice(&surface.to_le_bytes());
+            }
+        }
+
+        if minor >= 8 {
+            let water_plane = |bytes: &mut Vec<u8>| {
+                bytes.extend_from_slice(&2f32.to_le_bytes());
+                bytes.extend_from_slice(&0i32.to_le_bytes());
+                bytes.extend_from_slice(&1f32.to_le_bytes());
+                bytes.extend_from_slice(&2f32.to_le_bytes());
+                bytes.extend_from_slice(&50f32.to_le_bytes());
+                bytes.extend_from_slice(&3i32.to_le_bytes());
+            };
+            water_plane(&mut bytes);
+            bytes.extend_from_slice(&2i32.to_le_bytes());
+            bytes.extend_from_slice(&1i32.to_le_bytes());
+            for _ in 0..2 {
+                if minor >= 9 {
+                    water_plane(&mut bytes);
+                } else {
+                    bytes.extend_from_slice(&2f32.to_le_bytes());
+                }
+            }
+        }
+
+        bytes
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn read_versions() {
+        // No pixel format, u16 surface ids and no water
+        let gnd = Gnd::from_reader(&mut gnd_bytes(4).as_slice()).unwrap();
+        assert_eq!(gnd.lightmap.pixel_format, 1);
+        assert_eq!(gnd.ground_mesh_cubes[0].east_facing_surface, -1);
+        assert!(gnd.water.is_none());
+
+        let gnd = Gnd::from_reader(&mut gnd_bytes(9).as_slice()).unwrap();
+        let Some(water) = gnd.water else {
+            unreachable!("GND V1.9 has water.");
+        };
+        assert_eq!(water.planes[1].water_level, 2.);
+        assert_eq!(water.planes[1].wave_height, 1.);
+
+        assert!(matches!(
+            Gnd::from_reader(&mut gnd_bytes(10).as_slice()),
+            Err(crate::Error::UnknownVersion(Version(1, 10, 0)))
+        ));
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn write_round_trip() {
+        for minor in [5, 7, 8, 9] {
+            let bytes = gnd_bytes(minor);
+            let gnd = Gnd::from_reader(&mut bytes.as_slice()).unwrap();
+            assert_eq!(gnd.ground_mesh_cubes[0].north_facing_surface, -1);
+            // Lightmaps are stored one after the other, each with its shadowmap first
+            assert_eq!(gnd.lightmap.shadow_map_pixels[1][0], 1);
+            assert_eq!(gnd.lightmap.light_map_pixels[1][0], 11);
+            assert_eq!(gnd.surfaces[0].bottom_left_vertex_color, [3, 2, 1, 4]);
+            assert_eq!(
+                gnd.water.as_ref().map(|water| water.planes.len()),
+                (minor >= 8).then_some(2)
+            );
+            assert_eq!(gnd.water_planes().len(), usize::from(minor >= 8));
+
+            let mut written = vec![];
+            gnd.to_writer(&mut written).unwrap();
+            assert_eq!(written, bytes, "GND V1.{minor}");
+        }
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn write_raw_textures() {
+        let mut bytes = gnd_bytes(7);
+        // Texture paths start after the header, scale and texture count and length
+        let mut texture = b"DATA\\Texture.BMP\0junk".to_vec();
+        texture.resize(80, 0);
+        bytes[26..106].copy_from_slice(&texture);
+
+        let mut gnd = Gnd::from_reader(&mut bytes.as_slice()).unwrap();
+        assert_eq!(&*gnd.textures[0], "data/texture.bmp");
+        let mut written = vec![];
+        gnd.to_writer(&mut written).unwrap();
+        assert_eq!(written, bytes);
+
+        gnd.textures[0] = "data/Other.bmp".into();
+        let mut written = vec![];
+        gnd.to_writer(&mut written).unwrap();
+        let mut texture = b"data\\Other.bmp".to_vec();
+        texture.resize(80, 0);
+        assert_eq!(written[26..106], texture);
+    }
+
+    /// Maps are mostly lit and have little colored light, reading the
+    /// lightmaps in the wrong order would mix colors into the shadowmaps
+    #[test]
+    #[cfg(feature = "ragnarok_grf")]
+    #[ignore = "Needs the data.grf of the game at the root of the workspace"]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn read_real_lightmaps() {
+        use std::path::Path;
+
+        let grf =
+            ragnarok_grf::Grf::new(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../../data.grf"))
+                .unwrap();
+        let bytes = grf.read_file(Path::new("data/prontera.gnd")).unwrap();
+        let gnd = Gnd::from_reader(&mut bytes.as_ref()).unwrap();
+
+        let average = |pixels: &[Box<[u8]>]| {
+            let pixels = pixels.iter().flatten();
+            pixels.clone().map(|pixel| u64::from(*pixel)).sum::<u64>()
+                / pixels.count().max(1) as u64
+        };
+        assert!(
+            average(&gnd.lightmap.shadow_map_pixels) > 2 * average(&gnd.lightmap.light_map_pixels)
+        );
+    }
+}
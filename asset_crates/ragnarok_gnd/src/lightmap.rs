@@ -1,6 +1,6 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
-use ragnarok_rebuild_common::reader_ext::ReaderExt;
+use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
 #[derive(Debug)]
 pub struct Lightmap {
@@ -12,18 +12,30 @@ pub struct Lightmap {
 }
 
 impl Lightmap {
-    pub fn from_reader(mut reader: &mut dyn Read) -> Result<Self, super::Error> {
+    /// Pixel format used by GNDs that predate the field
+    const DEFAULT_PIXEL_FORMAT: i32 = 1;
+
+    pub fn from_reader(mut reader: &mut dyn Read, version: &Version) -> Result<Self, super::Error> {
         let lightmap_count = reader.read_le_u32()?;
         let width = reader.read_le_u32()?;
         let height = reader.read_le_u32()?;
-        let pixel_format = reader.read_le_i32()?;
+        let pixel_format = if version >= &Version(1, 5, 0) {
+            reader.read_le_i32()?
+        } else {
+            Self::DEFAULT_PIXEL_FORMAT
+        };
 
-        let shadow_map_pixels = (0..(lightmap_count))
-            .map(|_| Self::read_shadowmap_data(reader, width, height))
-            .collect::<Result<Box<[_]>, super::Error>>()?;
-        let light_map_pixels = (0..(lightmap_count))
-            .map(|_| Self::read_lightmap_data(reader, width, height))
-            .collect::<Result<Box<[_]>, super::Error>>()?;
+        // Each lightmap stores its shadowmap followed by its lightmap
+        let (shadow_map_pixels, light_map_pixels) = (0..(lightmap_count))
+            .map(|_| {
+                Ok((
+                    Self::read_shadowmap_data(reader, width, height)?,
+                    Self::read_lightmap_data(reader, width, height)?,
+                ))
+            })
+            .collect::<Result<(Vec<_>, Vec<_>), super::Error>>()?;
+        let shadow_map_pixels = shadow_map_pixels.into_boxed_slice();
+        let light_map_pixels = light_map_pixels.into_boxed_slice();
 
         Ok(Self {
             pixel_format,
@@ -34,6 +46,36 @@ impl Lightmap {
         })
     }
 
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), super::Error> {
+        if self.shadow_map_pixels.len() != self.light_map_pixels.len() {
+            return Err(super::Error::MismatchedLightmaps(
+                self.shadow_map_pixels.len(),
+                self.light_map_pixels.len(),
+            ));
+        }
+        let Ok(lightmap_count) = u32::try_from(self.shadow_map_pixels.len()) else {
+            return Err(super::Error::TooManyElements("lightmaps"));
+        };
+
+        writer.write_all(&lightmap_count.to_le_bytes())?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        if version >= &Version(1, 5, 0) {
+            writer.write_all(&self.pixel_format.to_le_bytes())?;
+        }
+
+        let shadow_map_len = self.width as usize * self.height as usize;
+        for (shadow_map, light_map) in self.shadow_map_pixels.iter().zip(&self.light_map_pixels) {
+            if shadow_map.len() != shadow_map_len || light_map.len() != shadow_map_len * 3 {
+                return Err(super::Error::WrongLightmapSize(self.width, self.height));
+            }
+            writer.write_all(shadow_map)?;
+            writer.write_all(light_map)?;
+        }
+
+        Ok(())
+    }
+
     fn read_shadowmap_data(
         mut reader: &mut dyn Read,
         width: u32,
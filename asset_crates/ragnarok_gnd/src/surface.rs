@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 
@@ -33,6 +33,24 @@ impl Surface {
         })
     }
 
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), super::Error> {
+        let [blu, blv] = self.bottom_left;
+        let [bru, brv] = self.bottom_right;
+        let [tlu, tlv] = self.top_left;
+        let [tru, trv] = self.top_right;
+        for uv in [blu, bru, tlu, tru, blv, brv, tlv, trv] {
+            writer.write_all(&uv.to_le_bytes())?;
+        }
+
+        writer.write_all(&self.texture_id.to_le_bytes())?;
+        writer.write_all(&self.lightmap_id.to_le_bytes())?;
+
+        let [r, g, b, a] = self.bottom_left_vertex_color;
+        writer.write_all(&[b, g, r, a])?;
+
+        Ok(())
+    }
+
     fn read_uvs(mut reader: &mut dyn Read) -> Result<[[f32; 2]; 4], super::Error> {
         let blu = reader.read_le_f32()?;
         let bru = reader.read_le_f32()?;
@@ -0,0 +1,114 @@
+use std::io::{Read, Write};
+
+use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
+use ragnarok_water_plane::WaterPlane;
+
+/// Water of a GND, split in a grid of `horizontal` by `vertical` planes
+#[derive(Debug)]
+pub struct WaterGrid {
+    pub base: WaterPlane,
+    pub horizontal: i32,
+    pub vertical: i32,
+    /// Planes of the grid, on version 1.8 only their `water_level` is
+    /// stored and the rest is taken from `base`
+    pub planes: Box<[WaterPlane]>,
+}
+
+/// How the water is stored on each version of the GND
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaterLayout {
+    /// Before 1.8, the water is on the RSW
+    None,
+    /// 1.8, the grid only stores the level of each plane
+    Levels,
+    /// 1.9, the grid stores full [`WaterPlane`]s
+    Planes,
+}
+
+impl WaterLayout {
+    fn of(version: &Version) -> Result<Self, super::Error> {
+        match version {
+            Version(1, 0..=7, 0) => Ok(Self::None),
+            Version(1, 8, 0) => Ok(Self::Levels),
+            Version(1, 9, 0) => Ok(Self::Planes),
+            // No layout is known past 1.9, reading them as 1.9 would
+            // silently produce garbage
+            version => Err(super::Error::UnknownVersion(*version)),
+        }
+    }
+}
+
+impl WaterGrid {
+    /// Reads the water of the GND, see [`WaterLayout`].
+    pub fn from_reader(
+        mut reader: &mut dyn Read,
+        version: &Version,
+    ) -> Result<Option<Self>, super::Error> {
+        let layout = WaterLayout::of(version)?;
+        if layout == WaterLayout::None {
+            return Ok(None);
+        }
+
+        let base = WaterPlane::from_reader(reader)?;
+        let horizontal = reader.read_le_i32()?;
+        let vertical = reader.read_le_i32()?;
+        let planes = (0..(horizontal * vertical))
+            .map(|_| match layout {
+                WaterLayout::Levels => {
+                    let mut water_plane = base;
+                    water_plane.water_level = reader.read_le_f32()?;
+                    Ok(water_plane)
+                }
+                _ => Ok(WaterPlane::from_reader(reader)?),
+            })
+            .collect::<Result<Box<[_]>, super::Error>>()?;
+
+        Ok(Some(Self {
+            base,
+            horizontal,
+            vertical,
+            planes,
+        }))
+    }
+
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), super::Error> {
+        let layout = WaterLayout::of(version)?;
+        if layout == WaterLayout::None {
+            return Err(super::Error::MismatchedWater(*version));
+        }
+        if self.planes.len() as i64 != i64::from(self.horizontal) * i64::from(self.vertical) {
+            return Err(super::Error::WrongWaterPlaneCount(
+                self.horizontal,
+                self.vertical,
+                self.planes.len(),
+            ));
+        }
+
+        self.base.to_writer(writer)?;
+        writer.write_all(&self.horizontal.to_le_bytes())?;
+        writer.write_all(&self.vertical.to_le_bytes())?;
+        for water_plane in self.planes.iter() {
+            match layout {
+                WaterLayout::Levels => writer.write_all(&water_plane.water_level.to_le_bytes())?,
+                _ => water_plane.to_writer(writer)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The base plane followed by every plane of the grid with a
+    /// different level than it
+    pub fn distinct_planes(&self) -> Box<[WaterPlane]> {
+        let base_water_level = self.base.water_level;
+        [self.base]
+            .into_iter()
+            .chain(
+                self.planes
+                    .iter()
+                    .copied()
+                    .filter(|plane| (plane.water_level - base_water_level).abs() > f32::EPSILON),
+            )
+            .collect()
+    }
+}
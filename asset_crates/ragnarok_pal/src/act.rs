@@ -0,0 +1,52 @@
+//! Conversion from and to Adobe color tables.
+
+use std::io::{Read, Write};
+
+use crate::{Error, Pal};
+
+impl Pal {
+    /// Size of the color table, 256 RGB colors
+    const ACT_TABLE_SIZE: usize = 768;
+    /// Size of the color table followed by the color count and the
+    /// transparent color index
+    const ACT_EXTENDED_SIZE: usize = 772;
+
+    pub fn from_act(reader: &mut dyn Read) -> Result<Self, Error> {
+        let mut contents = vec![];
+        reader.read_to_end(&mut contents)?;
+
+        let count = match contents.len() {
+            Self::ACT_TABLE_SIZE => 256,
+            Self::ACT_EXTENDED_SIZE => {
+                match u16::from_be_bytes([contents[768], contents[769]]) {
+                    // Some tools write 0 meaning all colors
+                    0 => 256,
+                    count => usize::from(count),
+                }
+            }
+            len => return Err(Error::InvalidAct(len)),
+        };
+        if count > 256 {
+            return Err(Error::TooManyColors(count));
+        }
+
+        let rgb = contents[..Self::ACT_TABLE_SIZE]
+            .chunks_exact(3)
+            .take(count)
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+            .collect::<Vec<_>>();
+
+        Self::from_rgb(&rgb)
+    }
+
+    pub fn to_act(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        for color in self.colors.iter() {
+            writer.write_all(&[color.red, color.green, color.blue])?;
+        }
+        // Color count
+        writer.write_all(&256u16.to_be_bytes())?;
+        // The first color is the key color
+        writer.write_all(&0u16.to_be_bytes())?;
+        Ok(())
+    }
+}
@@ -1,10 +1,81 @@
-use std::{io::Cursor, path::Path};
+//! Debug tool for Ragnarok Online palettes.
+//!
+//! ## Usage
+//!
+//! * `pal_debug`: Reports warnings of all palettes of `data.grf`.
+//! * `pal_debug convert <input> <output> [format]`: Converts a palette between formats.
+//! * `pal_debug export <palette> <output> [format]`: Exports a palette from `data.grf`.
+//!
+//! Formats are `ro`, `jasc`, `gpl`, `act`, and `png`, when not given they are
+//! inferred from the extension of the file. Input `.pal` files can be
+//! either `ro` or `jasc`.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Cursor, Read},
+    path::Path,
+};
 
 use ragnarok_grf::Grf;
 use ragnarok_pal::Pal;
 use ragnarok_rebuild_common::warning::ReportWarning;
 
+/// Size of each swatch when exporting to `png`
+const SWATCH_SIZE: u32 = 16;
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Ragnarok,
+    Jasc,
+    Gimp,
+    Act,
+    Swatch,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ro" | "pal" => Some(Self::Ragnarok),
+            "jasc" => Some(Self::Jasc),
+            "gpl" => Some(Self::Gimp),
+            "act" => Some(Self::Act),
+            "png" => Some(Self::Swatch),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_name)
+    }
+}
+
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] => report(),
+        ["convert", input, output, format @ ..] => {
+            convert(Path::new(input), Path::new(output), format.first().copied())
+        }
+        ["export", palette, output, format @ ..] => export(
+            Path::new(palette),
+            Path::new(output),
+            format.first().copied(),
+        ),
+        _ => println!(
+            "Usage: pal_debug [convert <input> <output> [format] | export <palette> <output> [format]]"
+        ),
+    }
+}
+
+fn report() {
     #[expect(clippy::unwrap_used, reason = "This is a test")]
     let grf = Grf::new(Path::new("data.grf")).unwrap();
 
@@ -36,3 +107,80 @@ fn main() {
         }
     }
 }
+
+fn convert(input: &Path, output: &Path, format: Option<&str>) {
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    let content = std::fs::read(input).unwrap();
+
+    let Some(input_format) = Format::from_path(input).map(|format| match format {
+        Format::Ragnarok if content.starts_with(b"JASC-PAL") => Format::Jasc,
+        format => format,
+    }) else {
+        println!("{input:?}: Unknown palette format.");
+        return;
+    };
+
+    let Ok(pal) = read_palette(&mut content.as_slice(), input_format)
+        .inspect_err(|err| println!("{input:?}: {err}"))
+    else {
+        return;
+    };
+
+    write_palette(&pal, output, format);
+}
+
+fn export(palette: &Path, output: &Path, format: Option<&str>) {
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+
+    let Ok(pal_content) = grf
+        .read_file(palette)
+        .inspect_err(|err| println!("{palette:?}: {err}"))
+    else {
+        return;
+    };
+    let Ok(pal) = Pal::from_reader(&mut Cursor::new(&pal_content))
+        .inspect_err(|err| println!("{palette:?}: {err}"))
+    else {
+        return;
+    };
+
+    write_palette(&pal, output, format);
+}
+
+fn read_palette(reader: &mut dyn Read, format: Format) -> Result<Pal, ragnarok_pal::Error> {
+    match format {
+        Format::Ragnarok => Pal::from_reader(reader),
+        Format::Jasc => Pal::from_jasc_pal(reader),
+        Format::Gimp => Pal::from_gpl(reader),
+        Format::Act => Pal::from_act(reader),
+        Format::Swatch => Pal::from_swatch_png(reader),
+    }
+}
+
+fn write_palette(pal: &Pal, output: &Path, format: Option<&str>) {
+    let Some(output_format) = format.map_or_else(|| Format::from_path(output), Format::from_name)
+    else {
+        println!("{output:?}: Unknown palette format.");
+        return;
+    };
+
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    let mut writer = BufWriter::new(File::create(output).unwrap());
+
+    let name = output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Palette");
+    let result = match output_format {
+        Format::Ragnarok => pal.to_writer(&mut writer),
+        Format::Jasc => pal.to_jasc_pal(&mut writer),
+        Format::Gimp => pal.to_gpl(&mut writer, name),
+        Format::Act => pal.to_act(&mut writer),
+        Format::Swatch => pal.to_swatch_png(&mut writer, SWATCH_SIZE),
+    };
+
+    if let Err(err) = result {
+        println!("{output:?}: {err}");
+    }
+}
@@ -2,6 +2,16 @@ use std::fmt::Display;
 
 #[derive(Debug)]
 pub enum Error {
+    InvalidJascPal(String),
+    InvalidGimpPalette(String),
+    InvalidAct(usize),
+    TooManyColors(usize),
+    #[cfg(feature = "png")]
+    InvalidSwatch(u32, u32),
+    #[cfg(feature = "png")]
+    PngDecoding(png::DecodingError),
+    #[cfg(feature = "png")]
+    PngEncoding(png::EncodingError),
     Io(std::io::Error),
 }
 
@@ -11,10 +21,42 @@ impl From<std::io::Error> for Error {
     }
 }
 
+#[cfg(feature = "png")]
+impl From<png::DecodingError> for Error {
+    fn from(value: png::DecodingError) -> Self {
+        Self::PngDecoding(value)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for Error {
+    fn from(value: png::EncodingError) -> Self {
+        Self::PngEncoding(value)
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let msg = match self {
-            Self::Io(io) => format!("An IO error occurred while reading a Palette. '{io}'"),
+            Self::InvalidJascPal(reason) => format!("Invalid JASC-PAL palette. {reason}"),
+            Self::InvalidGimpPalette(reason) => format!("Invalid GIMP palette. {reason}"),
+            Self::InvalidAct(len) => {
+                format!("Adobe color table had {len} bytes, expected 768 or 772.")
+            }
+            Self::TooManyColors(count) => {
+                format!("Palette had {count} colors, but a Palette can only hold 256.")
+            }
+            #[cfg(feature = "png")]
+            Self::InvalidSwatch(width, height) => {
+                format!("Swatch image had size {width}x{height}, expected a multiple of 16x16.")
+            }
+            #[cfg(feature = "png")]
+            Self::PngDecoding(err) => format!("Could not decode swatch image. '{err}'"),
+            #[cfg(feature = "png")]
+            Self::PngEncoding(err) => format!("Could not encode swatch image. '{err}'"),
+            Self::Io(io) => {
+                format!("An IO error occurred while reading or writing a Palette. '{io}'")
+            }
         };
         write!(f, "{msg}")
     }
@@ -0,0 +1,56 @@
+//! Conversion from and to GIMP palettes.
+
+use std::io::{Read, Write};
+
+use crate::{Error, Pal};
+
+impl Pal {
+    const GPL_SIGNATURE: &str = "GIMP Palette";
+
+    pub fn from_gpl(reader: &mut dyn Read) -> Result<Self, Error> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        let mut lines = contents.lines().map(str::trim);
+
+        if lines.next() != Some(Self::GPL_SIGNATURE) {
+            return Err(Error::InvalidGimpPalette("Missing signature.".to_owned()));
+        }
+
+        let rgb = lines
+            .filter(|line| {
+                !(line.is_empty()
+                    || line.starts_with('#')
+                    || line.starts_with("Name:")
+                    || line.starts_with("Columns:"))
+            })
+            .map(|line| {
+                // Colors may be followed by a name
+                let mut channels = line.split_whitespace().map(str::parse::<u8>);
+                match (channels.next(), channels.next(), channels.next()) {
+                    (Some(Ok(red)), Some(Ok(green)), Some(Ok(blue))) => Ok([red, green, blue]),
+                    _ => Err(Error::InvalidGimpPalette(format!(
+                        "Invalid color '{line}'."
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rgb(&rgb)
+    }
+
+    pub fn to_gpl(&self, writer: &mut dyn Write, name: &str) -> Result<(), Error> {
+        writeln!(writer, "{}", Self::GPL_SIGNATURE)?;
+        writeln!(writer, "Name: {name}")?;
+        writeln!(writer, "Columns: 16")?;
+        writeln!(writer, "#")?;
+        for (index, color) in self.colors.iter().enumerate() {
+            writeln!(
+                writer,
+                "{:>3} {:>3} {:>3}\tIndex {index}",
+                color.red, color.green, color.blue
+            )?;
+        }
+        Ok(())
+    }
+}
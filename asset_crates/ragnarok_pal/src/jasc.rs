@@ -0,0 +1,67 @@
+//! Conversion from and to JASC-PAL, the palette format of Paint Shop Pro,
+//! also used by Aseprite.
+
+use std::io::{Read, Write};
+
+use crate::{Error, Pal};
+
+impl Pal {
+    const JASC_SIGNATURE: &str = "JASC-PAL";
+    const JASC_VERSION: &str = "0100";
+
+    pub fn from_jasc_pal(reader: &mut dyn Read) -> Result<Self, Error> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        let mut lines = contents.lines().map(str::trim);
+
+        if lines.next() != Some(Self::JASC_SIGNATURE) {
+            return Err(Error::InvalidJascPal("Missing signature.".to_owned()));
+        }
+        if lines.next() != Some(Self::JASC_VERSION) {
+            return Err(Error::InvalidJascPal("Unknown version.".to_owned()));
+        }
+        let Some(Ok(count)) = lines.next().map(str::parse::<usize>) else {
+            return Err(Error::InvalidJascPal("Missing color count.".to_owned()));
+        };
+        if count > 256 {
+            return Err(Error::TooManyColors(count));
+        }
+
+        let rgb = lines
+            .filter(|line| !line.is_empty())
+            .take(count)
+            .map(|line| {
+                let mut channels = line.split_whitespace().map(str::parse::<u8>);
+                match (channels.next(), channels.next(), channels.next()) {
+                    (Some(Ok(red)), Some(Ok(green)), Some(Ok(blue))) => Ok([red, green, blue]),
+                    _ => Err(Error::InvalidJascPal(format!("Invalid color '{line}'."))),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if rgb.len() != count {
+            return Err(Error::InvalidJascPal(format!(
+                "Expected {count} colors but found {}.",
+                rgb.len()
+            )));
+        }
+
+        Self::from_rgb(&rgb)
+    }
+
+    pub fn to_jasc_pal(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        // JASC-PAL uses CRLF line endings
+        write!(
+            writer,
+            "{}\r\n{}\r\n{}\r\n",
+            Self::JASC_SIGNATURE,
+            Self::JASC_VERSION,
+            self.colors.len()
+        )?;
+        for color in self.colors.iter() {
+            write!(writer, "{} {} {}\r\n", color.red, color.green, color.blue)?;
+        }
+        Ok(())
+    }
+}
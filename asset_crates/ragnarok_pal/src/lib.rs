@@ -1,8 +1,13 @@
+mod act;
 mod error;
+mod gpl;
+mod jasc;
+#[cfg(feature = "png")]
+mod swatch;
 #[cfg(feature = "warning")]
 pub mod warnings;
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use ragnarok_rebuild_common::{Color, reader_ext::ReaderExt};
 
@@ -29,4 +34,100 @@ impl Pal {
             }),
         }
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> [u8; 1024] {
+        std::array::from_fn(|index| {
+            let color = &self.colors[index / 4];
+            match index % 4 {
+                0 => color.red,
+                1 => color.green,
+                2 => color.blue,
+                _ => color.alpha,
+            }
+        })
+    }
+
+    /// Builds a [`Pal`] from RGB triplets of formats that do not store
+    /// the alpha channel.
+    ///
+    /// Missing colors are filled with black, and the alpha of all colors
+    /// is set to 0, like in the Ragnarok Online palettes.
+    fn from_rgb(rgb: &[[u8; 3]]) -> Result<Self, Error> {
+        if rgb.len() > 256 {
+            return Err(Error::TooManyColors(rgb.len()));
+        }
+
+        Ok(Self {
+            colors: std::array::from_fn(|index| {
+                let [red, green, blue] = rgb.get(index).copied().unwrap_or_default();
+                Color {
+                    red,
+                    green,
+                    blue,
+                    alpha: 0,
+                }
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_rebuild_common::Color;
+
+    use crate::Pal;
+
+    fn test_palette() -> Pal {
+        Pal {
+            colors: std::array::from_fn(|index| Color {
+                red: index as u8,
+                green: 255 - index as u8,
+                blue: (index as u8).wrapping_mul(7),
+                alpha: 0,
+            }),
+        }
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn round_trip() {
+        let pal = test_palette();
+
+        let mut ro = vec![];
+        pal.to_writer(&mut ro).unwrap();
+        let from_ro = Pal::from_reader(&mut ro.as_slice()).unwrap();
+        assert_eq!(from_ro.colors, pal.colors);
+
+        let mut jasc = vec![];
+        pal.to_jasc_pal(&mut jasc).unwrap();
+        let from_jasc = Pal::from_jasc_pal(&mut jasc.as_slice()).unwrap();
+        assert_eq!(from_jasc.colors, pal.colors);
+
+        let mut gpl = vec![];
+        pal.to_gpl(&mut gpl, "test").unwrap();
+        let from_gpl = Pal::from_gpl(&mut gpl.as_slice()).unwrap();
+        assert_eq!(from_gpl.colors, pal.colors);
+
+        let mut act = vec![];
+        pal.to_act(&mut act).unwrap();
+        let from_act = Pal::from_act(&mut act.as_slice()).unwrap();
+        assert_eq!(from_act.colors, pal.colors);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn swatch_round_trip() {
+        let pal = test_palette();
+
+        let mut png = vec![];
+        pal.to_swatch_png(&mut png, 4).unwrap();
+        let from_png = Pal::from_swatch_png(&mut png.as_slice()).unwrap();
+        assert_eq!(from_png.colors, pal.colors);
+    }
 }
@@ -0,0 +1,82 @@
+//! Conversion from and to a 16x16 grid of color swatches stored as a PNG.
+
+use std::io::{Cursor, Read, Write};
+
+use crate::{Error, Pal};
+
+impl Pal {
+    /// Number of swatches per row and column
+    const SWATCH_GRID: u32 = 16;
+
+    /// Reads a palette from a PNG with 16x16 swatches, sampling
+    /// the center of each swatch.
+    pub fn from_swatch_png(reader: &mut dyn Read) -> Result<Self, Error> {
+        let mut contents = vec![];
+        reader.read_to_end(&mut contents)?;
+
+        let mut decoder = png::Decoder::new(Cursor::new(contents));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut png_reader = decoder.read_info()?;
+
+        let Some(buffer_size) = png_reader.output_buffer_size() else {
+            let info = png_reader.info();
+            return Err(Error::InvalidSwatch(info.width, info.height));
+        };
+        let mut pixels = vec![0; buffer_size];
+        let frame = png_reader.next_frame(&mut pixels)?;
+
+        if frame.width == 0
+            || frame.height == 0
+            || frame.width % Self::SWATCH_GRID != 0
+            || frame.height % Self::SWATCH_GRID != 0
+        {
+            return Err(Error::InvalidSwatch(frame.width, frame.height));
+        }
+
+        let swatch_width = frame.width / Self::SWATCH_GRID;
+        let swatch_height = frame.height / Self::SWATCH_GRID;
+        let pixel_size = frame.color_type.samples();
+
+        let rgb = (0..(Self::SWATCH_GRID * Self::SWATCH_GRID))
+            .map(|index| {
+                let x = (index % Self::SWATCH_GRID) * swatch_width + swatch_width / 2;
+                let y = (index / Self::SWATCH_GRID) * swatch_height + swatch_height / 2;
+                let start = y as usize * frame.line_size + x as usize * pixel_size;
+                let pixel = &pixels[start..(start + pixel_size)];
+                match frame.color_type {
+                    png::ColorType::Grayscale | png::ColorType::GrayscaleAlpha => {
+                        [pixel[0], pixel[0], pixel[0]]
+                    }
+                    _ => [pixel[0], pixel[1], pixel[2]],
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_rgb(&rgb)
+    }
+
+    /// Writes the palette as a PNG with 16x16 swatches, each swatch
+    /// is a square of `swatch_size` pixels.
+    pub fn to_swatch_png(&self, writer: &mut dyn Write, swatch_size: u32) -> Result<(), Error> {
+        let swatch_size = swatch_size.max(1);
+        let size = Self::SWATCH_GRID * swatch_size;
+
+        let pixels = (0..size)
+            .flat_map(|y| (0..size).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let index = (y / swatch_size) * Self::SWATCH_GRID + x / swatch_size;
+                let color = &self.colors[index as usize];
+                [color.red, color.green, color.blue]
+            })
+            .collect::<Vec<_>>();
+
+        let mut encoder = png::Encoder::new(writer, size, size);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&pixels)?;
+        png_writer.finish()?;
+
+        Ok(())
+    }
+}
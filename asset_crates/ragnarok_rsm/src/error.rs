@@ -9,6 +9,8 @@ pub enum Error {
     InvalidMeshName,
     InvalidShadeType(i32),
     IncompleteRead(Version, usize),
+    TooManyElements(&'static str),
+    UnrepresentableData(Version, &'static str),
 }
 
 impl From<io::Error> for Error {
@@ -34,6 +36,12 @@ impl std::fmt::Display for Error {
                 f,
                 "Could not read RSM to the end. RSM V{version} had {unread} unread bytes."
             ),
+            Self::TooManyElements(elements) => {
+                write!(f, "RSM had too many {elements} to be written.")
+            }
+            Self::UnrepresentableData(version, data) => {
+                write!(f, "{data} can't be written on RSM V{version}.")
+            }
         }
     }
 }
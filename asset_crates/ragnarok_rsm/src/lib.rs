@@ -4,9 +4,13 @@ mod volume_box;
 #[cfg(feature = "warning")]
 pub mod warnings;
 
-use std::io::Read;
+use std::io::{Read, Write};
 
-use ragnarok_rebuild_common::{Version, euc_kr::read_n_euc_kr_strings, reader_ext::ReaderExt};
+use ragnarok_rebuild_common::{
+    Version,
+    euc_kr::{read_n_euc_kr_strings, write_n_euc_kr_strings},
+    reader_ext::ReaderExt,
+};
 
 pub use self::{error::Error, volume_box::VolumeBox};
 
@@ -109,6 +113,86 @@ impl Rsm {
         })
     }
 
+    /// Writes the [`Rsm`], the padding skipped by [`Rsm::from_reader`] is
+    /// written as `0`s.
+    ///
+    /// Fails if parts of the [`Rsm`] do not match its version, e.g.
+    /// [`Textures::Paths`](mesh::Textures::Paths) before `2.3`.
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), Error> {
+        if (*self.signature).ne("GRSM") {
+            return Err(Error::InvalidSignature(self.signature.clone()));
+        }
+        let version = self.version;
+
+        let animation_length = match self.animation_duration {
+            AnimationDuration::Simple(duration) if version < Version(2, 2, 0) => duration,
+            AnimationDuration::PerSecond(duration, _) if version >= Version(2, 2, 0) => duration,
+            _ => {
+                return Err(Error::UnrepresentableData(version, "Animation duration"));
+            }
+        };
+        let shade_type: i32 = match self.shade_type {
+            ShadeType::Unlit => 0,
+            ShadeType::Flat => 1,
+            ShadeType::Smooth => 2,
+        };
+
+        writer.write_all(self.signature.as_bytes())?;
+        writer.write_all(&[version.major(), version.minor()])?;
+        writer.write_all(&(animation_length as i32).to_le_bytes())?;
+        writer.write_all(&shade_type.to_le_bytes())?;
+        if version >= Version(1, 4, 0) {
+            writer.write_all(&[self.alpha])?;
+        } else if self.alpha != 0xff {
+            return Err(Error::UnrepresentableData(version, "Alpha"));
+        }
+        if let AnimationDuration::PerSecond(_, per_second) = self.animation_duration {
+            writer.write_all(&per_second.to_le_bytes())?;
+        }
+        if version < Version(2, 2, 0) {
+            writer.write_all(&[0; 16])?;
+        }
+
+        self.write_textures(writer)?;
+        self.write_mesh_names(writer)?;
+
+        write_count(writer, self.meshes.len(), "meshes")?;
+        for mesh in self.meshes.iter() {
+            mesh.to_writer(writer, &version)?;
+        }
+
+        if version < Version(1, 6, 0) {
+            write_count(
+                writer,
+                self.position_key_frames.len(),
+                "position key frames",
+            )?;
+            for position_key_frame in self.position_key_frames.iter() {
+                position_key_frame.to_writer(writer)?;
+            }
+        } else if !self.position_key_frames.is_empty() {
+            return Err(Error::UnrepresentableData(version, "Position key frames"));
+        }
+
+        match &self.volume_boxes {
+            Some(volume_boxes) => {
+                write_count(writer, volume_boxes.len(), "volume boxes")?;
+                for volume_box in volume_boxes.iter() {
+                    volume_box.to_writer(writer, &version)?;
+                }
+            }
+            // Only V2.3 files can end before the volume boxes
+            None if version < Version(2, 3, 0) => writer.write_all(&0u32.to_le_bytes())?,
+            None => (),
+        }
+
+        if version >= Version(1, 5, 0) && version < Version(1, 6, 0) {
+            writer.write_all(&[0; 4])?;
+        }
+
+        Ok(())
+    }
+
     fn read_signature<R: Read>(reader: &mut R) -> Result<Box<str>, Error> {
         let signature = {
             let buffer: [u8; 4] = reader.read_array()?;
@@ -151,6 +235,23 @@ impl Rsm {
         Ok(textures)
     }
 
+    fn write_textures(&self, writer: &mut dyn Write) -> Result<(), self::Error> {
+        if self.version < Version(2, 3, 0) {
+            let len = if self.version >= Version(2, 2, 0) {
+                None
+            } else {
+                Some(40)
+            };
+
+            write_count(writer, self.textures.len(), "textures")?;
+            write_n_euc_kr_strings(writer, &self.textures, len)?;
+        } else if !self.textures.is_empty() {
+            return Err(Error::UnrepresentableData(self.version, "Model textures"));
+        }
+
+        Ok(())
+    }
+
     fn read_meshs_names(
         mut reader: &mut dyn Read,
         version: &Version,
@@ -165,6 +266,22 @@ impl Rsm {
         Ok(mesh_names)
     }
 
+    fn write_mesh_names(&self, writer: &mut dyn Write) -> Result<(), self::Error> {
+        if self.version >= Version(2, 2, 0) {
+            write_count(writer, self.root_meshes.len(), "root meshes")?;
+            write_n_euc_kr_strings(writer, &self.root_meshes, None)?;
+        } else if self.root_meshes.len() == 1 {
+            write_n_euc_kr_strings(writer, &self.root_meshes, Some(40))?;
+        } else {
+            return Err(Error::UnrepresentableData(
+                self.version,
+                "More than one root mesh",
+            ));
+        }
+
+        Ok(())
+    }
+
     fn read_volume_boxes<R: Read>(
         reader: &mut R,
         version: &Version,
@@ -187,6 +304,19 @@ impl Rsm {
     }
 }
 
+/// Writes the number of `elements` as a `u32`
+pub(crate) fn write_count(
+    writer: &mut dyn Write,
+    count: usize,
+    elements: &'static str,
+) -> Result<(), Error> {
+    let Ok(count) = u32::try_from(count) else {
+        return Err(Error::TooManyElements(elements));
+    };
+    writer.write_all(&count.to_le_bytes())?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum AnimationDuration {
     /// Duration is given as a multiple of 1000
@@ -210,3 +340,153 @@ impl AnimationDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ragnarok_rebuild_common::Version;
+
+    use super::{
+        AnimationDuration, Rsm, ShadeType, VolumeBox,
+        mesh::{
+            Face, Mesh, PositionKeyFrame, RotationKeyFrame, ScaleKeyFrame, TextureAnimation,
+            TextureUV, Textures, Transformation,
+        },
+    };
+
+    fn mesh(version: Version) -> Mesh {
+        let v2_2 = version >= Version(2, 2, 0);
+        let v2_3 = version >= Version(2, 3, 0);
+        Mesh {
+            name: "mesh".into(),
+            parent_name: "".into(),
+            textures: if v2_3 {
+                Textures::Paths(["texture/model.bmp".into()].into())
+            } else {
+                Textures::Indexes([0].into())
+            },
+            transformation_matrix: [1., 0., 0., 0., 1., 0., 0., 0., 1.],
+            transformation: if v2_2 {
+                Transformation::Simple([1., 2., 3.])
+            } else {
+                Transformation::Complete {
+                    offset: [1., 2., 3.],
+                    position: [4., 5., 6.],
+                    rotation_angle: 0.5,
+                    rotation_axis: [0., 1., 0.],
+                    scale: [1., -1., 1.],
+                }
+            },
+            vertices: [[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]].into(),
+            uvs: [TextureUV {
+                color: [0xff, 0x80, 0x40, 0xff],
+                uv: [0.25, 0.75],
+            }]
+            .into(),
+            faces: [Face {
+                vertices: [0, 1, 2],
+                uv: [0, 0, 0],
+                texture_id: 0,
+                two_side: 1,
+                smoothing_group: if v2_2 { [1, 2].into() } else { [1].into() },
+            }]
+            .into(),
+            scale_key_frames: if version >= Version(1, 6, 0) {
+                [ScaleKeyFrame {
+                    frame: 10,
+                    scale: [2., 2., 2.],
+                    data: 0.,
+                }]
+                .into()
+            } else {
+                [].into()
+            },
+            rotation_key_frames: [RotationKeyFrame {
+                frame: 20,
+                quaternion: [0., 0., 0., 1.],
+            }]
+            .into(),
+            position_key_frames: if v2_2 {
+                [PositionKeyFrame {
+                    frame: 30,
+                    position: [1., 1., 1.],
+                    data: 0.,
+                }]
+                .into()
+            } else {
+                [].into()
+            },
+            texture_animations: if v2_3 {
+                [TextureAnimation {
+                    texture_id: 0,
+                    animations: [].into(),
+                }]
+                .into()
+            } else {
+                [].into()
+            },
+        }
+    }
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn write_round_trip() {
+        for version in [Version(1, 5, 0), Version(2, 3, 0)] {
+            let v2_2 = version >= Version(2, 2, 0);
+            let rsm = Rsm {
+                signature: "GRSM".into(),
+                version,
+                animation_duration: if v2_2 {
+                    AnimationDuration::PerSecond(60., 30.)
+                } else {
+                    AnimationDuration::Simple(1000.)
+                },
+                shade_type: ShadeType::Flat,
+                alpha: 0x80,
+                textures: if v2_2 {
+                    [].into()
+                } else {
+                    ["texture/model.bmp".into()].into()
+                },
+                root_meshes: ["mesh".into()].into(),
+                meshes: [mesh(version)].into(),
+                position_key_frames: if v2_2 {
+                    [].into()
+                } else {
+                    [PositionKeyFrame {
+                        frame: 0,
+                        position: [0., 1., 0.],
+                        data: 0.,
+                    }]
+                    .into()
+                },
+                volume_boxes: Some(
+                    [VolumeBox {
+                        size: [1., 2., 3.],
+                        position: [4., 5., 6.],
+                        rotation: [0., 0.5, 0.],
+                        flag: 1,
+                    }]
+                    .into(),
+                ),
+            };
+
+            let mut written = vec![];
+            rsm.to_writer(&mut written).unwrap();
+            let read = Rsm::from_reader(&mut written.as_slice()).unwrap();
+            assert_eq!(read.version, version);
+            assert_eq!(read.alpha, 0x80);
+            assert_eq!(read.meshes[0].faces[0].two_side, 1);
+
+            let mut rewritten = vec![];
+            read.to_writer(&mut rewritten).unwrap();
+            assert_eq!(written, rewritten);
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_texture_scheme() {
+        let mut mesh = mesh(Version(2, 3, 0));
+        mesh.textures = Textures::Indexes([0].into());
+        assert!(mesh.to_writer(&mut vec![], &Version(2, 3, 0)).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
@@ -51,4 +51,43 @@ impl Face {
             smoothing_group,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), crate::Error> {
+        if version >= &Version(2, 2, 0) {
+            let Some(len) = self
+                .smoothing_group
+                .len()
+                .checked_mul(4)
+                .and_then(|len| len.checked_add(20))
+                .and_then(|len| i32::try_from(len).ok())
+            else {
+                return Err(crate::Error::TooManyElements("smoothing groups"));
+            };
+            writer.write_all(&len.to_le_bytes())?;
+        } else if version >= &Version(1, 2, 0) && self.smoothing_group.len() != 1 {
+            return Err(crate::Error::UnrepresentableData(
+                *version,
+                "Face without exactly one smoothing group",
+            ));
+        } else if version < &Version(1, 2, 0) && !self.smoothing_group.is_empty() {
+            return Err(crate::Error::UnrepresentableData(
+                *version,
+                "Face smoothing groups",
+            ));
+        }
+
+        for vertex in self.vertices {
+            writer.write_all(&vertex.to_le_bytes())?;
+        }
+        for uv in self.uv {
+            writer.write_all(&uv.to_le_bytes())?;
+        }
+        writer.write_all(&self.texture_id.to_le_bytes())?;
+        writer.write_all(&[0, 0, self.two_side, 0, 0, 0])?;
+        for smoothing_group in self.smoothing_group.iter() {
+            writer.write_all(&smoothing_group.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
 }
@@ -8,10 +8,16 @@ mod texture_uv;
 use std::{
     collections::{HashMap, hash_map::Entry},
     hash::Hash,
-    io::{self, Read},
+    io::{self, Read, Write},
 };
 
-use ragnarok_rebuild_common::{Version, euc_kr::read_n_euc_kr_strings, reader_ext::ReaderExt};
+use ragnarok_rebuild_common::{
+    Version,
+    euc_kr::{read_n_euc_kr_strings, write_n_euc_kr_strings},
+    reader_ext::ReaderExt,
+};
+
+use crate::write_count;
 
 pub use self::{
     face::Face, position_key_frame::PositionKeyFrame, rotation_key_frame::RotationKeyFrame,
@@ -74,6 +80,129 @@ impl Mesh {
         })
     }
 
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), super::Error> {
+        let names = [self.name.clone(), self.parent_name.clone()];
+        if version >= &Version(2, 2, 0) {
+            write_n_euc_kr_strings(writer, &names, None)?;
+        } else {
+            write_n_euc_kr_strings(writer, &names, Some(40))?;
+        }
+
+        match &self.textures {
+            Textures::Paths(textures) if version >= &Version(2, 3, 0) => {
+                write_count(writer, textures.len(), "mesh textures")?;
+                write_n_euc_kr_strings(writer, textures, None)?;
+            }
+            Textures::Indexes(texture_indexes) if version < &Version(2, 3, 0) => {
+                write_count(writer, texture_indexes.len(), "texture indexes")?;
+                for texture_index in texture_indexes.iter() {
+                    writer.write_all(&texture_index.to_le_bytes())?;
+                }
+            }
+            _ => {
+                return Err(super::Error::UnrepresentableData(
+                    *version,
+                    "Mesh texture scheme",
+                ));
+            }
+        }
+
+        for value in self.transformation_matrix {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        match &self.transformation {
+            Transformation::Simple(position) if version >= &Version(2, 2, 0) => {
+                write_f32s(writer, position)?;
+            }
+            Transformation::Complete {
+                offset,
+                position,
+                rotation_angle,
+                rotation_axis,
+                scale,
+            } if version < &Version(2, 2, 0) => {
+                write_f32s(writer, offset)?;
+                write_f32s(writer, position)?;
+                writer.write_all(&rotation_angle.to_le_bytes())?;
+                write_f32s(writer, rotation_axis)?;
+                write_f32s(writer, scale)?;
+            }
+            _ => {
+                return Err(super::Error::UnrepresentableData(
+                    *version,
+                    "Mesh transformation",
+                ));
+            }
+        }
+
+        write_count(writer, self.vertices.len(), "vertices")?;
+        for vertex in self.vertices.iter() {
+            write_f32s(writer, vertex)?;
+        }
+
+        write_count(writer, self.uvs.len(), "uvs")?;
+        for uv in self.uvs.iter() {
+            uv.to_writer(writer, version)?;
+        }
+
+        write_count(writer, self.faces.len(), "faces")?;
+        for face in self.faces.iter() {
+            face.to_writer(writer, version)?;
+        }
+
+        if version >= &Version(1, 6, 0) {
+            write_count(writer, self.scale_key_frames.len(), "scale key frames")?;
+            for scale_key_frame in self.scale_key_frames.iter() {
+                scale_key_frame.to_writer(writer)?;
+            }
+        } else if !self.scale_key_frames.is_empty() {
+            return Err(super::Error::UnrepresentableData(
+                *version,
+                "Scale key frames",
+            ));
+        }
+
+        write_count(
+            writer,
+            self.rotation_key_frames.len(),
+            "rotation key frames",
+        )?;
+        for rotation_key_frame in self.rotation_key_frames.iter() {
+            rotation_key_frame.to_writer(writer)?;
+        }
+
+        if version >= &Version(2, 2, 0) {
+            write_count(
+                writer,
+                self.position_key_frames.len(),
+                "position key frames",
+            )?;
+            for position_key_frame in self.position_key_frames.iter() {
+                position_key_frame.to_writer(writer)?;
+            }
+        } else if !self.position_key_frames.is_empty() {
+            return Err(super::Error::UnrepresentableData(
+                *version,
+                "Mesh position key frames",
+            ));
+        }
+
+        if version >= &Version(2, 3, 0) {
+            write_count(writer, self.texture_animations.len(), "texture animations")?;
+            for texture_animation in self.texture_animations.iter() {
+                texture_animation.to_writer(writer)?;
+            }
+        } else if !self.texture_animations.is_empty() {
+            return Err(super::Error::UnrepresentableData(
+                *version,
+                "Texture animations",
+            ));
+        }
+
+        Ok(())
+    }
+
     fn read_name<R: Read>(
         reader: &mut R,
         version: &Version,
@@ -365,6 +494,13 @@ pub enum Transformation {
     Simple([f32; 3]),
 }
 
+pub(crate) fn write_f32s(writer: &mut dyn Write, values: &[f32]) -> Result<(), io::Error> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
 fn flat_normal(a: &[f32; 3], b: &[f32; 3], c: &[f32; 3]) -> [f32; 3] {
     let v1 = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
     let v2 = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 
@@ -25,4 +25,10 @@ impl PositionKeyFrame {
             data,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), io::Error> {
+        writer.write_all(&self.frame.to_le_bytes())?;
+        super::write_f32s(writer, &self.position)?;
+        writer.write_all(&self.data.to_le_bytes())
+    }
 }
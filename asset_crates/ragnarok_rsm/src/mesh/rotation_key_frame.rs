@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 
@@ -20,4 +20,9 @@ impl RotationKeyFrame {
 
         Ok(Self { frame, quaternion })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), io::Error> {
+        writer.write_all(&self.frame.to_le_bytes())?;
+        super::write_f32s(writer, &self.quaternion)
+    }
 }
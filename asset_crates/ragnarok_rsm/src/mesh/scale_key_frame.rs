@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 
@@ -21,4 +21,10 @@ impl ScaleKeyFrame {
 
         Ok(Self { frame, scale, data })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), io::Error> {
+        writer.write_all(&self.frame.to_le_bytes())?;
+        super::write_f32s(writer, &self.scale)?;
+        writer.write_all(&self.data.to_le_bytes())
+    }
 }
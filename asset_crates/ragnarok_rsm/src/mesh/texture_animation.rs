@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::reader_ext::ReaderExt;
 
@@ -22,6 +22,18 @@ impl TextureAnimation {
             animations,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), crate::Error> {
+        let Ok(animation_count) = i32::try_from(self.animations.len()) else {
+            return Err(crate::Error::TooManyElements("texture animations"));
+        };
+        writer.write_all(&self.texture_id.to_le_bytes())?;
+        writer.write_all(&animation_count.to_le_bytes())?;
+        for animation in self.animations.iter() {
+            animation.to_writer(writer)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -46,4 +58,17 @@ impl Animation {
             key_frames,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), crate::Error> {
+        let Ok(frame_count) = i32::try_from(self.key_frames.len()) else {
+            return Err(crate::Error::TooManyElements("texture key frames"));
+        };
+        writer.write_all(&self.animation_type.to_le_bytes())?;
+        writer.write_all(&frame_count.to_le_bytes())?;
+        for (frame, value) in self.key_frames.iter() {
+            writer.write_all(&frame.to_le_bytes())?;
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::{Error as IoError, Read};
+use std::io::{Error as IoError, Read, Write};
 
 use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
@@ -24,4 +24,11 @@ impl TextureUV {
             uv: [reader.read_le_f32()?, reader.read_le_f32()?],
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), IoError> {
+        if version >= &Version(1, 2, 0) {
+            writer.write_all(&self.color)?;
+        }
+        super::write_f32s(writer, &self.uv)
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use ragnarok_rebuild_common::{Version, reader_ext::ReaderExt};
 
@@ -39,4 +39,21 @@ impl VolumeBox {
             flag,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write, version: &Version) -> Result<(), crate::Error> {
+        for values in [self.size, self.position, self.rotation] {
+            for value in values {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        if version >= &Version(1, 3, 0) {
+            writer.write_all(&self.flag.to_le_bytes())?;
+        } else if self.flag != 0 {
+            return Err(crate::Error::UnrepresentableData(
+                *version,
+                "Volume box flag",
+            ));
+        }
+        Ok(())
+    }
 }
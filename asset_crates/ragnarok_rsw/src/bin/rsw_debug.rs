@@ -1,37 +1,46 @@
+//! Debug tool for Ragnarok Online world files.
+//!
+//! ## Usage
+//!
+//! * `rsw_debug`: Reports warnings of all rsws of `data.grf`, and the tiles
+//!   of their gats that do not fit on their quad tree.
+//! * `rsw_debug mapcache <output>`: Exports a rathena `map_cache.dat` with
+//!   all maps of `data.grf`, using the water level of their rsws, into the
+//!   `output` folder.
+
 #![expect(clippy::unwrap_used, reason = "This is a test")]
 
-use std::{io::Cursor, path::Path};
+use std::{
+    fs::File,
+    io::{BufWriter, Cursor},
+    path::Path,
+};
 
-use ragnarok_gat::Gat;
+use ragnarok_gat::{Gat, MapCache};
 use ragnarok_gnd::Gnd;
 use ragnarok_grf::Grf;
 use ragnarok_rebuild_common::warning::ReportWarning;
 use ragnarok_rsw::{Rsw, quad_tree::Crawler};
 
 fn main() {
-    let grf = Grf::new(Path::new("data.grf")).unwrap();
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
 
-    for rsw_filename in grf
-        .iter_filenames()
-        .filter(|filename| match filename.extension() {
-            Some(ext) => {
-                matches!(ext.to_str(), Some("rsw"))
-            }
-            None => false,
-        })
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
     {
-        let Ok(rsw_content) = grf
-            .read_file(rsw_filename)
-            .inspect_err(|err| println!("{rsw_filename:?}: {err}"))
-        else {
-            continue;
-        };
-        let Ok(rsw) = Rsw::from_reader(&mut Cursor::new(&rsw_content))
-            .inspect_err(|err| println!("{rsw_filename:?}: {err}"))
-        else {
-            continue;
-        };
+        [] => report(),
+        ["mapcache", output] => export_map_cache(Path::new(output)),
+        _ => println!("Usage: rsw_debug [mapcache <output>]"),
+    }
+}
 
+fn report() {
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+
+    for (rsw_filename, rsw) in iter_rsws(&grf) {
         let mut header = false;
         let report = rsw.report().to_string();
         if !report.is_empty() {
@@ -153,6 +162,79 @@ fn main() {
     }
 }
 
+fn export_map_cache(output: &Path) {
+    let grf = Grf::new(Path::new("data.grf")).unwrap();
+    std::fs::create_dir_all(output).unwrap();
+
+    let mut map_cache = MapCache::new();
+    for (rsw_filename, rsw) in iter_rsws(&grf) {
+        let Some(name) = rsw_filename.file_stem().and_then(|stem| stem.to_str()) else {
+            println!("{rsw_filename:?}: Map name is not valid UTF-8.");
+            continue;
+        };
+
+        let gat = match grf.read_file(Path::new(&format!("data/{}", rsw.gat_file))) {
+            Ok(gat_content) => match Gat::from_reader(&mut Cursor::new(&gat_content)) {
+                Ok(gat) => gat,
+                Err(err) => {
+                    println!("data/{:?}: {err}", rsw.gat_file);
+                    continue;
+                }
+            },
+            Err(err) => {
+                println!("{}: {}", rsw.gat_file, err);
+                continue;
+            }
+        };
+
+        // Newer maps keep their water on the gnd
+        let water_height = rsw
+            .water_configuration
+            .or_else(|| {
+                let gnd_content = grf
+                    .read_file(Path::new(&format!("data/{}", rsw.gnd_file)))
+                    .ok()?;
+                let gnd = Gnd::from_reader(&mut Cursor::new(&gnd_content)).ok()?;
+                gnd.water.map(|water| water.base)
+            })
+            .map(|water_plane| water_plane.water_level);
+
+        if let Err(err) = map_cache.push(name, &gat, water_height) {
+            println!("{rsw_filename:?}: {err}");
+        }
+    }
+
+    let mut writer = BufWriter::new(File::create(output.join("map_cache.dat")).unwrap());
+    if let Err(err) = map_cache.to_writer(&mut writer) {
+        println!("map_cache.dat: {err}");
+    }
+    println!("Exported {} maps.", map_cache.len());
+}
+
+fn iter_rsws(grf: &Grf) -> impl Iterator<Item = (&Path, Rsw)> {
+    grf.iter_filenames()
+        .filter(|filename| match filename.extension() {
+            Some(ext) => {
+                matches!(ext.to_str(), Some("rsw"))
+            }
+            None => false,
+        })
+        .filter_map(|rsw_filename| {
+            let Ok(rsw_content) = grf
+                .read_file(rsw_filename)
+                .inspect_err(|err| println!("{rsw_filename:?}: {err}"))
+            else {
+                return None;
+            };
+            let Ok(rsw) = Rsw::from_reader(&mut Cursor::new(&rsw_content))
+                .inspect_err(|err| println!("{rsw_filename:?}: {err}"))
+            else {
+                return None;
+            };
+            Some((rsw_filename.as_path(), rsw))
+        })
+}
+
 fn test_node(current_node: &Crawler<'_>, x: f32, z: f32, y_max: f32, y_min: f32) -> bool {
     let top = current_node.top;
     let bottom = current_node.bottom;
@@ -47,6 +47,7 @@ impl From<ragnarok_pal::Error> for Error {
     fn from(value: ragnarok_pal::Error) -> Self {
         match value {
             ragnarok_pal::Error::Io(io) => Error::Io(io),
+            _ => Error::BrokenPalette,
         }
     }
 }
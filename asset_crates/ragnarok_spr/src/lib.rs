@@ -117,6 +117,7 @@ impl Spr {
                     Err(io.into())
                 }
             }
+            Err(_) => Err(Error::BrokenPalette),
         }
     }
 }
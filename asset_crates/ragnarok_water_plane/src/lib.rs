@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
 use serde::{Deserialize, Serialize};
 
@@ -32,4 +32,13 @@ impl WaterPlane {
             texture_cyclical_interval,
         })
     }
+
+    pub fn to_writer(&self, writer: &mut dyn Write) -> Result<(), std::io::Error> {
+        writer.write_all(&self.water_level.to_le_bytes())?;
+        writer.write_all(&self.water_type.to_le_bytes())?;
+        writer.write_all(&self.wave_height.to_le_bytes())?;
+        writer.write_all(&self.wave_speed.to_le_bytes())?;
+        writer.write_all(&self.wave_pitch.to_le_bytes())?;
+        writer.write_all(&self.texture_cyclical_interval.to_le_bytes())
+    }
 }
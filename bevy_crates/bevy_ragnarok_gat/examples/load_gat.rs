@@ -24,7 +24,10 @@ use bevy::{
     transform::components::Transform,
     ui::{FlexDirection, Node, Val, widget::Text},
 };
-use bevy_ragnarok_gat::debug::{ToggleGatAabbs, ToggleGatQuads};
+use bevy_ragnarok_gat::{
+    debug::{ToggleGatAabbs, ToggleGatQuads},
+    plugin::AssetLoaderSettings,
+};
 
 fn main() {
     let mut app = App::new();
@@ -75,10 +78,15 @@ fn setup(
         .id();
 
     scene_spawner.spawn_as_child(
-        asset_server.load_with_settings("data/prontera.gat#Scene", |settings: &mut f32| {
-            // Ragnarok's usual world scale is 5. units
-            *settings = 5.;
-        }),
+        asset_server.load_with_settings(
+            "data/prontera.gat#Scene",
+            |settings: &mut AssetLoaderSettings| {
+                // Ragnarok's usual world scale is 5. units
+                settings.tile_scale = 5.;
+                // Tiles are needed to show the Aabbs
+                settings.spawn_tiles = true;
+            },
+        ),
         world,
     );
 
@@ -1,10 +1,13 @@
 use bevy_app::Update;
-use bevy_asset::AssetServer;
-use bevy_camera::primitives::Aabb;
-use bevy_color::{Color, palettes};
+use bevy_asset::{Assets, RenderAssetUsages};
+use bevy_camera::visibility::Visibility;
+use bevy_color::{Alpha, Color, ColorToComponents, LinearRgba, palettes};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
     event::Event,
+    hierarchy::{ChildOf, Children},
+    name::Name,
     observer::On,
     query::With,
     reflect::ReflectResource,
@@ -12,12 +15,16 @@ use bevy_ecs::{
     schedule::{IntoScheduleConfigs, common_conditions::resource_changed},
     system::{Commands, Local, Query, Res, ResMut},
 };
-use bevy_gizmos::{GizmoAsset, aabb::ShowAabbGizmo, retained::Gizmo};
+use bevy_gizmos::aabb::ShowAabbGizmo;
 use bevy_log::debug;
 use bevy_math::Vec3;
+use bevy_mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology};
+use bevy_pbr::{MeshMaterial3d, StandardMaterial};
 use bevy_reflect::Reflect;
+use bevy_render::alpha::AlphaMode;
+use bevy_transform::components::Transform;
 
-use crate::Tile;
+use crate::{Tile, grid::GatGrid};
 
 pub(crate) struct Plugin;
 
@@ -48,6 +55,10 @@ pub struct ToggleGatAabbs;
 #[derive(Debug, Event)]
 pub struct ToggleGatQuads;
 
+/// Marker of the entity holding the mesh of the quads of a [`GatGrid`]
+#[derive(Debug, Component)]
+struct GatQuads;
+
 fn toggle_gat_aabbs(_event: On<ToggleGatAabbs>, mut gat_debug: ResMut<GatDebug>) {
     debug!("Toggling Gat Aabbs");
     gat_debug.show_aabbs = !gat_debug.show_aabbs;
@@ -77,54 +88,108 @@ fn toggle_gat_quads(_event: On<ToggleGatQuads>, mut gat_debug: ResMut<GatDebug>)
 
 fn enable_gat_quads(
     mut commands: Commands,
-    tiles: Query<(Entity, &Tile, &Aabb)>,
-    asset_server: Res<AssetServer>,
+    grids: Query<(Entity, &GatGrid, Option<&Children>)>,
+    mut quads: Query<&mut Visibility, With<GatQuads>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     debug!("Enabling Gat Quads");
-    let tile_quad_color: Color = palettes::tailwind::ORANGE_500.into();
-    for (entity, tile, aabb) in tiles {
-        let mut gizmo = GizmoAsset::new();
-        let max_y = tile
-            .bottom_left
-            .max(tile.bottom_right)
-            .max(tile.top_left)
-            .max(tile.top_right);
-        let min = aabb.min();
-        let max = aabb.max();
-        gizmo.line(
-            Vec3::new(max.x, tile.bottom_right - max_y, max.z),
-            Vec3::new(max.x, tile.top_right - max_y, min.z),
-            tile_quad_color,
-        );
-        gizmo.line(
-            Vec3::new(max.x, tile.top_right - max_y, min.z),
-            Vec3::new(min.x, tile.top_left - max_y, min.z),
-            tile_quad_color,
-        );
-        gizmo.line(
-            Vec3::new(min.x, tile.top_left - max_y, min.z),
-            Vec3::new(min.x, tile.bottom_left - max_y, max.z),
-            tile_quad_color,
-        );
-        gizmo.line(
-            Vec3::new(min.x, tile.bottom_left - max_y, max.z),
-            Vec3::new(max.x, tile.bottom_right - max_y, max.z),
-            tile_quad_color,
-        );
-        commands.entity(entity).insert(Gizmo {
-            handle: asset_server.add(gizmo),
-            ..Default::default()
-        });
+    let mut material = None;
+    for (entity, grid, children) in grids {
+        // The mesh is only built the first time, afterwards it is just shown again
+        let existing = children
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|child| quads.contains(*child));
+        if let Some(existing) = existing {
+            if let Ok(mut visibility) = quads.get_mut(existing) {
+                *visibility = Visibility::Inherited;
+            }
+            continue;
+        }
+
+        let material = material
+            .get_or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    unlit: true,
+                    double_sided: true,
+                    cull_mode: None,
+                    alpha_mode: AlphaMode::Blend,
+                    depth_bias: 1.,
+                    ..Default::default()
+                })
+            })
+            .clone();
+        commands.spawn((
+            Name::new("Gat Quads"),
+            GatQuads,
+            Mesh3d(meshes.add(quads_mesh(grid))),
+            MeshMaterial3d(material),
+            Transform::default(),
+            Visibility::default(),
+            ChildOf(entity),
+        ));
     }
 }
 
-fn disable_gat_quads(mut commands: Commands, tiles: Query<Entity, With<Tile>>) {
+fn disable_gat_quads(mut quads: Query<&mut Visibility, With<GatQuads>>) {
     debug!("Disabling Gat Quads");
-    for tile in tiles {
-        commands.entity(tile).remove::<Gizmo>();
+    for mut visibility in quads.iter_mut() {
+        *visibility = Visibility::Hidden;
     }
 }
 
+/// A single mesh with a quad for every tile of the `grid`, colored by
+/// the type of the tile
+fn quads_mesh(grid: &GatGrid) -> Mesh {
+    let gat = grid.gat();
+
+    let mut positions = Vec::with_capacity(gat.tiles.len() * 4);
+    let mut colors = Vec::with_capacity(gat.tiles.len() * 4);
+    let mut indices = Vec::with_capacity(gat.tiles.len() * 6);
+    for (index, tile) in gat.tiles.iter().enumerate() {
+        let cell = gat.index_cell(index);
+        let x = cell.x as f32;
+        let z = cell.y as f32;
+
+        let Ok(first) = u32::try_from(positions.len()) else {
+            unreachable!("Maps are never big enough to overflow the indices.");
+        };
+        positions.extend([
+            grid.map_to_local(x, tile.top_left_altitude(), z),
+            grid.map_to_local(x + 1., tile.top_right_altitude(), z),
+            grid.map_to_local(x, tile.bottom_left_altitude(), z + 1.),
+            grid.map_to_local(x + 1., tile.bottom_right_altitude(), z + 1.),
+        ]);
+        colors.extend([tile_color(tile.tile_type()).to_f32_array(); 4]);
+        indices.extend([first, first + 1, first + 2, first + 1, first + 3, first + 2]);
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![Vec3::Y; gat.tiles.len() * 4])
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+fn tile_color(tile_type: ragnarok_gat::TileType) -> LinearRgba {
+    let color = if tile_type.is_water() {
+        palettes::tailwind::BLUE_500
+    } else if tile_type.is_walkable() {
+        palettes::tailwind::GREEN_500
+    } else if tile_type.is_snipeable() {
+        palettes::tailwind::YELLOW_500
+    } else {
+        palettes::tailwind::RED_500
+    };
+    color.with_alpha(0.4).into()
+}
+
 fn trigger_on_changes(
     mut commands: Commands,
     gat_debug: Res<GatDebug>,
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Ray3d, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
+use ragnarok_gat::Cell;
+
+use crate::raycast::GroundHit;
+
+/// Tiles of a [`Gat`](ragnarok_gat::Gat), present on the root of the
+/// [`Scene`](bevy_scene::Scene) generated when loading a `.gat`.
+///
+/// Local positions are relative to the entity holding the [`GatGrid`], world
+/// positions use its [`GlobalTransform`].
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(opaque)]
+#[reflect(Component, Clone)]
+pub struct GatGrid {
+    gat: Arc<ragnarok_gat::Gat>,
+    tile_scale: f32,
+}
+
+impl GatGrid {
+    pub(crate) fn new(gat: Arc<ragnarok_gat::Gat>, tile_scale: f32) -> Self {
+        Self { gat, tile_scale }
+    }
+
+    /// The source [`Gat`](ragnarok_gat::Gat)
+    pub fn gat(&self) -> &ragnarok_gat::Gat {
+        &self.gat
+    }
+
+    /// Size of a tile on the XZ plane
+    pub fn tile_scale(&self) -> f32 {
+        self.tile_scale
+    }
+
+    /// Offset between the cell coordinates and the local coordinates, the map is centered on the entity
+    fn offset(&self) -> Vec3 {
+        Vec3::new(self.gat.width as f32 / 2., 0., self.gat.height as f32 / 2.)
+    }
+
+    /// Converts a point in cell coordinates, where the cell at `(0, 0)` spans
+    /// from `(0., 0.)` to `(1., 1.)`, into local coordinates
+    pub fn map_to_local(&self, x: f32, altitude: f32, z: f32) -> Vec3 {
+        (Vec3::new(x, altitude, z) - self.offset())
+            * Vec3::new(self.tile_scale, 1., self.tile_scale)
+    }
+
+    /// Converts a local position into cell coordinates, see [`GatGrid::map_to_local`]
+    pub fn local_to_map(&self, position: Vec3) -> Vec3 {
+        position / Vec3::new(self.tile_scale, 1., self.tile_scale) + self.offset()
+    }
+
+    /// Local position of the center of `cell`, on the terrain
+    pub fn cell_to_local(&self, cell: Cell) -> Option<Vec3> {
+        self.gat.cell_index(cell)?;
+
+        let x = cell.x as f32 + 0.5;
+        let z = cell.y as f32 + 0.5;
+        let altitude = self.gat.height_at(x, z)?;
+        Some(self.map_to_local(x, altitude, z))
+    }
+
+    /// The cell containing a local position, ignoring its height
+    pub fn local_to_cell(&self, position: Vec3) -> Option<Cell> {
+        let map = self.local_to_map(position);
+        if !(map.x >= 0. && map.z >= 0.) {
+            return None;
+        }
+
+        let cell = Cell::new(map.x as u32, map.z as u32);
+        self.gat.cell_index(cell).map(|_| cell)
+    }
+
+    /// Local position on the terrain directly above or below `position`
+    pub fn local_ground(&self, position: Vec3) -> Option<Vec3> {
+        let map = self.local_to_map(position);
+        let altitude = self.gat.height_at(map.x, map.z)?;
+        Some(Vec3::new(position.x, altitude, position.z))
+    }
+
+    /// World position of the center of `cell`, see [`GatGrid::cell_to_local`]
+    pub fn cell_to_world(&self, transform: &GlobalTransform, cell: Cell) -> Option<Vec3> {
+        self.cell_to_local(cell)
+            .map(|position| transform.transform_point(position))
+    }
+
+    /// The cell containing a world position, see [`GatGrid::local_to_cell`]
+    pub fn world_to_cell(&self, transform: &GlobalTransform, position: Vec3) -> Option<Cell> {
+        self.local_to_cell(transform.affine().inverse().transform_point3(position))
+    }
+
+    /// Intersects a local ray with the terrain, see [`Gat::raycast`](ragnarok_gat::Gat::raycast).
+    ///
+    /// Returns the distance along the ray, in multiples of `direction`, and
+    /// the cell that was hit.
+    pub fn local_raycast(&self, origin: Vec3, direction: Vec3) -> Option<(f32, Cell)> {
+        let origin = self.local_to_map(origin);
+        let direction = direction / Vec3::new(self.tile_scale, 1., self.tile_scale);
+        self.gat.raycast(origin.to_array(), direction.to_array())
+    }
+
+    /// Intersects a world ray with the terrain, see [`GatGrid::local_raycast`]
+    pub fn world_raycast(&self, transform: &GlobalTransform, ray: Ray3d) -> Option<GroundHit> {
+        let inverse = transform.affine().inverse();
+        let (distance, cell) = self.local_raycast(
+            inverse.transform_point3(ray.origin),
+            inverse.transform_vector3(*ray.direction),
+        )?;
+
+        Some(GroundHit {
+            distance,
+            position: ray.get_point(distance),
+            cell,
+        })
+    }
+}
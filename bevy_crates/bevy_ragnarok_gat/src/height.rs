@@ -0,0 +1,66 @@
+use bevy_ecs::{
+    component::Component,
+    hierarchy::ChildOf,
+    query::{With, Without},
+    reflect::ReflectComponent,
+    system::{Query, SystemParam},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{Reflect, std_traits::ReflectDefault};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::grid::GatGrid;
+
+/// Keeps the entity standing on the terrain of the loaded [`GatGrid`]
+#[derive(Debug, Default, Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct SnapToGround;
+
+/// Height of the terrain of the loaded [`GatGrid`] in world coordinates
+#[derive(SystemParam)]
+pub struct GroundHeight<'w, 's> {
+    grids: Query<'w, 's, (&'static GatGrid, &'static GlobalTransform)>,
+}
+
+impl GroundHeight<'_, '_> {
+    /// Returns the point on the terrain that is above or below `position`,
+    /// or `None` if `position` is outside of the map or there isn't
+    /// exactly one [`GatGrid`].
+    ///
+    /// The terrain is expected to only be flipped or scaled, not rotated.
+    pub fn ground_at(&self, position: Vec3) -> Option<Vec3> {
+        let (grid, grid_transform) = self.grids.single().ok()?;
+        let local = grid_transform.affine().inverse().transform_point3(position);
+        let ground = grid.local_ground(local)?;
+
+        Some(grid_transform.transform_point(ground))
+    }
+
+    /// Returns the world Y of the terrain at `position`, see [`GroundHeight::ground_at`].
+    pub fn height_at(&self, position: Vec3) -> Option<f32> {
+        self.ground_at(position).map(|ground| ground.y)
+    }
+}
+
+pub(crate) fn snap_to_ground(
+    ground_height: GroundHeight,
+    mut snapping: Query<(&mut Transform, Option<&ChildOf>), With<SnapToGround>>,
+    parents: Query<&GlobalTransform, Without<SnapToGround>>,
+) {
+    for (mut transform, child_of) in snapping.iter_mut() {
+        let parent_transform = child_of
+            .and_then(|child_of| parents.get(child_of.parent()).ok())
+            .copied()
+            .unwrap_or_default();
+
+        let position = parent_transform.transform_point(transform.translation);
+        let Some(ground) = ground_height.ground_at(position) else {
+            continue;
+        };
+
+        let translation = parent_transform.affine().inverse().transform_point3(ground);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
@@ -1,8 +1,14 @@
 mod assets;
 #[cfg(feature = "debug")]
 pub mod debug;
+pub mod grid;
+pub mod height;
 mod loader;
+#[cfg(feature = "picking")]
+mod picking;
 pub mod plugin;
+pub mod raycast;
+pub mod sight;
 
 use std::borrow::Borrow;
 
@@ -16,8 +22,7 @@ pub struct Tile {
     pub bottom_right: f32,
     pub top_left: f32,
     pub top_right: f32,
-    // TODO change to gat::Tile when Bevy implements foreign type reflection
-    pub tile_type: u8,
+    pub tile_type: TileType,
     pub is_water_tile: bool,
 }
 
@@ -29,8 +34,68 @@ impl<T: Borrow<ragnarok_gat::Tile>> From<T> for Tile {
             bottom_right: tile.bottom_right_altitude(),
             top_left: tile.top_left_altitude(),
             top_right: tile.top_right_altitude(),
-            tile_type: tile.tile_type(),
+            tile_type: tile.tile_type().into(),
             is_water_tile: tile.is_water_tile(),
         }
     }
 }
+
+/// Reflectable mirror of [`ragnarok_gat::TileType`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum TileType {
+    WalkableBlock,
+    NonWalkableBlock,
+    NonWalkableWater,
+    WalkableWater,
+    SnipeableNonWalkableWater,
+    SnipeableCliff,
+    Cliff,
+    Unknown(u8),
+}
+
+impl TileType {
+    /// See [`ragnarok_gat::TileType::is_walkable`]
+    pub fn is_walkable(&self) -> bool {
+        ragnarok_gat::TileType::from(*self).is_walkable()
+    }
+
+    /// See [`ragnarok_gat::TileType::is_snipeable`]
+    pub fn is_snipeable(&self) -> bool {
+        ragnarok_gat::TileType::from(*self).is_snipeable()
+    }
+
+    /// See [`ragnarok_gat::TileType::is_water`]
+    pub fn is_water(&self) -> bool {
+        ragnarok_gat::TileType::from(*self).is_water()
+    }
+}
+
+impl From<ragnarok_gat::TileType> for TileType {
+    fn from(value: ragnarok_gat::TileType) -> Self {
+        match value {
+            ragnarok_gat::TileType::WalkableBlock => Self::WalkableBlock,
+            ragnarok_gat::TileType::NonWalkableBlock => Self::NonWalkableBlock,
+            ragnarok_gat::TileType::NonWalkableWater => Self::NonWalkableWater,
+            ragnarok_gat::TileType::WalkableWater => Self::WalkableWater,
+            ragnarok_gat::TileType::SnipeableNonWalkableWater => Self::SnipeableNonWalkableWater,
+            ragnarok_gat::TileType::SnipeableCliff => Self::SnipeableCliff,
+            ragnarok_gat::TileType::Cliff => Self::Cliff,
+            ragnarok_gat::TileType::Unknown(unknown) => Self::Unknown(unknown),
+        }
+    }
+}
+
+impl From<TileType> for ragnarok_gat::TileType {
+    fn from(value: TileType) -> Self {
+        match value {
+            TileType::WalkableBlock => Self::WalkableBlock,
+            TileType::NonWalkableBlock => Self::NonWalkableBlock,
+            TileType::NonWalkableWater => Self::NonWalkableWater,
+            TileType::WalkableWater => Self::WalkableWater,
+            TileType::SnipeableNonWalkableWater => Self::SnipeableNonWalkableWater,
+            TileType::SnipeableCliff => Self::SnipeableCliff,
+            TileType::Cliff => Self::Cliff,
+            TileType::Unknown(unknown) => Self::Unknown(unknown),
+        }
+    }
+}
@@ -1,18 +1,48 @@
+use std::sync::Arc;
+
 use bevy_asset::LoadContext;
 use bevy_camera::{primitives::Aabb, visibility::Visibility};
-use bevy_ecs::{hierarchy::ChildOf, name::Name, world::World};
+use bevy_ecs::{entity::Entity, hierarchy::ChildOf, name::Name, world::World};
 use bevy_math::{FloatOrd, Vec3};
 use bevy_ragnarok_quad_tree::TrackEntity;
 use bevy_scene::Scene;
 use bevy_transform::components::Transform;
+use serde::{Deserialize, Serialize};
+
+use crate::{Tile, grid::GatGrid};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetLoaderSettings {
+    /// Size of a tile on the XZ plane
+    pub tile_scale: f32,
+    /// Spawn one entity per tile with [`Tile`], [`Aabb`] and [`TrackEntity`].
+    ///
+    /// Large maps have over a hundred thousand tiles, prefer
+    /// querying the [`GatGrid`] instead.
+    pub spawn_tiles: bool,
+}
 
-use crate::Tile;
+impl Default for AssetLoaderSettings {
+    fn default() -> Self {
+        Self {
+            // Ragnarok's usual world scale is 5. units
+            tile_scale: 5.,
+            spawn_tiles: false,
+        }
+    }
+}
 
+/// Asset loader for [`Gat`](super::assets::Gat).
+///
+/// ## Labeled assets
+///
+/// * `Scene`: [`Scene`] = Root with the [`GatGrid`], and the tiles if
+///   [`AssetLoaderSettings::spawn_tiles`] is set.
 pub struct AssetLoader;
 
 impl bevy_asset::AssetLoader for AssetLoader {
     type Asset = super::assets::Gat;
-    type Settings = f32;
+    type Settings = AssetLoaderSettings;
     type Error = ragnarok_gat::Error;
 
     async fn load(
@@ -26,8 +56,8 @@ impl bevy_asset::AssetLoader for AssetLoader {
         let mut data: Vec<u8> = vec![];
         reader.read_to_end(&mut data).await?;
 
-        let gat = ragnarok_gat::Gat::from_reader(&mut data.as_slice())?;
-        Self::generate_altitude(load_context, *settings, &gat);
+        let gat = Arc::new(ragnarok_gat::Gat::from_reader(&mut data.as_slice())?);
+        Self::generate_altitude(load_context, settings, &gat);
 
         Ok(super::assets::Gat(gat))
     }
@@ -38,8 +68,13 @@ impl bevy_asset::AssetLoader for AssetLoader {
 }
 
 impl AssetLoader {
-    fn generate_altitude(load_context: &mut LoadContext, tile_scale: f32, gat: &ragnarok_gat::Gat) {
+    fn generate_altitude(
+        load_context: &mut LoadContext,
+        settings: &AssetLoaderSettings,
+        gat: &Arc<ragnarok_gat::Gat>,
+    ) {
         let mut world = World::new();
+        let grid = GatGrid::new(gat.clone(), settings.tile_scale);
 
         let root = world
             .spawn((
@@ -50,14 +85,22 @@ impl AssetLoader {
                         .map(|osstr| osstr.to_string_lossy().into_owned())
                         .unwrap_or("Gat".to_owned()),
                 ),
+                grid.clone(),
                 Transform::default(),
                 Visibility::default(),
             ))
             .id();
 
-        let half_tile_scale = tile_scale / 2.;
-        let scale = Vec3::new(tile_scale, 1., tile_scale);
-        let offset = Vec3::new(gat.width as f32 / 2., 0., gat.height as f32 / 2.);
+        if settings.spawn_tiles {
+            Self::spawn_tiles(&mut world, root, &grid);
+        }
+
+        load_context.add_labeled_asset("Scene".to_owned(), Scene::new(world));
+    }
+
+    fn spawn_tiles(world: &mut World, root: Entity, grid: &GatGrid) {
+        let gat = grid.gat();
+        let half_tile_scale = grid.tile_scale() / 2.;
         for (i, tile) in gat.tiles.iter().enumerate() {
             let Ok(x) = u32::try_from(i).map(|i| i % gat.width) else {
                 unreachable!("Should always fit in a u32.");
@@ -82,9 +125,11 @@ impl AssetLoader {
             world.spawn((
                 Name::new(format!("{x}/{z}")),
                 Tile::from(tile),
-                Transform::from_translation(
-                    (Vec3::new(x as f32 + 0.5, max.0, z as f32 + 0.5) - offset) * scale,
-                ),
+                Transform::from_translation(grid.map_to_local(
+                    x as f32 + 0.5,
+                    max.0,
+                    z as f32 + 0.5,
+                )),
                 Aabb::from_min_max(
                     Vec3::new(-half_tile_scale, min.0 - max.0, -half_tile_scale),
                     Vec3::new(half_tile_scale, 0., half_tile_scale),
@@ -93,7 +138,5 @@ impl AssetLoader {
                 TrackEntity,
             ));
         }
-
-        load_context.add_labeled_asset("Scene".to_owned(), Scene::new(world));
     }
 }
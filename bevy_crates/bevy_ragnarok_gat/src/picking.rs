@@ -0,0 +1,53 @@
+use bevy_app::PreUpdate;
+use bevy_camera::Camera;
+use bevy_ecs::{
+    entity::Entity,
+    message::MessageWriter,
+    schedule::IntoScheduleConfigs,
+    system::{Query, Res},
+};
+use bevy_picking::{
+    Pickable, PickingSystems,
+    backend::{HitData, PointerHits, ray::RayMap},
+};
+
+use bevy_transform::components::GlobalTransform;
+
+use crate::grid::GatGrid;
+
+/// A [`bevy_picking`] backend picking the entities holding a [`GatGrid`],
+/// the hit position is on the terrain and the cell can be found with
+/// [`GatGrid::world_to_cell`]
+pub(crate) struct Plugin;
+
+impl bevy_app::Plugin for Plugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(PreUpdate, update_hits.in_set(PickingSystems::Backend));
+    }
+}
+
+fn update_hits(
+    ray_map: Res<RayMap>,
+    cameras: Query<&Camera>,
+    grids: Query<(Entity, &GatGrid, &GlobalTransform, Option<&Pickable>)>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+) {
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok(camera) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+
+        let picks = grids
+            .iter()
+            .filter(|(_, _, _, pickable)| pickable.is_none_or(|pickable| pickable.is_hoverable))
+            .filter_map(|(entity, grid, transform, _)| {
+                let hit = grid.world_raycast(transform, ray)?;
+                let hit_data = HitData::new(ray_id.camera, hit.distance, Some(hit.position), None);
+                Some((entity, hit_data))
+            })
+            .collect::<Vec<_>>();
+        if !picks.is_empty() {
+            pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use bevy_app::PostUpdate;
 use bevy_asset::AssetApp;
 use bevy_camera::{
     primitives::Aabb,
@@ -6,10 +7,22 @@ use bevy_camera::{
 use bevy_ecs::{
     hierarchy::{ChildOf, Children},
     name::Name,
+    schedule::IntoScheduleConfigs,
+};
+use bevy_transform::{
+    TransformSystems,
+    components::{GlobalTransform, Transform, TransformTreeChanged},
+};
+
+use crate::{
+    Tile, TileType,
+    assets::Gat,
+    grid::GatGrid,
+    height::{SnapToGround, snap_to_ground},
+    loader::AssetLoader,
 };
-use bevy_transform::components::{GlobalTransform, Transform, TransformTreeChanged};
 
-use crate::{Tile, assets::Gat, loader::AssetLoader};
+pub use crate::loader::AssetLoaderSettings;
 
 pub struct Plugin;
 
@@ -19,6 +32,14 @@ impl bevy_app::Plugin for Plugin {
         app.init_asset::<Gat>().register_asset_loader(AssetLoader);
         // Register types
         app.register_type::<Tile>();
+        app.register_type::<TileType>();
+        app.register_type::<GatGrid>();
+        app.register_type::<SnapToGround>();
+        // Systems
+        app.add_systems(
+            PostUpdate,
+            snap_to_ground.before(TransformSystems::Propagate),
+        );
 
         // Things necessary for the Scene
         app.register_type::<Name>();
@@ -36,5 +57,9 @@ impl bevy_app::Plugin for Plugin {
         {
             app.add_plugins(crate::debug::Plugin);
         }
+        #[cfg(feature = "picking")]
+        {
+            app.add_plugins(crate::picking::Plugin);
+        }
     }
 }
@@ -0,0 +1,39 @@
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, SystemParam},
+};
+use bevy_math::{Ray3d, Vec3};
+use bevy_transform::components::GlobalTransform;
+use ragnarok_gat::Cell;
+
+use crate::grid::GatGrid;
+
+/// Intersection of a ray with the terrain of a [`GatGrid`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroundHit {
+    /// Distance from the origin of the ray
+    pub distance: f32,
+    /// World position of the hit
+    pub position: Vec3,
+    /// Cell of the hit
+    pub cell: Cell,
+}
+
+/// Raycasts against the terrain of the loaded [`GatGrid`]s
+#[derive(SystemParam)]
+pub struct GroundRaycast<'w, 's> {
+    grids: Query<'w, 's, (Entity, &'static GatGrid, &'static GlobalTransform)>,
+}
+
+impl GroundRaycast<'_, '_> {
+    /// Returns the closest hit of `ray` on the terrain of all [`GatGrid`]s,
+    /// along with the entity holding the [`GatGrid`] that was hit.
+    pub fn raycast(&self, ray: Ray3d) -> Option<(Entity, GroundHit)> {
+        self.grids
+            .iter()
+            .filter_map(|(entity, grid, transform)| {
+                grid.world_raycast(transform, ray).map(|hit| (entity, hit))
+            })
+            .min_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance))
+    }
+}
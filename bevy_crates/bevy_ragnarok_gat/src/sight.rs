@@ -0,0 +1,30 @@
+use bevy_ecs::system::{Query, SystemParam};
+use ragnarok_gat::Cell;
+
+use crate::grid::GatGrid;
+
+/// Line of sight queries over the loaded [`GatGrid`]
+#[derive(SystemParam)]
+pub struct LineOfSight<'w, 's> {
+    grids: Query<'w, 's, &'static GatGrid>,
+}
+
+impl LineOfSight<'_, '_> {
+    /// See [`Gat::has_line_of_sight`](ragnarok_gat::Gat::has_line_of_sight).
+    ///
+    /// Returns `false` if there isn't exactly one [`GatGrid`].
+    pub fn has_line_of_sight(&self, from: Cell, to: Cell) -> bool {
+        self.grids
+            .single()
+            .is_ok_and(|grid| grid.gat().has_line_of_sight(from, to))
+    }
+
+    /// See [`Gat::can_shoot`](ragnarok_gat::Gat::can_shoot).
+    ///
+    /// Returns `false` if there isn't exactly one [`GatGrid`].
+    pub fn can_shoot(&self, from: Cell, to: Cell) -> bool {
+        self.grids
+            .single()
+            .is_ok_and(|grid| grid.gat().can_shoot(from, to))
+    }
+}
@@ -15,6 +15,8 @@ pub struct GndAsset {
     pub scene: Handle<Scene>,
     /// Handles to texture [`Image`]
     pub textures: Vec<Handle<Image>>,
+    /// Handle to the [`Image`] atlas built from [`Gnd::lightmap`](ragnarok_gnd::Gnd::lightmap)
+    pub lightmap: Handle<Image>,
     /// Handle to [`ShaderStorageBuffer`] built from
     /// [`GroundMeshCubes::upwards_facing_surface`](ragnarok_gnd::GroundMeshCube::upwards_facing_surface),
     /// [`GroundMeshCubes::east_facing_surface`](ragnarok_gnd::GroundMeshCube::east_facing_surface), and
@@ -8,7 +8,7 @@ use bevy_ecs::{
     hierarchy::Children,
     lifecycle::Add,
     observer::On,
-    query::{With, Without},
+    query::{Or, With, Without},
     reflect::ReflectResource,
     resource::Resource,
     schedule::{
@@ -26,7 +26,7 @@ use bevy_reflect::Reflect;
 use bevy_render::storage::ShaderStorageBuffer;
 use bevy_transform::TransformSystems;
 
-use crate::{Cube, Ground, material::GndMaterial};
+use crate::{Chunk, Cube, Ground, material::GndMaterial};
 
 const AABB_COLOR: Srgba = palettes::tailwind::PURPLE_300;
 
@@ -44,8 +44,10 @@ impl bevy_app::Plugin for Plugin {
             PostUpdate,
             (
                 enable_gnd_edges.run_if(enable_gnd_edges_condition),
+                enable_gnd_chunk_edges.run_if(enable_gnd_edges_condition),
                 disable_gnd_edges.run_if(not(enable_gnd_edges_condition)),
                 enable_gnd_normals.run_if(enable_gnd_normals_condition),
+                enable_gnd_chunk_normals.run_if(enable_gnd_normals_condition),
                 disable_gnd_normals.run_if(not(enable_gnd_normals_condition)),
             )
                 .after(TransformSystems::Propagate)
@@ -56,6 +58,7 @@ impl bevy_app::Plugin for Plugin {
         app.add_observer(toggle_gnd_edges);
         app.add_observer(toggle_gnd_normals);
         app.add_observer(enable_gnd_aabbs_for_new_cubes);
+        app.add_observer(enable_gnd_aabbs_for_new_chunks);
     }
 }
 
@@ -104,7 +107,28 @@ fn enable_gnd_aabbs_for_new_cubes(
     }
 }
 
-fn enable_gnd_aabbs(mut commands: Commands, cubes: Query<&Children, With<Cube>>) {
+fn enable_gnd_aabbs_for_new_chunks(
+    event: On<Add, Chunk>,
+    mut commands: Commands,
+    chunks: Query<&Children, With<Chunk>>,
+    gnd_debug: ResMut<GndDebug>,
+) {
+    if gnd_debug.show_aabbs
+        && let Ok(children) = chunks.get(event.event_target())
+    {
+        for child in children {
+            commands.entity(*child).insert(ShowAabbGizmo {
+                color: Some(AABB_COLOR.into()),
+            });
+        }
+    }
+}
+
+fn enable_gnd_aabbs(
+    mut commands: Commands,
+    cubes: Query<&Children, With<Cube>>,
+    chunks: Query<&Children, With<Chunk>>,
+) {
     debug!("Enabling Gnd Aabbs");
     let cube_aabb_color = AABB_COLOR.into();
     for children in cubes {
@@ -114,9 +138,19 @@ fn enable_gnd_aabbs(mut commands: Commands, cubes: Query<&Children, With<Cube>>)
             });
         }
     }
+    // Meshes of a chunk each have their own aabb
+    for child in chunks.iter().flatten() {
+        commands.entity(*child).insert(ShowAabbGizmo {
+            color: Some(cube_aabb_color),
+        });
+    }
 }
 
-fn disable_gnd_aabbs(mut commands: Commands, cubes: Query<&Children, With<Cube>>) {
+#[expect(clippy::type_complexity, reason = "Queries are complex")]
+fn disable_gnd_aabbs(
+    mut commands: Commands,
+    cubes: Query<&Children, Or<(With<Cube>, With<Chunk>)>>,
+) {
     debug!("Disabling Gat Aabbs");
     for child in cubes.iter().flatten() {
         commands.entity(*child).remove::<ShowAabbGizmo>();
@@ -210,6 +244,53 @@ fn enable_gnd_edges(
     }
 }
 
+#[expect(clippy::type_complexity, reason = "Queries are complex")]
+fn enable_gnd_chunk_edges(
+    mut commands: Commands,
+    faces: Populated<
+        (Entity, &Mesh3d),
+        (
+            With<MeshMaterial3d<GndMaterial>>,
+            Without<MeshTag>,
+            Without<Gizmo>,
+        ),
+    >,
+    gnd_assets: GndAssets<'_>,
+    mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
+) {
+    let edge_color: Color = palettes::tailwind::ORANGE_700.into();
+
+    for (chunk_mesh, mesh) in faces.into_inner() {
+        let Some(vertices) = gnd_assets
+            .meshes
+            .get(mesh.id())
+            .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_POSITION))
+            .and_then(|vertices| vertices.as_float3())
+        else {
+            continue;
+        };
+
+        // Chunk meshes have the 4 vertices of each face one after the other
+        let mut gizmos = GizmoAsset::new();
+        for face in vertices.chunks_exact(4) {
+            let [bottom_left, bottom_right, top_left, top_right] =
+                [face[0], face[1], face[2], face[3]].map(Vec3::from_array);
+            gizmos.line(bottom_left, bottom_right, edge_color);
+            gizmos.line(bottom_left, top_left, edge_color);
+            gizmos.line(bottom_right, top_left, edge_color);
+            gizmos.line(bottom_right, top_right, edge_color);
+            gizmos.line(top_left, top_right, edge_color);
+        }
+
+        let handle = gizmo_assets.add(gizmos);
+
+        commands.entity(chunk_mesh).insert(Gizmo {
+            handle,
+            ..Default::default()
+        });
+    }
+}
+
 fn disable_gnd_edges(
     mut commands: Commands,
     faces: Populated<Entity, (With<MeshMaterial3d<GndMaterial>>, With<Gizmo>)>,
@@ -329,9 +410,54 @@ fn enable_gnd_normals(
     }
 }
 
+#[expect(clippy::type_complexity, reason = "Queries are complex")]
+fn enable_gnd_chunk_normals(
+    mut commands: Commands,
+    ground: Single<&Ground>,
+    chunks: Populated<(Entity, &Children), (With<Chunk>, Without<Gizmo>)>,
+    chunk_meshes: Query<&Mesh3d, With<MeshMaterial3d<GndMaterial>>>,
+    gnd_assets: GndAssets<'_>,
+    mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
+) {
+    for (chunk, children) in chunks.into_inner() {
+        let mut gizmos = GizmoAsset::new();
+        for mesh in chunk_meshes.iter_many(children) {
+            let Some(mesh) = gnd_assets.meshes.get(mesh.id()) else {
+                continue;
+            };
+            let (Some(vertices), Some(normals)) = (
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                    .and_then(|vertices| vertices.as_float3()),
+                mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+                    .and_then(|normals| normals.as_float3()),
+            ) else {
+                continue;
+            };
+
+            for (vertex, normal) in vertices.iter().zip(normals) {
+                let vertex = Vec3::from_array(*vertex);
+                let normal = Vec3::from_array(*normal);
+                gizmos.arrow(
+                    vertex,
+                    vertex + normal * Vec3::new(1., ground.scale, 1.),
+                    Color::srgb(normal.x.abs(), normal.y.abs(), normal.z.abs()),
+                );
+            }
+        }
+
+        let handle = gizmo_assets.add(gizmos);
+
+        commands.entity(chunk).insert(Gizmo {
+            handle,
+            ..Default::default()
+        });
+    }
+}
+
+#[expect(clippy::type_complexity, reason = "Queries are complex")]
 fn disable_gnd_normals(
     mut commands: Commands,
-    cubes: Populated<Entity, (With<Cube>, With<Gizmo>)>,
+    cubes: Populated<Entity, (Or<(With<Cube>, With<Chunk>)>, With<Gizmo>)>,
 ) {
     for cube in cubes.into_inner() {
         commands.entity(cube).remove::<Gizmo>();
@@ -22,3 +22,14 @@ pub struct Ground {
 #[derive(Debug, Component, Reflect)]
 #[reflect(Component)]
 pub struct Cube;
+
+/// Represents a block of ground cubes merged into one mesh per texture,
+/// the block starts at cube `x`/`z` and spans `width`x`height` cubes
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct Chunk {
+    pub x: u32,
+    pub z: u32,
+    pub width: u32,
+    pub height: u32,
+}
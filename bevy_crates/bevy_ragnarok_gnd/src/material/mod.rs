@@ -22,7 +22,8 @@ impl bevy_app::Plugin for Plugin {
 }
 
 #[derive(Clone, Asset, Reflect, AsBindGroup)]
-#[bindless(index_table(range(0..6)))]
+#[bindless(index_table(range(0..8)))]
+#[bind_group_data(GndMaterialKey)]
 pub struct GndMaterial {
     #[texture(0)]
     #[sampler(1)]
@@ -36,10 +37,16 @@ pub struct GndMaterial {
     pub surfaces: Handle<ShaderStorageBuffer>,
     #[storage(5, binding_array(13), read_only)]
     pub normals: Handle<ShaderStorageBuffer>,
+    #[texture(6)]
+    #[sampler(7)]
+    pub lightmap: Handle<Image>,
+    /// Whether the material is used by [`Chunk`](crate::Chunk) meshes, whose
+    /// vertices already have their heights, uvs, and colors
+    pub chunked: bool,
 }
 
 impl GndMaterial {
-    pub const SURFACE_UVS_STRIDE: usize = 2 * 4 * 4;
+    pub const SURFACE_UVS_STRIDE: usize = 2 * 4 * 4 + 2 * 2 * 4 + 4 * 4;
     pub const SURFACE_IDS_STRIDE: usize = 4;
     pub const HEIGHTS_STRIDE: usize = 4 * 4;
     pub const NORMALS_STRIDE: usize = 4 * 4 * 4;
@@ -78,7 +85,7 @@ impl Material for GndMaterial {
         _pipeline: &bevy_pbr::MaterialPipeline,
         descriptor: &mut bevy_render::render_resource::RenderPipelineDescriptor,
         _layout: &bevy_mesh::MeshVertexBufferLayoutRef,
-        _key: bevy_pbr::MaterialPipelineKey<Self>,
+        key: bevy_pbr::MaterialPipelineKey<Self>,
     ) -> bevy_ecs::error::Result<(), bevy_render::render_resource::SpecializedMeshPipelineError>
     {
         descriptor.label = Some(
@@ -92,6 +99,22 @@ impl Material for GndMaterial {
             )
             .into(),
         );
+        if key.bind_group_data.chunked {
+            descriptor.vertex.shader_defs.push("GND_CHUNKED".into());
+        }
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GndMaterialKey {
+    chunked: bool,
+}
+
+impl From<&GndMaterial> for GndMaterialKey {
+    fn from(value: &GndMaterial) -> Self {
+        Self {
+            chunked: value.chunked,
+        }
+    }
+}
@@ -34,6 +34,12 @@ struct GndSurface {
     bottom_right_uv: vec2<f32>,
     top_left_uv: vec2<f32>,
     top_right_uv: vec2<f32>,
+    lightmap_min_uv: vec2<f32>,
+    lightmap_max_uv: vec2<f32>,
+    bottom_left_color: u32,
+    bottom_right_color: u32,
+    top_left_color: u32,
+    top_right_color: u32,
 }
 
 struct GndCubeFaceNormals {
@@ -52,6 +58,8 @@ struct GndBindings {
     surface_id: u32,
     surface: u32,
     normals: u32,
+    lightmap: u32,
+    lightmap_sampler: u32,
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<storage> gnd_bindings: array<GndBindings>;
@@ -68,6 +76,8 @@ struct GndBindings {
 @group(#{MATERIAL_BIND_GROUP}) @binding(3) var<storage> gnd_surface_ids: array<u32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(4) var<storage> gnd_surfaces: array<GndSurface>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(5) var<storage> gnd_cube_face_normals: array<GndCubeFaceNormals>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(6) var gnd_lightmap: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(7) var gnd_lightmap_sampler: sampler;
 
 #endif // BINDLESS
 
@@ -82,6 +92,40 @@ fn gnd_default_material(in: VertexOutput, is_front: bool) -> PbrInput {
 }
 #endif // MESH_PIPELINE
 
+#ifdef GND_CHUNKED
+// Vertices of chunks already have their final positions, uvs, and colors
+fn chunk_vertex(in: Vertex) -> VertexOutput {
+    var vertex_output: VertexOutput;
+
+    vertex_output.instance_index = in.instance_index;
+
+    var world_from_local = mesh_functions::get_world_from_local(in.instance_index);
+    vertex_output.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(in.position, 1.0));
+    vertex_output.position = position_world_to_clip(vertex_output.world_position.xyz);
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    vertex_output.unclipped_depth = vertex_output.world_position.z;
+#endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
+
+#ifdef VERTEX_NORMALS || NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    vertex_output.world_normal = mesh_functions::mesh_normal_local_to_world(
+        in.normal,
+        in.instance_index
+    );
+#endif
+#ifdef VERTEX_UVS_A
+    vertex_output.uv = in.uv;
+#endif // VERTEX_UVS_A
+#ifdef VERTEX_UVS_B
+    vertex_output.uv_b = in.uv_b;
+#endif // VERTEX_UVS_B
+#ifdef VERTEX_COLORS
+    vertex_output.color = in.color;
+#endif // VERTEX_COLORS
+
+    return vertex_output;
+}
+#endif // GND_CHUNKED
+
 @vertex
 fn vertex(
     in: Vertex,
@@ -89,6 +133,9 @@ fn vertex(
     @builtin(vertex_index) vertex_index: u32,
 #endif
 ) -> VertexOutput {
+#ifdef GND_CHUNKED
+    return chunk_vertex(in);
+#else // GND_CHUNKED
     var vertex_output: VertexOutput;
 
     vertex_output.instance_index = in.instance_index;
@@ -183,8 +230,35 @@ fn vertex(
         vertex_output.uv = surface.top_right_uv;
     }
 #endif // VERTEX_UVS_A
+#ifdef VERTEX_UVS_B
+    if index == 0 {
+        vertex_output.uv_b = surface.lightmap_min_uv;
+    } else if index == 1 {
+        vertex_output.uv_b = vec2(surface.lightmap_max_uv.x, surface.lightmap_min_uv.y);
+    } else if index == 2 {
+        vertex_output.uv_b = vec2(surface.lightmap_min_uv.x, surface.lightmap_max_uv.y);
+    } else if index == 3 {
+        vertex_output.uv_b = surface.lightmap_max_uv;
+    }
+#endif // VERTEX_UVS_B
+#ifdef VERTEX_COLORS
+    var color: u32;
+    if index == 0 {
+        color = surface.bottom_left_color;
+    } else if index == 1 {
+        color = surface.bottom_right_color;
+    } else if index == 2 {
+        color = surface.top_left_color;
+    } else if index == 3 {
+        color = surface.top_right_color;
+    }
+    // Colors are stored in sRGB
+    let srgb = unpack4x8unorm(color);
+    vertex_output.color = vec4(pow(srgb.rgb, vec3(2.2)), 1.);
+#endif // VERTEX_COLORS
 
     return vertex_output;
+#endif // GND_CHUNKED
 }
 
 #ifdef MESH_PIPELINE
@@ -200,11 +274,22 @@ fn fragment(
     let slot = mesh[in.instance_index].material_and_lightmap_bind_group_slot & 0xffffu;
     let texture = bindless_textures_2d[gnd_bindings[slot].texture];
     let texture_sampler = bindless_samplers_filtering[gnd_bindings[slot].texture_sampler];
+    let lightmap = bindless_textures_2d[gnd_bindings[slot].lightmap];
+    let lightmap_sampler = bindless_samplers_filtering[gnd_bindings[slot].lightmap_sampler];
 #else // BINDLESS
     let texture = gnd_texture;
     let texture_sampler = gnd_texture_sampler;
+    let lightmap = gnd_lightmap;
+    let lightmap_sampler = gnd_lightmap_sampler;
 #endif // BINDLESS
 
+    // Colored light in rgb, shadow in alpha
+#ifdef VERTEX_UVS_B
+    let lighting = textureSample(lightmap, lightmap_sampler, in.uv_b);
+#else // VERTEX_UVS_B
+    let lighting = vec4(0., 0., 0., 1.);
+#endif // VERTEX_UVS_B
+
     pbr_input.material.base_color = textureSample(texture, texture_sampler, in.uv);
     // Key out magenta
     if all(pbr_input.material.base_color.rgb == vec3(1.0, 0., 1.0)) {
@@ -213,10 +298,24 @@ fn fragment(
 
     // alpha discard
     pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+    // apply shadows of the lightmap
+    pbr_input.material.base_color = vec4(
+        pbr_input.material.base_color.rgb * lighting.a,
+        pbr_input.material.base_color.a
+    );
+#ifdef VERTEX_COLORS
+    // apply tint of the surfaces
+    pbr_input.material.base_color = vec4(
+        pbr_input.material.base_color.rgb * in.color.rgb,
+        pbr_input.material.base_color.a
+    );
+#endif // VERTEX_COLORS
 
     var out: FragmentOutput;
     // apply lighting
     out.color = apply_pbr_lighting(pbr_input);
+    // apply colored light of the lightmap
+    out.color = vec4(out.color.rgb + lighting.rgb, out.color.a);
 
     // apply in-shader post processing (fog, alpha-premultiply, and also tonemapping, debanding if the camera is non-hdr)
     // note this does not include fullscreen postprocessing effects like bloom.
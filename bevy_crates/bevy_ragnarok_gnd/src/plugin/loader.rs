@@ -1,27 +1,88 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap, ops::Range};
 
 use bevy_asset::{Handle, LoadContext, RenderAssetUsages, io::Reader};
 use bevy_camera::{primitives::Aabb, visibility::Visibility};
 use bevy_ecs::{bundle::Bundle, entity::Entity, hierarchy::ChildOf, name::Name, world::World};
-use bevy_image::Image;
+use bevy_image::{Image, ImageSampler};
 use bevy_log::trace;
-use bevy_math::Vec3;
-use bevy_mesh::{Mesh, Mesh3d, MeshTag};
+use bevy_math::{Vec2, Vec3, Vec4};
+use bevy_mesh::{Indices, Mesh, Mesh3d, MeshTag, PrimitiveTopology};
 use bevy_pbr::MeshMaterial3d;
 use bevy_ragnarok_water_plane::{WaterPlaneAsset, WaterPlaneBuilder};
-use bevy_render::storage::ShaderStorageBuffer;
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    storage::ShaderStorageBuffer,
+};
 use bevy_scene::Scene;
 use bevy_transform::components::Transform;
 
-use ragnarok_gnd::{Error, Gnd};
+use ragnarok_gnd::{Error, Gnd, Lightmap, Surface};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Cube, Ground,
+    Chunk, Cube, Ground,
     assets::GndAsset,
     material::GndMaterial,
     plugin::{GND_EAST_MESH, GND_NORTH_MESH, GND_TOP_MESH},
 };
 
+/// How the faces of the [`Gnd`] cubes are turned into entities
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroundChunking {
+    /// One [`Cube`] per ground cube, with an entity per face
+    #[default]
+    Cubes,
+    /// [`Chunk`]s of up to `n`x`n` cubes, with a mesh per texture
+    Blocks(u32),
+    /// [`Chunk`]s covering the leaves of the quad tree of the Rsw, with a mesh per texture
+    QuadTreeLeaves,
+}
+
+impl GroundChunking {
+    /// The quad tree of the Rsw has 5 levels under its root, splitting each side
+    /// of the map in 32 leaves
+    const QUAD_TREE_LEAVES_PER_SIDE: usize = 32;
+
+    /// Ranges of cubes covered by each chunk along a side of `length` cubes
+    fn ranges(self, length: usize) -> Vec<Range<usize>> {
+        match self {
+            Self::Cubes => (0..length).map(|i| i..(i + 1)).collect(),
+            Self::Blocks(size) => {
+                let size = (size as usize).max(1);
+                (0..length)
+                    .step_by(size)
+                    .map(|start| start..(start + size).min(length))
+                    .collect()
+            }
+            Self::QuadTreeLeaves => (0..Self::QUAD_TREE_LEAVES_PER_SIDE)
+                .map(|i| {
+                    (i * length / Self::QUAD_TREE_LEAVES_PER_SIDE)
+                        ..((i + 1) * length / Self::QUAD_TREE_LEAVES_PER_SIDE)
+                })
+                .filter(|range| !range.is_empty())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetLoaderSettings {
+    /// Strength of the shadows of the lightmap, usually the
+    /// `shadow_map_alpha` of the lighting parameters of the Rsw
+    pub shadow_map_alpha: f32,
+    /// How the ground cubes are grouped into entities
+    pub chunking: GroundChunking,
+}
+
+impl Default for AssetLoaderSettings {
+    fn default() -> Self {
+        Self {
+            shadow_map_alpha: 1.,
+            chunking: GroundChunking::default(),
+        }
+    }
+}
+
 /// Asset loader for [`GndAsset`]
 ///
 /// ## Labeled assets
@@ -30,6 +91,10 @@ use crate::{
 ///   cubes.
 /// * `Material`: [`GndMaterial`] = Material generated from [`Gnd`]
 ///   textures.
+/// * `Lightmap`: [`Image`] = Atlas of the [`Gnd`] lightmaps, with the
+///   colored light in RGB and the shadow in the alpha.
+/// * `Chunk{x}_{z}Mesh{n}`: [`Mesh`](bevy_mesh::Mesh) = Faces using texture `n` of the
+///   [`Chunk`] starting at cube `x`/`z`, when not using [`GroundChunking::Cubes`].
 /// * `Scene`: [`Scene`](bevy_scene::Scene) = Scene containing all objects represented
 ///   by the [`Gnd`].
 pub struct AssetLoader {
@@ -38,13 +103,13 @@ pub struct AssetLoader {
 
 impl bevy_asset::AssetLoader for AssetLoader {
     type Asset = GndAsset;
-    type Settings = ();
+    type Settings = AssetLoaderSettings;
     type Error = Error;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         trace!("Loading Gnd {:?}.", load_context.path());
@@ -54,23 +119,39 @@ impl bevy_asset::AssetLoader for AssetLoader {
         let gnd = Gnd::from_reader(&mut data.as_slice())?;
 
         let textures = self.load_textures(&gnd, load_context);
-        let surfaces = Self::build_surfaces(&gnd, load_context);
+        let lightmap_atlas = LightmapAtlas::new(&gnd.lightmap);
+        let lightmap = Self::build_lightmap(
+            &gnd,
+            &lightmap_atlas,
+            settings.shadow_map_alpha,
+            load_context,
+        );
+        let surfaces = Self::build_surfaces(&gnd, &lightmap_atlas, load_context);
         let surface_ids = Self::build_surface_ids(&gnd, load_context);
         let cube_faces = Self::build_cube_faces(&gnd, load_context);
         let normals = Self::build_cube_face_normals(&gnd, load_context);
         let materials = Self::build_materials(
             &textures,
+            lightmap.clone(),
             surface_ids.clone(),
             surfaces.clone(),
             cube_faces.clone(),
             normals.clone(),
+            settings.chunking != GroundChunking::Cubes,
             load_context,
         );
-        let scene = Self::build_scene(&gnd, &materials, load_context);
+        let scene = Self::build_scene(
+            &gnd,
+            &materials,
+            &lightmap_atlas,
+            settings.chunking,
+            load_context,
+        )?;
 
         Ok(GndAsset {
             scene,
             textures,
+            lightmap,
             surface_ids,
             surfaces,
             cube_faces,
@@ -92,15 +173,73 @@ impl AssetLoader {
             .collect()
     }
 
+    fn build_lightmap(
+        gnd: &Gnd,
+        lightmap_atlas: &LightmapAtlas,
+        shadow_map_alpha: f32,
+        load_context: &mut LoadContext<'_>,
+    ) -> Handle<Image> {
+        let lightmap = &gnd.lightmap;
+        let atlas_width = lightmap_atlas.width();
+        let atlas_height = lightmap_atlas.height();
+        let tile_size = lightmap.width as usize * lightmap.height as usize;
+
+        // The last tile of the atlas is left without light nor shadow, for surfaces without lightmap
+        let mut data = [0, 0, 0, 255].repeat(atlas_width as usize * atlas_height as usize);
+        for (id, (shadow, light)) in lightmap
+            .shadow_map_pixels
+            .iter()
+            .zip(lightmap.light_map_pixels.iter())
+            .enumerate()
+        {
+            let (column, row) = lightmap_atlas.tile(id);
+            for pixel in 0..tile_size.min(shadow.len()).min(light.len() / 3) {
+                let x = column * lightmap.width + pixel as u32 % lightmap.width;
+                let y = row * lightmap.height + pixel as u32 / lightmap.width;
+                let start = (y as usize * atlas_width as usize + x as usize) * 4;
+
+                let shadow = 255. - (255. - f32::from(shadow[pixel])) * shadow_map_alpha;
+                data[start..(start + 4)].copy_from_slice(&[
+                    light[pixel * 3],
+                    light[pixel * 3 + 1],
+                    light[pixel * 3 + 2],
+                    shadow.clamp(0., 255.) as u8,
+                ]);
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: atlas_width,
+                height: atlas_height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            if cfg!(feature = "debug") {
+                RenderAssetUsages::all()
+            } else {
+                RenderAssetUsages::RENDER_WORLD
+            },
+        );
+        image.sampler = ImageSampler::linear();
+
+        load_context.add_labeled_asset("Lightmap".to_owned(), image)
+    }
+
     fn build_surfaces(
         gnd: &Gnd,
+        lightmap_atlas: &LightmapAtlas,
         load_context: &mut LoadContext<'_>,
     ) -> Handle<ShaderStorageBuffer> {
         let mut surfaces = Vec::with_capacity(gnd.surfaces.len() * GndMaterial::SURFACE_UVS_STRIDE);
         #[cfg(debug_assertions)]
         let initial_capacity = surfaces.capacity();
 
-        for surface in &gnd.surfaces {
+        let vertex_colors = Self::surface_vertex_colors(gnd);
+
+        for (surface, vertex_colors) in gnd.surfaces.iter().zip(vertex_colors) {
             surfaces.extend_from_slice(&surface.bottom_left[0].to_le_bytes());
             surfaces.extend_from_slice(&surface.bottom_left[1].to_le_bytes());
             surfaces.extend_from_slice(&surface.bottom_right[0].to_le_bytes());
@@ -109,6 +248,16 @@ impl AssetLoader {
             surfaces.extend_from_slice(&surface.top_left[1].to_le_bytes());
             surfaces.extend_from_slice(&surface.top_right[0].to_le_bytes());
             surfaces.extend_from_slice(&surface.top_right[1].to_le_bytes());
+
+            let (lightmap_min, lightmap_max) = lightmap_atlas.uvs(surface.lightmap_id);
+            surfaces.extend_from_slice(&lightmap_min[0].to_le_bytes());
+            surfaces.extend_from_slice(&lightmap_min[1].to_le_bytes());
+            surfaces.extend_from_slice(&lightmap_max[0].to_le_bytes());
+            surfaces.extend_from_slice(&lightmap_max[1].to_le_bytes());
+
+            for vertex_color in vertex_colors {
+                surfaces.extend_from_slice(&vertex_color);
+            }
         }
 
         #[cfg(debug_assertions)]
@@ -120,6 +269,56 @@ impl AssetLoader {
         )
     }
 
+    /// Colors of the corners of each surface, in the order bottom left, bottom right,
+    /// top left, and top right.
+    ///
+    /// Like the original client, the corners of upwards facing surfaces take the color
+    /// of the surface of the cube sharing that corner, so the colors blend between cubes.
+    /// Other surfaces only use their own color.
+    fn surface_vertex_colors(gnd: &Gnd) -> Vec<[[u8; 4]; 4]> {
+        let mut vertex_colors = gnd
+            .surfaces
+            .iter()
+            .map(|surface| [surface.bottom_left_vertex_color; 4])
+            .collect::<Vec<_>>();
+
+        let Ok(width) = usize::try_from(gnd.width) else {
+            unreachable!("Width must fit on usize");
+        };
+        let Ok(height) = usize::try_from(gnd.height) else {
+            unreachable!("Height must fit on usize");
+        };
+
+        let upwards_color = |x: usize, z: usize| {
+            gnd.get_cube(x, z)
+                .and_then(|cube| usize::try_from(cube.upwards_facing_surface).ok())
+                .and_then(|surface_id| gnd.surfaces.get(surface_id))
+                .map(|surface| surface.bottom_left_vertex_color)
+        };
+
+        for z in 0..height {
+            for x in 0..width {
+                let Some(surface_id) = gnd
+                    .get_cube(x, z)
+                    .and_then(|cube| usize::try_from(cube.upwards_facing_surface).ok())
+                    .filter(|surface_id| *surface_id < vertex_colors.len())
+                else {
+                    continue;
+                };
+
+                let own_color = vertex_colors[surface_id][0];
+                vertex_colors[surface_id] = [
+                    own_color,
+                    upwards_color(x + 1, z).unwrap_or(own_color),
+                    upwards_color(x, z + 1).unwrap_or(own_color),
+                    upwards_color(x + 1, z + 1).unwrap_or(own_color),
+                ];
+            }
+        }
+
+        vertex_colors
+    }
+
     fn build_surface_ids(
         gnd: &Gnd,
         load_context: &mut LoadContext<'_>,
@@ -235,12 +434,18 @@ impl AssetLoader {
         )
     }
 
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Materials share all buffers of the Gnd"
+    )]
     fn build_materials(
         textures: &[Handle<Image>],
+        lightmap: Handle<Image>,
         surface_ids: Handle<ShaderStorageBuffer>,
         surfaces: Handle<ShaderStorageBuffer>,
         cube_faces: Handle<ShaderStorageBuffer>,
         normals: Handle<ShaderStorageBuffer>,
+        chunked: bool,
         load_context: &mut LoadContext<'_>,
     ) -> Vec<Handle<GndMaterial>> {
         let mut materials = Vec::with_capacity(textures.len());
@@ -250,10 +455,12 @@ impl AssetLoader {
         for (i, texture) in textures.iter().enumerate() {
             let material = GndMaterial {
                 texture: texture.clone(),
+                lightmap: lightmap.clone(),
                 cube_faces: cube_faces.clone(),
                 surface_ids: surface_ids.clone(),
                 surfaces: surfaces.clone(),
                 normals: normals.clone(),
+                chunked,
             };
             materials.push(load_context.add_labeled_asset(format!("Material{i}"), material));
         }
@@ -267,8 +474,10 @@ impl AssetLoader {
     fn build_scene(
         gnd: &Gnd,
         materials: &[Handle<GndMaterial>],
+        lightmap_atlas: &LightmapAtlas,
+        chunking: GroundChunking,
         load_context: &mut LoadContext<'_>,
-    ) -> Handle<Scene> {
+    ) -> Result<Handle<Scene>, Error> {
         let mut world = World::new();
 
         let ground = world
@@ -284,9 +493,20 @@ impl AssetLoader {
             ))
             .id();
 
-        Self::build_cubes(&mut world, gnd, ground, materials);
+        match chunking {
+            GroundChunking::Cubes => Self::build_cubes(&mut world, gnd, ground, materials)?,
+            chunking => Self::build_chunks(
+                &mut world,
+                gnd,
+                ground,
+                materials,
+                lightmap_atlas,
+                chunking,
+                load_context,
+            )?,
+        }
 
-        for (i, water_plane) in gnd.water_planes.iter().enumerate() {
+        for (i, water_plane) in gnd.water_planes().iter().enumerate() {
             world.spawn((
                 Name::new("WaterPlane".to_owned()),
                 WaterPlaneBuilder {
@@ -302,7 +522,7 @@ impl AssetLoader {
             ));
         }
 
-        load_context.add_labeled_asset("Scene".to_owned(), Scene::new(world))
+        Ok(load_context.add_labeled_asset("Scene".to_owned(), Scene::new(world)))
     }
 
     fn build_cubes(
@@ -310,7 +530,7 @@ impl AssetLoader {
         gnd: &Gnd,
         ground: Entity,
         materials: &[Handle<GndMaterial>],
-    ) {
+    ) -> Result<(), Error> {
         let Ok(width) = usize::try_from(gnd.width) else {
             unreachable!("Width must fit on usize");
         };
@@ -355,7 +575,10 @@ impl AssetLoader {
                 .enumerate()
                 {
                     if let Ok(surface_id) = usize::try_from(surface_id) {
-                        let surface = &gnd.surfaces[surface_id];
+                        let surface = Self::surface(gnd, surface_id)?;
+                        let Some(material) = materials.get(usize::from(surface.texture_id)) else {
+                            return Err(Error::MissingTexture(surface.texture_id));
+                        };
 
                         let Ok(tag) = u32::try_from((x + z * width) * 3 + i) else {
                             unreachable!("Tag must fit in u32");
@@ -367,12 +590,14 @@ impl AssetLoader {
                             discriminator,
                             mesh.clone(),
                             aabb,
-                            materials[usize::from(surface.texture_id)].clone(),
+                            material.clone(),
                         ));
                     }
                 }
             }
         }
+
+        Ok(())
     }
 
     fn build_cube_face(
@@ -395,6 +620,206 @@ impl AssetLoader {
         )
     }
 
+    fn build_chunks(
+        world: &mut World,
+        gnd: &Gnd,
+        ground: Entity,
+        materials: &[Handle<GndMaterial>],
+        lightmap_atlas: &LightmapAtlas,
+        chunking: GroundChunking,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<(), Error> {
+        let Ok(width) = usize::try_from(gnd.width) else {
+            unreachable!("Width must fit on usize");
+        };
+        let Ok(height) = usize::try_from(gnd.height) else {
+            unreachable!("Height must fit on usize");
+        };
+
+        let vertex_colors = Self::surface_vertex_colors(gnd);
+        let asset_usage = if cfg!(feature = "debug") {
+            RenderAssetUsages::all()
+        } else {
+            RenderAssetUsages::RENDER_WORLD
+        };
+
+        for z_range in chunking.ranges(height) {
+            for x_range in chunking.ranges(width) {
+                let chunk_entity = world
+                    .spawn((
+                        Name::new(format!("Chunk {}/{}", x_range.start, z_range.start)),
+                        Chunk {
+                            x: x_range.start as u32,
+                            z: z_range.start as u32,
+                            width: x_range.len() as u32,
+                            height: z_range.len() as u32,
+                        },
+                        Transform::default(),
+                        Visibility::default(),
+                        ChildOf(ground),
+                    ))
+                    .id();
+
+                // Faces of the chunk by texture
+                let mut chunk_vertices: BTreeMap<u16, ChunkVertices> = BTreeMap::new();
+                for z in z_range.clone() {
+                    for x in x_range.clone() {
+                        Self::push_cube_faces(
+                            gnd,
+                            x,
+                            z,
+                            lightmap_atlas,
+                            &vertex_colors,
+                            &mut chunk_vertices,
+                        )?;
+                    }
+                }
+
+                for (texture_id, vertices) in chunk_vertices {
+                    let Some(material) = materials.get(usize::from(texture_id)) else {
+                        return Err(Error::MissingTexture(texture_id));
+                    };
+                    let Some(aabb) = Aabb::enclosing(vertices.positions.iter().copied()) else {
+                        continue;
+                    };
+
+                    let mesh = load_context.add_labeled_asset(
+                        format!("Chunk{}_{}Mesh{texture_id}", x_range.start, z_range.start),
+                        vertices.into_mesh(asset_usage),
+                    );
+                    world.spawn((
+                        Name::new(format!("Texture {texture_id}")),
+                        aabb,
+                        Mesh3d(mesh),
+                        MeshMaterial3d(material.clone()),
+                        Transform::default(),
+                        Visibility::default(),
+                        ChildOf(chunk_entity),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the faces of the cube `x`/`z` to the vertices of the texture they use,
+    /// doing on the CPU what the shader does for [`Cube`] faces
+    fn push_cube_faces(
+        gnd: &Gnd,
+        x: usize,
+        z: usize,
+        lightmap_atlas: &LightmapAtlas,
+        vertex_colors: &[[[u8; 4]; 4]],
+        chunk_vertices: &mut BTreeMap<u16, ChunkVertices>,
+    ) -> Result<(), Error> {
+        let Some(cube) = gnd.get_cube(x, z) else {
+            unreachable!("Should never call with invalid coordinates.");
+        };
+        let Some(top_normals) = gnd.calculate_normals(x, z) else {
+            unreachable!("Should never call with invalid coordinates.");
+        };
+
+        let x0 = x as f32 - gnd.width as f32 / 2.;
+        let x1 = x0 + 1.;
+        let z0 = z as f32 - gnd.height as f32 / 2.;
+        let z1 = z0 + 1.;
+
+        let top = gnd.get_top_face_heights(x, z).map(|heights| {
+            (
+                [
+                    Vec3::new(x0, heights[0], z0),
+                    Vec3::new(x1, heights[1], z0),
+                    Vec3::new(x0, heights[2], z1),
+                    Vec3::new(x1, heights[3], z1),
+                ],
+                top_normals.map(Vec3::from_array),
+            )
+        });
+        // Side faces look towards the lower cube, heights grow downwards
+        let east = gnd.get_east_face_heights(x, z).map(|heights| {
+            let normal = if heights[1] > heights[3] || heights[0] > heights[2] {
+                Vec3::NEG_X
+            } else {
+                Vec3::X
+            };
+            (
+                [
+                    Vec3::new(x1, heights[0], z1),
+                    Vec3::new(x1, heights[1], z0),
+                    Vec3::new(x1, heights[2], z1),
+                    Vec3::new(x1, heights[3], z0),
+                ],
+                [normal; 4],
+            )
+        });
+        let north = gnd.get_north_face_heights(x, z).map(|heights| {
+            let normal = if heights[1] > heights[3] || heights[0] > heights[2] {
+                Vec3::NEG_Z
+            } else {
+                Vec3::Z
+            };
+            (
+                [
+                    Vec3::new(x0, heights[0], z1),
+                    Vec3::new(x1, heights[1], z1),
+                    Vec3::new(x0, heights[2], z1),
+                    Vec3::new(x1, heights[3], z1),
+                ],
+                [normal; 4],
+            )
+        });
+
+        for (surface_id, face) in [
+            (cube.upwards_facing_surface, top),
+            (cube.east_facing_surface, east),
+            (cube.north_facing_surface, north),
+        ] {
+            let (Ok(surface_id), Some((positions, normals))) = (usize::try_from(surface_id), face)
+            else {
+                continue;
+            };
+            let surface = Self::surface(gnd, surface_id)?;
+            let (lightmap_min, lightmap_max) = lightmap_atlas.uvs(surface.lightmap_id);
+
+            chunk_vertices
+                .entry(surface.texture_id)
+                .or_default()
+                .push_face(
+                    positions,
+                    normals,
+                    [
+                        surface.bottom_left,
+                        surface.bottom_right,
+                        surface.top_left,
+                        surface.top_right,
+                    ]
+                    .map(Vec2::from_array),
+                    [
+                        Vec2::from_array(lightmap_min),
+                        Vec2::new(lightmap_max[0], lightmap_min[1]),
+                        Vec2::new(lightmap_min[0], lightmap_max[1]),
+                        Vec2::from_array(lightmap_max),
+                    ],
+                    vertex_colors[surface_id].map(|color| {
+                        // Colors are stored in sRGB
+                        let [r, g, b, _] =
+                            color.map(|channel| (f32::from(channel) / 255.).powf(2.2));
+                        Vec4::new(r, g, b, 1.)
+                    }),
+                );
+        }
+
+        Ok(())
+    }
+
+    /// The surface `surface_id` of a cube, which may point past the surfaces of the [`Gnd`]
+    fn surface(gnd: &Gnd, surface_id: usize) -> Result<&Surface, Error> {
+        gnd.surfaces
+            .get(surface_id)
+            .ok_or(Error::MissingSurface(surface_id))
+    }
+
     fn cube_aabb(gnd: &Gnd, x: usize, z: usize) -> Aabb {
         let top_face = gnd.get_top_face_heights(x, z).map(|heights| {
             [
@@ -428,3 +853,114 @@ impl AssetLoader {
         aabb
     }
 }
+
+/// Vertices of the faces of a [`Chunk`] using the same texture
+#[derive(Debug, Default)]
+struct ChunkVertices {
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    lightmap_uvs: Vec<Vec2>,
+    colors: Vec<Vec4>,
+    indices: Vec<u32>,
+}
+
+impl ChunkVertices {
+    /// Pushes a face, vertices are in the order bottom left, bottom right,
+    /// top left, and top right
+    fn push_face(
+        &mut self,
+        positions: [Vec3; 4],
+        normals: [Vec3; 4],
+        uvs: [Vec2; 4],
+        lightmap_uvs: [Vec2; 4],
+        colors: [Vec4; 4],
+    ) {
+        let Ok(first) = u32::try_from(self.positions.len()) else {
+            unreachable!("Chunks must have less than u32::MAX vertices.");
+        };
+        self.positions.extend(positions);
+        self.normals.extend(normals);
+        self.uvs.extend(uvs);
+        self.lightmap_uvs.extend(lightmap_uvs);
+        self.colors.extend(colors);
+        self.indices
+            .extend([0, 1, 2, 1, 3, 2].map(|index| first + index));
+    }
+
+    fn into_mesh(self, asset_usage: RenderAssetUsages) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+            // Lightmap UVs
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, self.lightmap_uvs)
+            // Vertex colors
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+            .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+/// Layout of the [`Lightmap`] tiles in the lightmap atlas, tiles are laid out
+/// in rows with an extra tile at the end for surfaces without lightmap.
+struct LightmapAtlas {
+    columns: u32,
+    rows: u32,
+    tile_width: u32,
+    tile_height: u32,
+}
+
+impl LightmapAtlas {
+    fn new(lightmap: &Lightmap) -> Self {
+        let tiles = lightmap.shadow_map_pixels.len() as u32 + 1;
+        let columns = (tiles as f32).sqrt().ceil() as u32;
+        let rows = tiles.div_ceil(columns);
+
+        Self {
+            columns,
+            rows,
+            tile_width: lightmap.width.max(1),
+            tile_height: lightmap.height.max(1),
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.columns * self.tile_width
+    }
+
+    fn height(&self) -> u32 {
+        self.rows * self.tile_height
+    }
+
+    /// Column and row of the tile of a lightmap
+    fn tile(&self, id: usize) -> (u32, u32) {
+        let id = id as u32;
+        (id % self.columns, id / self.columns)
+    }
+
+    /// Minimum and maximum UVs of the lightmap `lightmap_id`, negative ids have no lightmap.
+    ///
+    /// Lightmaps have a border of 1 pixel that is shared with the neighboring surfaces
+    /// to smooth the filtering, so it is left out of the UVs.
+    fn uvs(&self, lightmap_id: i16) -> ([f32; 2], [f32; 2]) {
+        let id = match usize::try_from(lightmap_id) {
+            Ok(id) if id < (self.columns * self.rows - 1) as usize => id,
+            _ => (self.columns * self.rows - 1) as usize,
+        };
+        let (column, row) = self.tile(id);
+
+        let width = self.width() as f32;
+        let height = self.height() as f32;
+        let border_x = if self.tile_width > 2 { 1. } else { 0. };
+        let border_y = if self.tile_height > 2 { 1. } else { 0. };
+        let min_x = (column * self.tile_width) as f32 + border_x;
+        let min_y = (row * self.tile_height) as f32 + border_y;
+        let max_x = ((column + 1) * self.tile_width) as f32 - border_x;
+        let max_y = ((row + 1) * self.tile_height) as f32 - border_y;
+
+        (
+            [min_x / width, min_y / height],
+            [max_x / width, max_y / height],
+        )
+    }
+}
@@ -5,12 +5,14 @@ use std::borrow::Cow;
 use bevy_app::AppExit;
 use bevy_asset::{AssetApp, Assets, Handle, RenderAssetUsages, uuid_handle};
 use bevy_log::error;
-use bevy_math::{Vec2, Vec3};
+use bevy_math::{Vec2, Vec3, Vec4};
 use bevy_mesh::{Indices, Mesh};
 
 #[cfg(feature = "debug")]
 use crate::debug;
-use crate::{Cube, Ground, assets::GndAsset, material, plugin::loader::AssetLoader};
+use crate::{Chunk, Cube, Ground, assets::GndAsset, material, plugin::loader::AssetLoader};
+
+pub use self::loader::{AssetLoaderSettings, GroundChunking};
 
 const GND_TOP_MESH: Handle<Mesh> = uuid_handle!("886618db-d316-482e-8aeb-c79a73e47f44");
 const GND_EAST_MESH: Handle<Mesh> = uuid_handle!("8ddb2470-39cd-4083-b37d-93d2a84bb2d6");
@@ -35,6 +37,7 @@ impl bevy_app::Plugin for Plugin {
         // Types
         app.register_type::<Ground>();
         app.register_type::<Cube>();
+        app.register_type::<Chunk>();
 
         #[cfg(feature = "debug")]
         app.add_plugins(debug::Plugin);
@@ -67,6 +70,10 @@ impl bevy_app::Plugin for Plugin {
                 )
                 .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![Vec3::NEG_Y; 4])
                 .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![Vec2::ZERO; 4])
+                // Lightmap UVs
+                .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, vec![Vec2::ZERO; 4])
+                // Vertex colors
+                .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![Vec4::ONE; 4])
                 .with_inserted_indices(Indices::U16(INDICES.to_vec())),
         ) {
             error!("{err}");
@@ -88,6 +95,10 @@ impl bevy_app::Plugin for Plugin {
                 )
                 .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![Vec3::X; 4])
                 .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![Vec2::ZERO; 4])
+                // Lightmap UVs
+                .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, vec![Vec2::ZERO; 4])
+                // Vertex colors
+                .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![Vec4::ONE; 4])
                 .with_inserted_indices(Indices::U16(INDICES.to_vec())),
         ) {
             error!("{err}");
@@ -109,6 +120,10 @@ impl bevy_app::Plugin for Plugin {
                 )
                 .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![Vec3::NEG_Z; 4])
                 .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![Vec2::ZERO; 4])
+                // Lightmap UVs
+                .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, vec![Vec2::ZERO; 4])
+                // Vertex colors
+                .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![Vec4::ONE; 4])
                 .with_inserted_indices(Indices::U16(INDICES.to_vec())),
         ) {
             error!("{err}");
@@ -4,6 +4,7 @@
 
 pub mod plugin;
 
+use bevy_camera::visibility::Visibility;
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityHashSet},
@@ -56,3 +57,37 @@ pub struct TrackedEntity {
     /// [`QuadTreeNode`] that this entity is on
     node: Entity,
 }
+
+/// Culls the entities tracked by the [`QuadTree`] from the point of view of
+/// this camera.
+///
+/// Nodes outside of the [`Frustum`](bevy_camera::primitives::Frustum) of the
+/// camera, or further than [`QuadTreeCulling::max_distance`], hide the
+/// [`TrackingEntities`] of their whole subtree, which skips the frustum test
+/// of each hidden entity. Nodes are tested as columns spanning the height of
+/// the root of the [`QuadTree`].
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Clone, Copy, Reflect, Component)]
+#[reflect(Component)]
+pub struct QuadTreeCulling {
+    /// Distance from the camera after which nodes are culled
+    pub max_distance: f32,
+}
+
+impl Default for QuadTreeCulling {
+    fn default() -> Self {
+        Self {
+            max_distance: f32::INFINITY,
+        }
+    }
+}
+
+/// An entity hidden by [`QuadTreeCulling`], its [`Visibility`] is restored
+/// once its [`QuadTreeNode`] is in view again.
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct CulledByQuadTree {
+    /// [`Visibility`] of the entity before it was culled
+    visibility: Visibility,
+}
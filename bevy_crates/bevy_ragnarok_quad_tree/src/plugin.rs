@@ -1,8 +1,12 @@
 //! Sets up the [`QuadTree`].
 
-use bevy_app::{AppExit, Update};
-use bevy_camera::primitives::Aabb;
+use bevy_app::{AppExit, PostUpdate, Update};
+use bevy_camera::{
+    primitives::{Aabb, Frustum},
+    visibility::{Visibility, VisibilitySystems},
+};
 use bevy_ecs::{
+    entity::Entity,
     name::NameOrEntity,
     query::{Changed, With, Without},
     relationship::{Relationship, RelationshipTarget},
@@ -11,10 +15,13 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_log::{error, trace};
-use bevy_math::Vec3A;
+use bevy_math::{Affine3A, Vec3A};
 use bevy_transform::components::{GlobalTransform, Transform};
 
-use crate::{QuadTree, QuadTreeNode, TrackEntity, TrackedEntity, TrackingEntities};
+use crate::{
+    CulledByQuadTree, QuadTree, QuadTreeCulling, QuadTreeNode, TrackEntity, TrackedEntity,
+    TrackingEntities,
+};
 
 /// Add systems to update [`QuadTree`].
 #[cfg_attr(
@@ -29,10 +36,18 @@ impl bevy_app::Plugin for Plugin {
             Update,
             ((initial_insertion, reinsert_on_root), execute_subsystems).chain(),
         );
+        app.add_systems(
+            PostUpdate,
+            cull_tracked_entities
+                .after(VisibilitySystems::UpdateFrusta)
+                .before(VisibilitySystems::VisibilityPropagate),
+        );
 
         app.register_type::<TrackEntity>();
         app.register_type::<QuadTree>();
         app.register_type::<QuadTreeNode>();
+        app.register_type::<QuadTreeCulling>();
+        app.register_type::<CulledByQuadTree>();
         #[cfg(feature = "reflect")]
         {
             use crate::TrackingEntities;
@@ -135,6 +150,96 @@ fn push_tracked_entities_to_child_nodes(
     }
 }
 
+/// Hides the [`TrackingEntities`] of the subtrees of the [`QuadTree`] that
+/// the camera with [`QuadTreeCulling`] can't see, and shows them again
+/// once they are in view.
+fn cull_tracked_entities(
+    mut commands: Commands,
+    camera: Option<Single<(&QuadTreeCulling, &Frustum, &GlobalTransform)>>,
+    quad_tree: Single<Entity, (With<QuadTree>, Without<QuadTreeNode>)>,
+    nodes: Query<(
+        &Aabb,
+        &GlobalTransform,
+        Option<&QuadTree>,
+        Option<&TrackingEntities>,
+    )>,
+    mut visibilities: Query<(&mut Visibility, Option<&CulledByQuadTree>)>,
+    culled_entities: Query<(Entity, &CulledByQuadTree)>,
+) {
+    let Some(camera) = camera else {
+        // Without a camera culling, everything is shown again
+        for (entity, culled_by_quad_tree) in culled_entities {
+            if let Ok((mut visibility, _)) = visibilities.get_mut(entity) {
+                *visibility = culled_by_quad_tree.visibility;
+            }
+            commands.entity(entity).remove::<CulledByQuadTree>();
+        }
+        return;
+    };
+    let (culling, frustum, camera_transform) = camera.into_inner();
+    let camera_position = camera_transform.translation_vec3a();
+
+    let Ok((root_aabb, root_transform, _, _)) = nodes.get(*quad_tree) else {
+        error!("Quad tree {} does not have an Aabb.", *quad_tree);
+        return;
+    };
+    let root_aabb = world_aabb(root_aabb, root_transform);
+
+    let mut to_visit = vec![(*quad_tree, false)];
+    while let Some((node, parent_culled)) = to_visit.pop() {
+        let Ok((aabb, transform, child_nodes, tracking_entities)) = nodes.get(node) else {
+            continue;
+        };
+
+        let culled = parent_culled || {
+            let mut column = world_aabb(aabb, transform);
+            column.center.y = root_aabb.center.y;
+            column.half_extents.y = root_aabb.half_extents.y;
+
+            let closest = camera_position.clamp(column.min(), column.max());
+            !frustum.intersects_obb(&column, &Affine3A::IDENTITY, true, false)
+                || closest.distance(camera_position) > culling.max_distance
+        };
+
+        for entity in tracking_entities
+            .into_iter()
+            .flat_map(|tracking| tracking.iter())
+        {
+            let Ok((mut visibility, culled_by_quad_tree)) = visibilities.get_mut(entity) else {
+                continue;
+            };
+            match (culled, culled_by_quad_tree) {
+                (true, None) => {
+                    commands.entity(entity).insert(CulledByQuadTree {
+                        visibility: *visibility,
+                    });
+                    *visibility = Visibility::Hidden;
+                }
+                (false, Some(culled_by_quad_tree)) => {
+                    commands.entity(entity).remove::<CulledByQuadTree>();
+                    *visibility = culled_by_quad_tree.visibility;
+                }
+                (true, Some(_)) | (false, None) => (),
+            }
+        }
+
+        if let Some(child_nodes) = child_nodes {
+            to_visit.extend(child_nodes.iter().map(|child| (child, culled)));
+        }
+    }
+}
+
+/// Bounds in world space of an [`Aabb`] moved by `transform`
+fn world_aabb(aabb: &Aabb, transform: &GlobalTransform) -> Aabb {
+    let affine = transform.affine();
+    Aabb {
+        center: affine.transform_point3a(aabb.center),
+        half_extents: affine.matrix3.x_axis.abs() * aabb.half_extents.x
+            + affine.matrix3.y_axis.abs() * aabb.half_extents.y
+            + affine.matrix3.z_axis.abs() * aabb.half_extents.z,
+    }
+}
+
 trait ContainsPointExt {
     fn contains_point(&self, point: impl Into<Vec3A>) -> bool;
 }
@@ -156,3 +261,79 @@ impl ContainsPointExt for Aabb {
         x_test && z_test
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bevy_camera::{
+        primitives::{Aabb, Frustum},
+        visibility::Visibility,
+    };
+    use bevy_ecs::{relationship::Relationship, system::RunSystemOnce, world::World};
+    use bevy_math::{Mat4, Vec3};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::cull_tracked_entities;
+    use crate::{CulledByQuadTree, QuadTreeCulling, QuadTreeNode, TrackedEntity};
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "This is a test")]
+    fn culls_subtrees() {
+        let mut world = World::new();
+        let root = world
+            .spawn((
+                Aabb::from_min_max(Vec3::new(-10., -1., -10.), Vec3::new(10., 1., 10.)),
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+        let mut spawn_node = |min: Vec3, max: Vec3| {
+            let node = world
+                .spawn((
+                    Aabb::from_min_max(min, max),
+                    GlobalTransform::IDENTITY,
+                    <QuadTreeNode as Relationship>::from(root),
+                ))
+                .id();
+            world
+                .spawn((
+                    Visibility::Visible,
+                    <TrackedEntity as Relationship>::from(node),
+                ))
+                .id()
+        };
+        let in_view = spawn_node(Vec3::new(0., -1., 0.), Vec3::new(10., 1., 10.));
+        let out_of_view = spawn_node(Vec3::new(-10., -1., -10.), Vec3::new(0., 1., 0.));
+
+        // Looking down on the middle of the node in view
+        let camera_transform =
+            Transform::from_xyz(5., 5., 5.).looking_at(Vec3::new(5., 0., 5.), Vec3::X);
+        let frustum = Frustum::from_clip_from_world(
+            &(Mat4::perspective_rh(0.5, 1., 0.1, 100.) * camera_transform.to_matrix().inverse()),
+        );
+        let camera = world
+            .spawn((
+                QuadTreeCulling { max_distance: 100. },
+                frustum,
+                GlobalTransform::from(camera_transform),
+            ))
+            .id();
+
+        world.run_system_once(cull_tracked_entities).unwrap();
+        assert_eq!(world.get(in_view), Some(&Visibility::Visible));
+        assert_eq!(world.get(out_of_view), Some(&Visibility::Hidden));
+        assert!(world.get::<CulledByQuadTree>(out_of_view).is_some());
+
+        // The camera is 4 above the node in view
+        world
+            .get_mut::<QuadTreeCulling>(camera)
+            .unwrap()
+            .max_distance = 3.;
+        world.run_system_once(cull_tracked_entities).unwrap();
+        assert_eq!(world.get(in_view), Some(&Visibility::Hidden));
+
+        world.entity_mut(camera).remove::<QuadTreeCulling>();
+        world.run_system_once(cull_tracked_entities).unwrap();
+        assert_eq!(world.get(in_view), Some(&Visibility::Visible));
+        assert_eq!(world.get(out_of_view), Some(&Visibility::Visible));
+        assert!(world.get::<CulledByQuadTree>(out_of_view).is_none());
+    }
+}
@@ -0,0 +1,134 @@
+//! Merges the primitives of static Rsm models into larger meshes
+
+use bevy_asset::RenderAssetUsages;
+use bevy_camera::primitives::Aabb;
+use bevy_math::{Affine3A, Vec3, Vec3A};
+use bevy_mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+
+/// Vertices of primitives of static models that use the same
+/// [`RsmMaterial`](crate::materials::RsmMaterial)
+#[derive(Debug, Default)]
+pub struct StaticBatch {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl StaticBatch {
+    /// Appends the vertices of a primitive `mesh`, moved by `transform`.
+    ///
+    /// Returns `false` if `mesh` does not have the attributes of
+    /// a Rsm primitive.
+    pub fn push(&mut self, mesh: &Mesh, transform: &Affine3A) -> bool {
+        let (
+            Some(VertexAttributeValues::Float32x3(positions)),
+            Some(VertexAttributeValues::Float32x3(normals)),
+            Some(VertexAttributeValues::Float32x2(uvs)),
+            Some(VertexAttributeValues::Float32x4(colors)),
+            Some(indices),
+        ) = (
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0),
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR),
+            mesh.indices(),
+        )
+        else {
+            return false;
+        };
+
+        let first = self.positions.len() as u32;
+        // Normals are moved by the inverse transpose to survive non uniform scales
+        let normal_matrix = transform.matrix3.inverse().transpose();
+
+        self.positions.extend(positions.iter().map(|position| {
+            transform
+                .transform_point3(Vec3::from_array(*position))
+                .to_array()
+        }));
+        self.normals.extend(normals.iter().map(|normal| {
+            (normal_matrix * Vec3A::from_array(*normal))
+                .normalize_or_zero()
+                .to_array()
+        }));
+        self.uvs.extend_from_slice(uvs);
+        self.colors.extend_from_slice(colors);
+        self.indices
+            .extend(indices.iter().map(|index| first + index as u32));
+
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Bounds of all vertices of the batch
+    pub fn aabb(&self) -> Option<Aabb> {
+        Aabb::enclosing(self.positions.iter().copied().map(Vec3::from_array))
+    }
+
+    pub fn into_mesh(self, asset_usage: RenderAssetUsages) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+            .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::RenderAssetUsages;
+    use bevy_math::{Affine3A, Vec3};
+    use bevy_mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+
+    use super::StaticBatch;
+
+    fn triangle() -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]],
+            )
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 1., 0.]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0., 0.]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.; 4]; 3])
+            .with_inserted_indices(Indices::U16(vec![0, 1, 2]))
+    }
+
+    #[test]
+    fn merges_transformed_meshes() {
+        let mut batch = StaticBatch::default();
+        assert!(batch.push(&triangle(), &Affine3A::IDENTITY));
+        assert!(batch.push(
+            &triangle(),
+            &Affine3A::from_scale_rotation_translation(
+                Vec3::new(2., -1., 1.),
+                Default::default(),
+                Vec3::new(10., 0., 0.),
+            ),
+        ));
+
+        let mesh = batch.into_mesh(RenderAssetUsages::all());
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            unreachable!("Batches have u32 indices.");
+        };
+        assert_eq!(indices, &[0, 1, 2, 3, 4, 5]);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            unreachable!("Batches have positions.");
+        };
+        assert_eq!(positions[4], [12., 0., 0.]);
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            unreachable!("Batches have normals.");
+        };
+        assert_eq!(normals[4], [0., -1., 0.]);
+    }
+}
@@ -1,22 +1,30 @@
 //! Extensions to Ragnarok Online's Rsm files
 
 use bevy_animation::{
-    animated_field,
+    AnimationClip, AnimationTargetId, animated_field,
     animation_curves::{AnimatableCurve, AnimatedField, AnimationCurve},
 };
 use bevy_asset::RenderAssetUsages;
 use bevy_camera::primitives::Aabb;
-use bevy_math::{Mat4, Quat, Vec3, curve::UnevenSampleAutoCurve};
+use bevy_ecs::name::Name;
+use bevy_math::{EulerRot, Mat4, Quat, Vec3, curve::UnevenSampleAutoCurve};
 use bevy_transform::components::Transform;
 use ragnarok_rsm::{
-    AnimationDuration, Rsm,
+    AnimationDuration, Rsm, VolumeBox,
     mesh::{Mesh, Primitive, Transformation},
 };
 
+use crate::UvTransform;
+
 /// Extension trait for the root [`Rsm`] file
 pub trait RsmExt {
     #[must_use]
     fn position_animation_curve(&self) -> Option<impl AnimationCurve>;
+
+    /// Offset removed from the root mesh when recentering the model, see
+    /// [`RsmMeshExt::recentered_transform`]
+    #[must_use]
+    fn root_offset(&self) -> Vec3;
 }
 
 impl RsmExt for Rsm {
@@ -57,6 +65,20 @@ impl RsmExt for Rsm {
             None
         }
     }
+
+    fn root_offset(&self) -> Vec3 {
+        let Some(root_mesh) = self
+            .root_meshes
+            .first()
+            .and_then(|root_mesh| self.meshes.iter().find(|mesh| mesh.name == *root_mesh))
+        else {
+            return Vec3::ZERO;
+        };
+        match (&root_mesh.transformation, root_mesh.bounds()) {
+            (Transformation::Simple(_), _) | (_, None) => Vec3::ZERO,
+            (_, Some(bounds)) => Vec3::new(bounds.center.x, bounds.max().y, bounds.center.z),
+        }
+    }
 }
 
 /// Extension for a [`Rsm`]'s [`Mesh`].
@@ -90,6 +112,20 @@ pub trait RsmMeshExt {
         &self,
         animation_duration: AnimationDuration,
     ) -> Option<impl AnimationCurve>;
+
+    /// Id of the primitives of the mesh using the texture `texture_id`
+    /// targeted by the texture animations
+    #[must_use]
+    fn texture_animation_target_id(&self, texture_id: i32) -> AnimationTargetId;
+
+    /// Adds the curves of the texture animations of the mesh to
+    /// `animation_clip`, animating the [`UvTransform`] of its primitives.
+    /// Returns whether any curve was added.
+    fn add_texture_animation_curves(
+        &self,
+        animation_duration: AnimationDuration,
+        animation_clip: &mut AnimationClip,
+    ) -> bool;
 }
 
 impl RsmMeshExt for Mesh {
@@ -227,28 +263,91 @@ impl RsmMeshExt for Mesh {
             None
         }
     }
+
+    fn texture_animation_target_id(&self, texture_id: i32) -> AnimationTargetId {
+        AnimationTargetId::from_names(
+            [
+                Name::new(self.name.to_string()),
+                Name::new(format!("Texture{texture_id}")),
+            ]
+            .iter(),
+        )
+    }
+
+    fn add_texture_animation_curves(
+        &self,
+        animation_duration: AnimationDuration,
+        animation_clip: &mut AnimationClip,
+    ) -> bool {
+        let mut populated = false;
+
+        for texture_animation in &self.texture_animations {
+            let id = self.texture_animation_target_id(texture_animation.texture_id);
+            for animation in &texture_animation.animations {
+                let curve =
+                    match UnevenSampleAutoCurve::new(animation.key_frames.iter().map(
+                        |(frame, value)| (animation_duration.transform(*frame as f32), *value),
+                    )) {
+                        Ok(curve) => curve,
+                        Err(err) => {
+                            log::error!(
+                                "Failed to build texture animation of {} due to `{err}`.",
+                                self.name
+                            );
+                            continue;
+                        }
+                    };
+
+                match animation.animation_type {
+                    0 => animation_clip.add_curve_to_target(
+                        id,
+                        AnimatableCurve::new(animated_field!(UvTransform::offset_u), curve),
+                    ),
+                    1 => animation_clip.add_curve_to_target(
+                        id,
+                        AnimatableCurve::new(animated_field!(UvTransform::offset_v), curve),
+                    ),
+                    2 => animation_clip.add_curve_to_target(
+                        id,
+                        AnimatableCurve::new(animated_field!(UvTransform::scale_u), curve),
+                    ),
+                    3 => animation_clip.add_curve_to_target(
+                        id,
+                        AnimatableCurve::new(animated_field!(UvTransform::scale_v), curve),
+                    ),
+                    4 => animation_clip.add_curve_to_target(
+                        id,
+                        AnimatableCurve::new(animated_field!(UvTransform::rotation), curve),
+                    ),
+                    animation_type => {
+                        log::warn!(
+                            "Mesh {} had unknown texture animation type {animation_type}.",
+                            self.name
+                        );
+                        continue;
+                    }
+                }
+                populated = true;
+            }
+        }
+
+        populated
+    }
 }
 
 /// Extension for a [`Rsm`] [`Mesh`]'s [`Primitive`].
 pub trait RsmPrimitiveExt {
-    fn into_mesh(self) -> bevy_mesh::Mesh;
+    fn into_mesh(self, asset_usage: RenderAssetUsages) -> bevy_mesh::Mesh;
 }
 
 impl RsmPrimitiveExt for Primitive {
-    fn into_mesh(self) -> bevy_mesh::Mesh {
-        bevy_mesh::Mesh::new(
-            bevy_mesh::PrimitiveTopology::TriangleList,
-            if cfg!(feature = "debug") {
-                RenderAssetUsages::all()
-            } else {
-                RenderAssetUsages::RENDER_WORLD
-            },
-        )
-        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_POSITION, self.vertices)
-        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_NORMAL, self.normals)
-        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_UV_0, self.uv)
-        .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_COLOR, self.color)
-        .with_inserted_indices(bevy_mesh::Indices::U16(self.indices))
+    fn into_mesh(self, asset_usage: RenderAssetUsages) -> bevy_mesh::Mesh {
+        bevy_mesh::Mesh::new(bevy_mesh::PrimitiveTopology::TriangleList, asset_usage)
+            .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_POSITION, self.vertices)
+            .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_NORMAL, self.normals)
+            .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_UV_0, self.uv)
+            .with_inserted_attribute(bevy_mesh::Mesh::ATTRIBUTE_COLOR, self.color)
+            .with_inserted_indices(bevy_mesh::Indices::U16(self.indices))
     }
 }
 
@@ -303,3 +402,111 @@ impl RsmTransformationExt for Transformation {
         }
     }
 }
+
+/// Extension for a [`Rsm`]'s [`VolumeBox`].
+pub trait RsmVolumeBoxExt {
+    /// Transform of the center of the box, relative to the root of a model
+    /// whose root mesh was moved by `root_offset`, see [`RsmExt::root_offset`]
+    fn transform(&self, root_offset: Vec3) -> Transform;
+
+    fn half_size(&self) -> Vec3;
+}
+
+impl RsmVolumeBoxExt for VolumeBox {
+    fn transform(&self, root_offset: Vec3) -> Transform {
+        Transform {
+            translation: Vec3::from_array(self.position) - root_offset,
+            rotation: Quat::from_euler(
+                EulerRot::XYZ,
+                self.rotation[0],
+                self.rotation[1],
+                self.rotation[2],
+            ),
+            scale: Vec3::ONE,
+        }
+    }
+
+    fn half_size(&self) -> Vec3 {
+        Vec3::from_array(self.size) / 2.
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::any::TypeId;
+
+    use bevy_animation::{AnimationClip, animation_curves::EvaluatorId};
+    use ragnarok_rsm::{
+        AnimationDuration,
+        mesh::{Mesh, TextureAnimation, Textures, Transformation},
+    };
+
+    use super::RsmMeshExt;
+    use crate::UvTransform;
+
+    /// Mesh with a texture animation of `animation_type` on its texture 0
+    fn animated_mesh(animation_type: i32) -> Mesh {
+        let mut bytes = vec![];
+        // Texture 0 has one animation
+        bytes.extend_from_slice(&0i32.to_le_bytes());
+        bytes.extend_from_slice(&1i32.to_le_bytes());
+        // Animation with two key frames
+        bytes.extend_from_slice(&animation_type.to_le_bytes());
+        bytes.extend_from_slice(&2i32.to_le_bytes());
+        for (frame, value) in [(0i32, 0f32), (1000, 1.)] {
+            bytes.extend_from_slice(&frame.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let Ok(texture_animation) = TextureAnimation::from_reader(&mut bytes.as_slice()) else {
+            unreachable!("Texture animation is complete.");
+        };
+
+        Mesh {
+            name: "mesh".into(),
+            parent_name: "".into(),
+            textures: Textures::Indexes(Box::new([0])),
+            transformation_matrix: [1., 0., 0., 0., 1., 0., 0., 0., 1.],
+            transformation: Transformation::Simple([0.; 3]),
+            vertices: Box::new([]),
+            uvs: Box::new([]),
+            faces: Box::new([]),
+            scale_key_frames: Box::new([]),
+            rotation_key_frames: Box::new([]),
+            position_key_frames: Box::new([]),
+            texture_animations: Box::new([texture_animation]),
+        }
+    }
+
+    #[test]
+    fn texture_animation_fields() {
+        // Animation types follow the order of the fields of `UvTransform`:
+        // offset_u, offset_v, scale_u, scale_v, and rotation
+        for animation_type in 0..=4 {
+            let mesh = animated_mesh(animation_type);
+            let mut clip = AnimationClip::default();
+            assert!(mesh.add_texture_animation_curves(AnimationDuration::Simple(1000.), &mut clip));
+
+            let Some([curve]) = clip
+                .curves_for_target(mesh.texture_animation_target_id(0))
+                .map(Vec::as_slice)
+            else {
+                unreachable!("Texture 0 has one curve.");
+            };
+            let EvaluatorId::ComponentField(field) = curve.0.evaluator_id() else {
+                unreachable!("Texture animations animate fields.");
+            };
+            assert_eq!(
+                **field,
+                (TypeId::of::<UvTransform>(), animation_type as usize)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_texture_animation() {
+        let mesh = animated_mesh(5);
+        let mut clip = AnimationClip::default();
+        assert!(!mesh.add_texture_animation_curves(AnimationDuration::Simple(1000.), &mut clip));
+        assert!(clip.curves().is_empty());
+    }
+}
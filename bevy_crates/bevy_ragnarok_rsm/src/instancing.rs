@@ -0,0 +1,794 @@
+//! Draws many copies of a static Rsm model with one draw call per primitive
+
+use std::num::NonZero;
+
+use bevy_app::Update;
+use bevy_asset::{AssetId, AssetServer, Assets, Handle, embedded_asset, load_embedded_asset};
+use bevy_camera::visibility::{NoFrustumCulling, Visibility};
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Transparent3d},
+    prepass::{OpaqueNoLightmap3dBatchSetKey, OpaqueNoLightmap3dBinKey},
+};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    name::{Name, NameOrEntity},
+    query::{Has, ROQueryItem, With, Without},
+    reflect::ReflectComponent,
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{
+        Commands, Populated, Query, Res, ResMut, SystemChangeTick, SystemParamItem,
+        lifetimeless::{Read, SRes},
+    },
+    world::Ref,
+};
+use bevy_image::Image;
+use bevy_math::{Affine3A, Mat4};
+use bevy_mesh::{Mesh3d, MeshVertexBufferLayoutRef, VertexBufferLayout};
+use bevy_pbr::{
+    MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshViewBindGroup,
+    SetMeshViewBindingArrayBindGroup, ViewKeyCache,
+};
+use bevy_reflect::Reflect;
+use bevy_render::{
+    Extract, ExtractSchedule, Render, RenderApp, RenderStartup, RenderSystems,
+    batching::NoAutomaticBatching,
+    mesh::{RenderMesh, RenderMeshBufferInfo, allocator::MeshAllocator},
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, BinnedRenderPhaseType, DrawFunctions, InputUniformIndex, PhaseItem,
+        PhaseItemExtraIndex, RenderCommand, RenderCommandResult, SetItemPipeline,
+        TrackedRenderPass, ViewBinnedRenderPhases, ViewSortedRenderPhases,
+    },
+    render_resource::{
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, Buffer,
+        BufferInitDescriptor, BufferUsages, Face, PipelineCache, RenderPipelineDescriptor,
+        SamplerBindingType, ShaderStages, SpecializedMeshPipeline, SpecializedMeshPipelineError,
+        SpecializedMeshPipelines, TextureSampleType, VertexAttribute, VertexFormat, VertexStepMode,
+        binding_types::{sampler, texture_2d, uniform_buffer_sized},
+    },
+    renderer::RenderDevice,
+    sync_component::SyncComponentPlugin,
+    sync_world::{MainEntity, RenderEntity},
+    texture::GpuImage,
+    view::ExtractedView,
+};
+use bevy_scene::Scene;
+use bevy_shader::{Shader, ShaderDefVal};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    Model, RsmMaterials,
+    assets::RsmModel,
+    materials::{BLEND_DEPTH_BIAS, RsmMaterial, RsmMaterialKey},
+};
+
+/// Index of the bind group of the [`RsmMaterial`] of the instances, the
+/// same as the one of [`bevy_pbr::MATERIAL_BIND_GROUP_INDEX`]
+const MATERIAL_BIND_GROUP_INDEX: usize = 3;
+/// First shader location of the per instance attributes, after the ones
+/// used by [`MeshPipeline`]
+const INSTANCE_SHADER_LOCATION: u32 = 8;
+/// Vec4s per instance, 4 for the transform and 3 for the normal matrix
+const INSTANCE_VEC4S: usize = 7;
+
+pub struct Plugin;
+
+impl bevy_app::Plugin for Plugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        embedded_asset!(app, "materials/shaders/rsm_instanced_shader.wgsl");
+
+        app.add_plugins(SyncComponentPlugin::<InstancedPrimitive>::default())
+            .add_systems(Update, spawn_instanced_primitives);
+
+        app.register_type::<RsmInstances>();
+        app.register_type::<InstancedPrimitive>();
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .add_render_command::<AlphaMask3d, DrawRsmInstanced>()
+            .add_render_command::<Transparent3d, DrawRsmInstanced>()
+            .init_resource::<SpecializedMeshPipelines<RsmInstancedPipeline>>()
+            .add_systems(RenderStartup, init_rsm_instanced_pipeline)
+            .add_systems(ExtractSchedule, extract_instanced_primitives)
+            .add_systems(
+                Render,
+                (
+                    prepare_instanced_primitives.in_set(RenderSystems::PrepareResources),
+                    queue_instanced_primitives.in_set(RenderSystems::QueueMeshes),
+                ),
+            );
+    }
+}
+
+/// Copies of a static Rsm model.
+///
+/// Once the model is loaded, an [`InstancedPrimitive`] is spawned as a child
+/// for each of its primitives, drawing all copies in a single draw call.
+/// Copies with 1 or 3 negative scale axis are drawn with
+/// [`RsmMaterials::inverted`], in their own [`InstancedPrimitive`].
+///
+/// Only models without animations can be instanced, and changes to
+/// `transforms` after the primitives were spawned are not tracked.
+/// Instanced models do not cast shadows.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct RsmInstances {
+    pub model: Handle<RsmModel>,
+    /// Transforms of each copy, relative to the entity
+    pub transforms: Vec<Transform>,
+}
+
+/// A primitive of a [`RsmInstances`] drawn once for all `instances`
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+#[require(NoFrustumCulling, NoAutomaticBatching)]
+pub struct InstancedPrimitive {
+    pub material: Handle<RsmMaterial>,
+    /// Transforms of each copy of the primitive, relative to the
+    /// [`RsmInstances`]
+    pub instances: Vec<Affine3A>,
+}
+
+/// Spawns the [`InstancedPrimitive`]s of [`RsmInstances`] whose model
+/// finished loading.
+fn spawn_instanced_primitives(
+    mut commands: Commands,
+    rsm_instances: Populated<(NameOrEntity, &RsmInstances), Without<Children>>,
+    rsm_models: Res<Assets<RsmModel>>,
+    scenes: Res<Assets<Scene>>,
+) {
+    for (entity, rsm_instances) in rsm_instances.into_inner() {
+        let Some(scene) = rsm_models
+            .get(&rsm_instances.model)
+            .and_then(|rsm_model| scenes.get(&rsm_model.model))
+        else {
+            continue;
+        };
+        let scene_world = &scene.world;
+
+        let animated = scene_world.try_query::<&Model>().is_some_and(|mut models| {
+            models
+                .iter(scene_world)
+                .any(|model| model.animation.is_some())
+        });
+        if animated {
+            log::error!("{entity} has an animated model, it can't be instanced.");
+            commands.entity(entity.entity).remove::<RsmInstances>();
+            continue;
+        }
+
+        let Some(mut primitives) = scene_world.try_query::<(Entity, &Mesh3d, &RsmMaterials)>()
+        else {
+            continue;
+        };
+        for (i, (primitive, mesh, rsm_materials)) in primitives.iter(scene_world).enumerate() {
+            // Transform of the primitive relative to the root of the model
+            let mut model_from_primitive = Affine3A::IDENTITY;
+            let mut ancestor = Some(primitive);
+            while let Some(current) = ancestor {
+                if let Some(transform) = scene_world.get::<Transform>(current) {
+                    model_from_primitive = transform.compute_affine() * model_from_primitive;
+                }
+                ancestor = scene_world.get::<ChildOf>(current).map(ChildOf::parent);
+            }
+
+            let mut base = Vec::new();
+            let mut inverted = Vec::new();
+            for transform in &rsm_instances.transforms {
+                let instance = transform.compute_affine() * model_from_primitive;
+                if rsm_materials.material_for(&GlobalTransform::from(instance))
+                    == &rsm_materials.base
+                {
+                    base.push(instance);
+                } else {
+                    inverted.push(instance);
+                }
+            }
+
+            for (name, material, instances) in [
+                (format!("InstancedPrimitive{i}"), &rsm_materials.base, base),
+                (
+                    format!("InstancedPrimitive{i}/Inverted"),
+                    &rsm_materials.inverted,
+                    inverted,
+                ),
+            ] {
+                if instances.is_empty() {
+                    continue;
+                }
+                commands.spawn((
+                    Name::new(name),
+                    Mesh3d(mesh.0.clone()),
+                    InstancedPrimitive {
+                        material: material.clone(),
+                        instances,
+                    },
+                    ChildOf(entity.entity),
+                ));
+            }
+        }
+    }
+}
+
+/// [`InstancedPrimitive`] in the render world
+#[derive(Component)]
+struct ExtractedInstancedPrimitive {
+    texture: AssetId<Image>,
+    alpha: f32,
+    key: RsmMaterialKey,
+    /// Instance transforms in world space
+    instances: Vec<Affine3A>,
+}
+
+/// Gpu resources of an [`ExtractedInstancedPrimitive`]
+#[derive(Component)]
+struct PreparedInstancedPrimitive {
+    instance_buffer: Buffer,
+    instance_count: u32,
+    bind_group: BindGroup,
+}
+
+#[expect(clippy::type_complexity, reason = "Queries are complex")]
+fn extract_instanced_primitives(
+    mut commands: Commands,
+    primitives: Extract<Query<(RenderEntity, Ref<InstancedPrimitive>, Ref<GlobalTransform>)>>,
+    materials: Extract<Res<Assets<RsmMaterial>>>,
+    extracted: Query<(), With<ExtractedInstancedPrimitive>>,
+) {
+    for (render_entity, primitive, global_transform) in primitives.iter() {
+        if extracted.contains(render_entity)
+            && !primitive.is_changed()
+            && !global_transform.is_changed()
+        {
+            continue;
+        }
+        // Materials that are not loaded yet are retried next frame
+        let Some(material) = materials.get(&primitive.material) else {
+            continue;
+        };
+
+        let world_from_primitive = global_transform.affine();
+        commands
+            .entity(render_entity)
+            .insert(ExtractedInstancedPrimitive {
+                texture: material.texture.id(),
+                alpha: material.alpha,
+                key: RsmMaterialKey::from(material),
+                instances: primitive
+                    .instances
+                    .iter()
+                    .map(|instance| world_from_primitive * *instance)
+                    .collect(),
+            });
+    }
+}
+
+fn prepare_instanced_primitives(
+    mut commands: Commands,
+    primitives: Query<(
+        Entity,
+        Ref<ExtractedInstancedPrimitive>,
+        Has<PreparedInstancedPrimitive>,
+    )>,
+    images: Res<RenderAssets<GpuImage>>,
+    pipeline: Res<RsmInstancedPipeline>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, primitive, prepared) in primitives.iter() {
+        if prepared && !primitive.is_changed() {
+            continue;
+        }
+        // Textures that are not loaded yet are retried next frame
+        let Some(texture) = images.get(primitive.texture) else {
+            continue;
+        };
+
+        let mut instance_data = Vec::with_capacity(primitive.instances.len() * INSTANCE_VEC4S * 16);
+        for instance in &primitive.instances {
+            // Normals are moved by the inverse transpose to survive non uniform scales
+            let normal_matrix = instance.matrix3.inverse().transpose();
+            let world_from_local = Mat4::from(*instance);
+            for value in world_from_local
+                .to_cols_array()
+                .into_iter()
+                .chain(normal_matrix.x_axis.extend(0.).to_array())
+                .chain(normal_matrix.y_axis.extend(0.).to_array())
+                .chain(normal_matrix.z_axis.extend(0.).to_array())
+            {
+                instance_data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let instance_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("rsm_instance_buffer"),
+            contents: &instance_data,
+            usage: BufferUsages::VERTEX,
+        });
+
+        let alpha: Vec<u8> = [primitive.alpha, 0., 0., 0.]
+            .into_iter()
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        let alpha_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("rsm_instanced_alpha_buffer"),
+            contents: &alpha,
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "rsm_instanced_material_bind_group",
+            &pipeline.material_layout,
+            &BindGroupEntries::sequential((
+                &texture.texture_view,
+                &texture.sampler,
+                alpha_buffer.as_entire_binding(),
+            )),
+        );
+
+        commands.entity(entity).insert(PreparedInstancedPrimitive {
+            instance_buffer,
+            instance_count: primitive.instances.len() as u32,
+            bind_group,
+        });
+    }
+}
+
+/// Queues blended primitives in [`Transparent3d`], and the others in
+/// [`AlphaMask3d`] like [`RsmMaterial`]s that are not blended
+#[expect(clippy::too_many_arguments, reason = "Queuing reads the whole view")]
+fn queue_instanced_primitives(
+    alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<RsmInstancedPipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<RsmInstancedPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<RenderMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    view_key_cache: Res<ViewKeyCache>,
+    primitives: Query<
+        (Entity, &MainEntity, &ExtractedInstancedPrimitive),
+        With<PreparedInstancedPrimitive>,
+    >,
+    mut alpha_mask_phases: ResMut<ViewBinnedRenderPhases<AlphaMask3d>>,
+    mut transparent_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<&ExtractedView>,
+    ticks: SystemChangeTick,
+) {
+    let alpha_mask_draw_rsm_instanced = alpha_mask_draw_functions.read().id::<DrawRsmInstanced>();
+    let transparent_draw_rsm_instanced = transparent_draw_functions.read().id::<DrawRsmInstanced>();
+
+    for view in views.iter() {
+        let (Some(alpha_mask_phase), Some(transparent_phase)) = (
+            alpha_mask_phases.get_mut(&view.retained_view_entity),
+            transparent_phases.get_mut(&view.retained_view_entity),
+        ) else {
+            continue;
+        };
+        let Some(view_key) = view_key_cache.get(&view.retained_view_entity) else {
+            continue;
+        };
+        let rangefinder = view.rangefinder3d();
+
+        for (entity, main_entity, primitive) in primitives.iter() {
+            // Hidden primitives are not extracted as meshes
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(*main_entity)
+            else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+
+            let mesh_key = *view_key
+                | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology())
+                | if primitive.key.blend {
+                    MeshPipelineKey::BLEND_ALPHA
+                } else {
+                    MeshPipelineKey::MAY_DISCARD
+                };
+
+            let pipeline_id = match pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                RsmInstancedPipelineKey {
+                    mesh_key,
+                    material_key: primitive.key,
+                },
+                &mesh.layout,
+            ) {
+                Ok(pipeline_id) => pipeline_id,
+                Err(err) => {
+                    log::error!("Failed to specialize instanced Rsm pipeline due to `{err}`.");
+                    continue;
+                }
+            };
+
+            if primitive.key.blend {
+                transparent_phase.add(Transparent3d {
+                    entity: (entity, *main_entity),
+                    pipeline: pipeline_id,
+                    draw_function: transparent_draw_rsm_instanced,
+                    distance: rangefinder.distance_translation(&mesh_instance.translation)
+                        + BLEND_DEPTH_BIAS,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::None,
+                    indexed: mesh.indexed(),
+                });
+            } else {
+                // Instances bring their own vertex buffer, so they are drawn
+                // outside of the batching of meshes
+                alpha_mask_phase.add(
+                    OpaqueNoLightmap3dBatchSetKey {
+                        pipeline: pipeline_id,
+                        draw_function: alpha_mask_draw_rsm_instanced,
+                        material_bind_group_index: None,
+                        vertex_slab: Default::default(),
+                        index_slab: None,
+                    },
+                    OpaqueNoLightmap3dBinKey {
+                        asset_id: mesh_instance.mesh_asset_id.untyped(),
+                    },
+                    (entity, *main_entity),
+                    InputUniformIndex::default(),
+                    BinnedRenderPhaseType::NonMesh,
+                    ticks.this_run(),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+struct RsmInstancedPipeline {
+    mesh_pipeline: MeshPipeline,
+    /// Takes the place of the mesh bind group, instances do not read
+    /// the mesh uniforms
+    empty_layout: BindGroupLayout,
+    empty_bind_group: BindGroup,
+    material_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+fn init_rsm_instanced_pipeline(
+    mut commands: Commands,
+    mesh_pipeline: Res<MeshPipeline>,
+    render_device: Res<RenderDevice>,
+    asset_server: Res<AssetServer>,
+) {
+    let empty_layout = render_device.create_bind_group_layout("rsm_instanced_empty_layout", &[]);
+    let empty_bind_group =
+        render_device.create_bind_group("rsm_instanced_empty_bind_group", &empty_layout, &[]);
+    let material_layout = render_device.create_bind_group_layout(
+        "rsm_instanced_material_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                sampler(SamplerBindingType::Filtering),
+                uniform_buffer_sized(false, NonZero::new(16)),
+            ),
+        ),
+    );
+
+    commands.insert_resource(RsmInstancedPipeline {
+        mesh_pipeline: mesh_pipeline.clone(),
+        empty_layout,
+        empty_bind_group,
+        material_layout,
+        shader: load_embedded_asset!(
+            asset_server.as_ref(),
+            "materials/shaders/rsm_instanced_shader.wgsl"
+        ),
+    });
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct RsmInstancedPipelineKey {
+    mesh_key: MeshPipelineKey,
+    material_key: RsmMaterialKey,
+}
+
+impl SpecializedMeshPipeline for RsmInstancedPipeline {
+    type Key = RsmInstancedPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key.mesh_key, layout)?;
+        descriptor.label = Some("rsm_instanced_pipeline".into());
+
+        descriptor.layout.truncate(2);
+        descriptor.layout.push(self.empty_layout.clone());
+        descriptor.layout.push(self.material_layout.clone());
+
+        descriptor.vertex.shader = self.shader.clone();
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: (INSTANCE_VEC4S as u64) * VertexFormat::Float32x4.size(),
+            step_mode: VertexStepMode::Instance,
+            attributes: (0..INSTANCE_VEC4S as u32)
+                .map(|i| VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: u64::from(i) * VertexFormat::Float32x4.size(),
+                    shader_location: INSTANCE_SHADER_LOCATION + i,
+                })
+                .collect(),
+        });
+
+        descriptor.primitive.cull_mode = if key.material_key.double_sided {
+            None
+        } else if key.material_key.inverted_scale {
+            Some(Face::Front)
+        } else {
+            Some(Face::Back)
+        };
+
+        let material_bind_group = ShaderDefVal::UInt(
+            "MATERIAL_BIND_GROUP".into(),
+            MATERIAL_BIND_GROUP_INDEX as u32,
+        );
+        descriptor
+            .vertex
+            .shader_defs
+            .push(material_bind_group.clone());
+        let Some(fragment) = &mut descriptor.fragment else {
+            unreachable!("Mesh pipelines have a fragment state.");
+        };
+        fragment.shader = self.shader.clone();
+        fragment.shader_defs.push(material_bind_group);
+        if key.material_key.double_sided {
+            fragment
+                .shader_defs
+                .push("RSM_MATERIAL_DOUBLE_SIDED".into());
+        }
+        if key.material_key.inverted_scale {
+            fragment.shader_defs.push("RSM_MATERIAL_MIRRORED".into());
+        }
+        if key.material_key.blend {
+            fragment.shader_defs.push("RSM_MATERIAL_BLEND".into());
+        }
+
+        Ok(descriptor)
+    }
+}
+
+type DrawRsmInstanced = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshViewBindingArrayBindGroup<1>,
+    SetRsmInstancedBindGroups<MATERIAL_BIND_GROUP_INDEX>,
+    DrawInstancedPrimitive,
+);
+
+/// Binds the material of the instances at `I`, and an empty bind group
+/// in place of the mesh bind group at `I - 1`
+struct SetRsmInstancedBindGroups<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetRsmInstancedBindGroups<I> {
+    type Param = SRes<RsmInstancedPipeline>;
+    type ViewQuery = ();
+    type ItemQuery = Read<PreparedInstancedPrimitive>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, '_, Self::ViewQuery>,
+        prepared: Option<ROQueryItem<'w, '_, Self::ItemQuery>>,
+        pipeline: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(prepared) = prepared else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_bind_group(I - 1, &pipeline.into_inner().empty_bind_group, &[]);
+        pass.set_bind_group(I, &prepared.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+struct DrawInstancedPrimitive;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawInstancedPrimitive {
+    type Param = (
+        SRes<RenderAssets<RenderMesh>>,
+        SRes<RenderMeshInstances>,
+        SRes<MeshAllocator>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = Read<PreparedInstancedPrimitive>;
+
+    fn render<'w>(
+        item: &P,
+        _view: ROQueryItem<'w, '_, Self::ViewQuery>,
+        prepared: Option<ROQueryItem<'w, '_, Self::ItemQuery>>,
+        (meshes, render_mesh_instances, mesh_allocator): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_allocator = mesh_allocator.into_inner();
+
+        let Some(prepared) = prepared else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(item.main_entity())
+        else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(vertex_buffer_slice) =
+            mesh_allocator.mesh_vertex_slice(&mesh_instance.mesh_asset_id)
+        else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_vertex_buffer(0, vertex_buffer_slice.buffer.slice(..));
+        pass.set_vertex_buffer(1, prepared.instance_buffer.slice(..));
+
+        match &gpu_mesh.buffer_info {
+            RenderMeshBufferInfo::Indexed {
+                index_format,
+                count,
+            } => {
+                let Some(index_buffer_slice) =
+                    mesh_allocator.mesh_index_slice(&mesh_instance.mesh_asset_id)
+                else {
+                    return RenderCommandResult::Skip;
+                };
+
+                pass.set_index_buffer(index_buffer_slice.buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(
+                    index_buffer_slice.range.start..(index_buffer_slice.range.start + count),
+                    vertex_buffer_slice.range.start as i32,
+                    0..prepared.instance_count,
+                );
+            }
+            RenderMeshBufferInfo::NonIndexed => {
+                pass.draw(vertex_buffer_slice.range, 0..prepared.instance_count);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::{Assets, Handle, uuid_handle};
+    use bevy_ecs::{
+        hierarchy::{ChildOf, Children},
+        system::RunSystemOnce,
+        world::World,
+    };
+    use bevy_math::{Affine3A, Quat, Vec3};
+    use bevy_mesh::Mesh3d;
+    use bevy_scene::Scene;
+    use bevy_transform::components::Transform;
+
+    use super::{InstancedPrimitive, RsmInstances, spawn_instanced_primitives};
+    use crate::{Model, ModelAnimation, RsmMaterials, assets::RsmModel, materials::RsmMaterial};
+
+    const BASE: Handle<RsmMaterial> = uuid_handle!("3c1b6a2e-8d4f-4e0a-9b1c-2f7d5e6a8b90");
+    const INVERTED: Handle<RsmMaterial> = uuid_handle!("9a4e2c7b-1f3d-4b6a-8e5c-0d2b7f9a1c34");
+
+    /// Model whose only primitive is 1 unit along X from the root
+    fn rsm_model(world: &mut World, animated: bool) -> Handle<RsmModel> {
+        let mut scene_world = World::new();
+        let root = scene_world
+            .spawn((
+                Model {
+                    animation: animated.then(|| ModelAnimation {
+                        animation: Handle::default(),
+                        animation_node_index: Default::default(),
+                    }),
+                },
+                Transform::default(),
+            ))
+            .id();
+        scene_world.spawn((
+            Mesh3d(Handle::default()),
+            RsmMaterials {
+                base: BASE,
+                inverted: INVERTED,
+            },
+            Transform::from_xyz(1., 0., 0.),
+            ChildOf(root),
+        ));
+
+        let model = world
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(scene_world));
+        world
+            .resource_mut::<Assets<RsmModel>>()
+            .add(RsmModel { model })
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Assets<Scene>>();
+        world.init_resource::<Assets<RsmModel>>();
+        world
+    }
+
+    #[test]
+    fn splits_mirrored_instances() {
+        let mut world = world();
+        let model = rsm_model(&mut world, false);
+        let rsm_instances = world
+            .spawn(RsmInstances {
+                model,
+                transforms: vec![
+                    Transform::from_xyz(0., 0., 10.),
+                    Transform::from_xyz(0., 0., 20.),
+                    Transform::from_scale(Vec3::new(-1., 1., 1.)),
+                ],
+            })
+            .id();
+
+        let Ok(()) = world.run_system_once(spawn_instanced_primitives) else {
+            unreachable!("There are RsmInstances to spawn.");
+        };
+
+        let mut primitives = world.query::<(&InstancedPrimitive, &ChildOf)>();
+        let primitives = primitives.iter(&world).collect::<Vec<_>>();
+        assert_eq!(primitives.len(), 2);
+        assert!(
+            primitives
+                .iter()
+                .all(|(_, child_of)| child_of.parent() == rsm_instances)
+        );
+
+        let Some((base, _)) = primitives
+            .iter()
+            .find(|(primitive, _)| primitive.material == BASE)
+        else {
+            unreachable!("Instances without mirroring use the base material.");
+        };
+        assert_eq!(
+            base.instances,
+            [
+                Affine3A::from_translation(Vec3::new(1., 0., 10.)),
+                Affine3A::from_translation(Vec3::new(1., 0., 20.)),
+            ]
+        );
+
+        let Some((inverted, _)) = primitives
+            .iter()
+            .find(|(primitive, _)| primitive.material == INVERTED)
+        else {
+            unreachable!("Mirrored instances use the inverted material.");
+        };
+        assert_eq!(
+            inverted.instances,
+            [Affine3A::from_scale_rotation_translation(
+                Vec3::new(-1., 1., 1.),
+                Quat::IDENTITY,
+                Vec3::new(-1., 0., 0.),
+            )]
+        );
+    }
+
+    #[test]
+    fn animated_models_are_not_instanced() {
+        let mut world = world();
+        let model = rsm_model(&mut world, true);
+        let rsm_instances = world
+            .spawn(RsmInstances {
+                model,
+                transforms: vec![Transform::default(); 2],
+            })
+            .id();
+
+        let Ok(()) = world.run_system_once(spawn_instanced_primitives) else {
+            unreachable!("There are RsmInstances to spawn.");
+        };
+
+        assert!(world.get::<RsmInstances>(rsm_instances).is_none());
+        assert!(world.get::<Children>(rsm_instances).is_none());
+    }
+}
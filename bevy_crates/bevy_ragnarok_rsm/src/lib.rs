@@ -1,16 +1,21 @@
 //! Builds Ragnarok Online's Rsm files to be used in Bevy
 
 pub mod assets;
+pub mod batching;
 #[cfg(feature = "debug")]
 pub mod debug;
 pub mod extensions;
+pub mod instancing;
 pub mod materials;
 pub mod plugin;
+pub mod volume_box;
 
 use bevy_animation::{AnimationClip, graph::AnimationNodeIndex};
 use bevy_asset::Handle;
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Mat3, Vec2};
 use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
 
 use crate::materials::RsmMaterial;
 
@@ -35,3 +40,104 @@ pub struct RsmMaterials {
     /// Used when the model has 1 or 3 negative scale axis
     pub inverted: Handle<RsmMaterial>,
 }
+
+impl RsmMaterials {
+    /// Material to use based on the number of negative scale axis
+    /// of `global_transform`
+    pub fn material_for(&self, global_transform: &GlobalTransform) -> &Handle<RsmMaterial> {
+        let inverted_axis = global_transform.scale().is_negative_bitmask().count_ones();
+        if inverted_axis.is_multiple_of(2) {
+            &self.base
+        } else {
+            &self.inverted
+        }
+    }
+}
+
+/// Transform of the texture coordinates of a Rsm model primitive, animated
+/// by the model's [`AnimationClip`] and copied into its [`RsmMaterials`]
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+pub struct UvTransform {
+    pub offset_u: f32,
+    pub offset_v: f32,
+    pub scale_u: f32,
+    pub scale_v: f32,
+    /// Rotation in radians around the center of the texture
+    pub rotation: f32,
+}
+
+impl Default for UvTransform {
+    fn default() -> Self {
+        Self {
+            offset_u: 0.,
+            offset_v: 0.,
+            scale_u: 1.,
+            scale_v: 1.,
+            rotation: 0.,
+        }
+    }
+}
+
+impl UvTransform {
+    /// Matrix applied to the texture coordinates, scaling then rotating
+    /// them around the center of the texture before the offset
+    pub fn matrix(&self) -> Mat3 {
+        let center = Vec2::splat(0.5);
+        Mat3::from_translation(Vec2::new(self.offset_u, self.offset_v) + center)
+            * Mat3::from_angle(self.rotation)
+            * Mat3::from_scale(Vec2::new(self.scale_u, self.scale_v))
+            * Mat3::from_translation(-center)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy_math::Vec2;
+
+    use super::UvTransform;
+
+    /// Asserts that `uv_transform` moves the texture coordinate `from` to `to`
+    fn assert_moves(uv_transform: UvTransform, from: [f32; 2], to: [f32; 2]) {
+        let moved = uv_transform
+            .matrix()
+            .transform_point2(Vec2::from_array(from));
+        assert!(
+            moved.abs_diff_eq(Vec2::from_array(to), 1e-6),
+            "{moved} != {to:?}"
+        );
+    }
+
+    #[test]
+    fn default_matrix() {
+        assert_moves(UvTransform::default(), [0.25, 0.75], [0.25, 0.75]);
+    }
+
+    #[test]
+    fn matrix_pivots_on_center() {
+        let uv_transform = UvTransform {
+            scale_u: 2.,
+            scale_v: 0.5,
+            rotation: FRAC_PI_2,
+            ..Default::default()
+        };
+        // The center stays in place for both the scale and the rotation
+        assert_moves(uv_transform, [0.5, 0.5], [0.5, 0.5]);
+        // Scaled to (1.5, 0.5) then rotated a quarter turn around the center
+        assert_moves(uv_transform, [1., 0.5], [0.5, 1.5]);
+    }
+
+    #[test]
+    fn matrix_offset() {
+        let uv_transform = UvTransform {
+            offset_u: 0.25,
+            offset_v: -0.5,
+            scale_u: 2.,
+            ..Default::default()
+        };
+        assert_moves(uv_transform, [0.5, 0.5], [0.75, 0.]);
+        assert_moves(uv_transform, [0., 0.], [-0.25, -0.5]);
+    }
+}
@@ -2,6 +2,7 @@ use std::marker::PhantomData;
 
 use bevy_asset::{Asset, AssetApp, AssetPath, Handle, embedded_asset, embedded_path};
 use bevy_image::Image;
+use bevy_math::Mat3;
 use bevy_mesh::MeshVertexBufferLayoutRef;
 use bevy_pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
 use bevy_reflect::Reflect;
@@ -12,6 +13,10 @@ use bevy_render::{
 };
 use bevy_shader::ShaderRef;
 
+/// Bias of the sorting of blended models, drawing them after the
+/// water planes around them
+pub(crate) const BLEND_DEPTH_BIAS: f32 = 20.;
+
 pub struct Plugin;
 
 impl bevy_app::Plugin for Plugin {
@@ -40,6 +45,12 @@ pub struct RsmMaterial {
     #[texture(0)]
     #[sampler(1)]
     pub texture: Handle<Image>,
+    /// Transform of the texture coordinates, see [`crate::UvTransform`]
+    #[uniform(2)]
+    pub uv_transform: Mat3,
+    /// Alpha of the whole model, from 0 to 1
+    #[uniform(3)]
+    pub alpha: f32,
     /// Double sided materials are visible from both sides
     pub double_sided: bool,
     /// There can be models that have N numbers of negative scale axis,
@@ -49,15 +60,30 @@ pub struct RsmMaterial {
     pub transparency: bool,
 }
 
+impl RsmMaterial {
+    /// Whether the material is drawn with [`AlphaMode::Blend`]
+    pub fn is_blended(&self) -> bool {
+        self.transparency || self.alpha < 1.
+    }
+}
+
 impl Material for RsmMaterial {
     fn alpha_mode(&self) -> AlphaMode {
-        if self.transparency {
+        if self.is_blended() {
             AlphaMode::Blend
         } else {
             AlphaMode::Mask(0.5)
         }
     }
 
+    fn depth_bias(&self) -> f32 {
+        if self.is_blended() {
+            BLEND_DEPTH_BIAS
+        } else {
+            0.
+        }
+    }
+
     fn vertex_shader() -> ShaderRef {
         ShaderRef::Path(
             AssetPath::from_path_buf(embedded_path!("shaders/rsm_vertex_shader.wgsl"))
@@ -110,6 +136,9 @@ impl Material for RsmMaterial {
             if key.bind_group_data.inverted_scale {
                 frag.shader_defs.push("RSM_MATERIAL_MIRRORED".into());
             }
+            if key.bind_group_data.blend {
+                frag.shader_defs.push("RSM_MATERIAL_BLEND".into());
+            }
         }
 
         Ok(())
@@ -118,8 +147,9 @@ impl Material for RsmMaterial {
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RsmMaterialKey {
-    double_sided: bool,
-    inverted_scale: bool,
+    pub(crate) double_sided: bool,
+    pub(crate) inverted_scale: bool,
+    pub(crate) blend: bool,
 }
 
 impl From<&RsmMaterial> for RsmMaterialKey {
@@ -127,6 +157,7 @@ impl From<&RsmMaterial> for RsmMaterialKey {
         Self {
             double_sided: value.double_sided,
             inverted_scale: value.inverse_scale,
+            blend: value.is_blended(),
         }
     }
 }
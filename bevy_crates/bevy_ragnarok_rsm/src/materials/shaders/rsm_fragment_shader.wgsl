@@ -4,12 +4,14 @@
     pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
     pbr_types::{
         PbrInput,
+        STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND,
         STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK,
     },
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var rsm_texture: texture_2d<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(1) var rsm_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> rsm_alpha: f32;
 
 fn rsm_default_material(in: VertexOutput, is_front: bool) -> PbrInput {
     #ifdef RSM_MATERIAL_DOUBLE_SIDED
@@ -25,7 +27,11 @@ fn rsm_default_material(in: VertexOutput, is_front: bool) -> PbrInput {
     var pbr_input = pbr_input_from_vertex_output(in, is_front_m, double_sided);
 
     pbr_input.material.reflectance = vec3(0.0);
+    #ifdef RSM_MATERIAL_BLEND
+    pbr_input.material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND;
+    #else
     pbr_input.material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK;
+    #endif
 
     return pbr_input;
 }
@@ -39,6 +45,7 @@ fn fragment(
     if all(color.rgb == vec3(1.0, 0., 1.0)) {
         discard;
     }
+    color.a *= rsm_alpha;
 
     var pbr_input = rsm_default_material(in, is_front);
     pbr_input.material.base_color = color;
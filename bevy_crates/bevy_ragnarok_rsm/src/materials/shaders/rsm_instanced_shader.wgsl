@@ -0,0 +1,118 @@
+#import bevy_pbr::{
+    mesh_types::MESH_FLAGS_SHADOW_RECEIVER_BIT,
+    mesh_view_bindings::view,
+    pbr_functions::{
+        apply_pbr_lighting,
+        calculate_view,
+        main_pass_post_lighting_processing,
+        prepare_world_normal,
+    },
+    pbr_types::{
+        PbrInput,
+        pbr_input_new,
+        STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND,
+        STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK,
+    },
+    view_transformations::position_world_to_clip,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var rsm_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var rsm_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> rsm_alpha: vec4<f32>;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    // Per instance
+    @location(8) world_from_local_0: vec4<f32>,
+    @location(9) world_from_local_1: vec4<f32>,
+    @location(10) world_from_local_2: vec4<f32>,
+    @location(11) world_from_local_3: vec4<f32>,
+    @location(12) normal_matrix_0: vec4<f32>,
+    @location(13) normal_matrix_1: vec4<f32>,
+    @location(14) normal_matrix_2: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+}
+
+@vertex
+fn vertex(in: Vertex) -> VertexOutput {
+    var vertex_output: VertexOutput;
+
+    let world_from_local = mat4x4<f32>(
+        in.world_from_local_0,
+        in.world_from_local_1,
+        in.world_from_local_2,
+        in.world_from_local_3,
+    );
+    let normal_matrix = mat3x3<f32>(
+        in.normal_matrix_0.xyz,
+        in.normal_matrix_1.xyz,
+        in.normal_matrix_2.xyz,
+    );
+
+    vertex_output.world_position = world_from_local * vec4<f32>(in.position, 1.0);
+    vertex_output.position = position_world_to_clip(vertex_output.world_position.xyz);
+    vertex_output.world_normal = normalize(normal_matrix * in.normal);
+    vertex_output.uv = in.uv;
+
+    return vertex_output;
+}
+
+fn rsm_instanced_material(in: VertexOutput, is_front: bool) -> PbrInput {
+    #ifdef RSM_MATERIAL_DOUBLE_SIDED
+    let double_sided = true;
+    #else
+    let double_sided = false;
+    #endif
+    #ifdef RSM_MATERIAL_MIRRORED
+    let is_front_m = !is_front;
+    #else
+    let is_front_m = is_front;
+    #endif
+
+    // Same as `pbr_input_from_vertex_output`, without reading the mesh uniforms
+    var pbr_input = pbr_input_new();
+    pbr_input.flags = MESH_FLAGS_SHADOW_RECEIVER_BIT;
+    pbr_input.is_orthographic = view.clip_from_view[3].w == 1.0;
+    pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
+    pbr_input.frag_coord = in.position;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = prepare_world_normal(in.world_normal, double_sided, is_front_m);
+    pbr_input.N = normalize(pbr_input.world_normal);
+
+    pbr_input.material.reflectance = vec3(0.0);
+    #ifdef RSM_MATERIAL_BLEND
+    pbr_input.material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND;
+    #else
+    pbr_input.material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK;
+    #endif
+
+    return pbr_input;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> @location(0) vec4<f32> {
+    var color = textureSample(rsm_texture, rsm_sampler, in.uv);
+    if all(color.rgb == vec3(1.0, 0., 1.0)) {
+        discard;
+    }
+    color.a *= rsm_alpha.x;
+
+    var pbr_input = rsm_instanced_material(in, is_front);
+    pbr_input.material.base_color = color;
+
+    var out_color = apply_pbr_lighting(pbr_input);
+    out_color = main_pass_post_lighting_processing(pbr_input, out_color);
+
+    return out_color;
+}
@@ -13,6 +13,8 @@
 }
 #endif
 
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> rsm_uv_transform: mat3x3<f32>;
+
 @vertex
 fn vertex(in: Vertex) -> VertexOutput {
     var vertex_output: VertexOutput;
@@ -32,7 +34,7 @@ fn vertex(in: Vertex) -> VertexOutput {
         in.instance_index
     );
 #endif
-    vertex_output.uv = in.uv;
+    vertex_output.uv = (rsm_uv_transform * vec3<f32>(in.uv, 1.0)).xy;
 
 #ifdef DEPTH_CLAMP_ORTHO
     vertex_output.clip_position_unclamped = vertex_output.position;
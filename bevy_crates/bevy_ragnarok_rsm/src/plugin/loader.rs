@@ -4,32 +4,44 @@ use bevy_animation::{
     AnimationClip, AnimationPlayer, AnimationTarget, AnimationTargetId,
     graph::{AnimationGraph, AnimationGraphHandle},
 };
-use bevy_asset::{AssetLoader as BevyAssetLoader, Handle, LoadContext, io::Reader};
+use bevy_asset::{
+    AssetLoader as BevyAssetLoader, Handle, LoadContext, RenderAssetUsages, io::Reader,
+};
 use bevy_camera::visibility::Visibility;
 use bevy_ecs::{
     bundle::Bundle,
     entity::Entity,
     hierarchy::{ChildOf, Children},
     name::Name,
-    spawn::{SpawnIter, SpawnRelated, SpawnableList},
+    spawn::{SpawnRelated, SpawnableList},
     world::World,
 };
 use bevy_image::Image;
+use bevy_math::Mat3;
 use bevy_mesh::Mesh3d;
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_scene::Scene;
 use bevy_transform::components::Transform;
 use ragnarok_rsm::{Rsm, ShadeType, mesh::Textures};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Model, ModelAnimation, RsmMaterials,
+    Model, ModelAnimation, RsmMaterials, UvTransform,
     assets::RsmModel,
-    extensions::{RsmExt, RsmMeshExt, RsmPrimitiveExt},
+    extensions::{RsmExt, RsmMeshExt, RsmPrimitiveExt, RsmVolumeBoxExt},
     materials::RsmMaterial,
+    volume_box::VolumeBox,
 };
 
 type TextureCache = HashMap<(Handle<Image>, bool), (Handle<RsmMaterial>, Handle<RsmMaterial>)>;
 
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AssetLoaderSettings {
+    /// Keeps the meshes of the model in the main world, needed to
+    /// batch static props with [`StaticBatch`](crate::batching::StaticBatch)
+    pub keep_meshes: bool,
+}
+
 pub struct AssetLoader {
     texture_path_prefix: PathBuf,
 }
@@ -48,13 +60,13 @@ impl AssetLoader {
 
 impl BevyAssetLoader for AssetLoader {
     type Asset = RsmModel;
-    type Settings = ();
+    type Settings = AssetLoaderSettings;
     type Error = ragnarok_rsm::Error;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut data: Vec<u8> = vec![];
@@ -62,8 +74,14 @@ impl BevyAssetLoader for AssetLoader {
 
         let rsm = ragnarok_rsm::Rsm::from_reader(&mut data.as_slice())?;
 
+        let mesh_asset_usage = if cfg!(feature = "debug") || settings.keep_meshes {
+            RenderAssetUsages::all()
+        } else {
+            RenderAssetUsages::RENDER_WORLD
+        };
+
         let scene = {
-            let scene = SceneBuilder::build(&rsm, load_context, self);
+            let scene = SceneBuilder::build(&rsm, load_context, self, mesh_asset_usage);
             load_context.add_labeled_asset("Scene".to_owned(), scene)
         };
 
@@ -78,7 +96,12 @@ impl BevyAssetLoader for AssetLoader {
 pub struct SceneBuilder;
 
 impl SceneBuilder {
-    pub fn build(rsm: &Rsm, load_context: &mut LoadContext<'_>, loader: &AssetLoader) -> Scene {
+    pub fn build(
+        rsm: &Rsm,
+        load_context: &mut LoadContext<'_>,
+        loader: &AssetLoader,
+        mesh_asset_usage: RenderAssetUsages,
+    ) -> Scene {
         log::trace!("Generating animated prop {:?}.", load_context.path());
         let mut world = World::new();
 
@@ -114,9 +137,25 @@ impl SceneBuilder {
                 &mut texture_cache,
                 load_context,
                 loader,
+                mesh_asset_usage,
             )),
         ));
 
+        if let Some(volume_boxes) = &rsm.volume_boxes {
+            let root_offset = rsm.root_offset();
+            for (i, volume_box) in volume_boxes.iter().enumerate() {
+                world.spawn((
+                    ChildOf(root),
+                    Name::new(format!("VolumeBox{i}")),
+                    volume_box.transform(root_offset),
+                    VolumeBox {
+                        half_size: volume_box.half_size(),
+                        flag: volume_box.flag,
+                    },
+                ));
+            }
+        }
+
         Scene { world }
     }
 
@@ -189,6 +228,9 @@ impl SceneBuilder {
                     animation_clip.add_curve_to_target(id, scale);
                     populated = true;
                 }
+                if mesh.add_texture_animation_curves(animation_duration, &mut animation_clip) {
+                    populated = true;
+                }
             }
 
             if populated {
@@ -216,6 +258,7 @@ struct MeshListItem {
 }
 
 impl MeshList {
+    #[expect(clippy::too_many_arguments, reason = "Meshes are built recursively")]
     pub fn new(
         rsm: &Rsm,
         to_build: &[&str],
@@ -224,6 +267,7 @@ impl MeshList {
         texture_cache: &mut TextureCache,
         load_context: &mut LoadContext,
         loader: &AssetLoader,
+        mesh_asset_usage: RenderAssetUsages,
     ) -> Self {
         let mut mesh_list = Vec::new();
 
@@ -275,9 +319,11 @@ impl MeshList {
                 primitives: PrimitiveList::new(
                     rsm,
                     mesh_index,
+                    animation_player,
                     texture_cache,
                     load_context,
                     loader,
+                    mesh_asset_usage,
                 ),
                 animation_player,
                 children: MeshList::new(
@@ -288,6 +334,7 @@ impl MeshList {
                     texture_cache,
                     load_context,
                     loader,
+                    mesh_asset_usage,
                 ),
             });
         }
@@ -331,15 +378,19 @@ struct PrimitiveListItem {
     mesh: Handle<bevy_mesh::Mesh>,
     material: Handle<RsmMaterial>,
     inverted_material: Handle<RsmMaterial>,
+    /// Target of the texture animation of the primitive, if its texture is animated
+    texture_animation: Option<AnimationTarget>,
 }
 
 impl PrimitiveList {
     pub fn new(
         rsm: &Rsm,
         mesh_index: usize,
+        animation_player: Entity,
         texture_cache: &mut TextureCache,
         load_context: &mut LoadContext,
         loader: &AssetLoader,
+        mesh_asset_usage: RenderAssetUsages,
     ) -> Self {
         let mut primitive_list = Vec::new();
 
@@ -359,8 +410,16 @@ impl PrimitiveList {
 
         for (id, primitive) in mesh_attributes.primitives.into_iter().enumerate() {
             let texture_id = primitive.texture_id;
+            let texture_animation = rsm_mesh
+                .texture_animations
+                .iter()
+                .any(|texture_animation| texture_animation.texture_id == texture_id)
+                .then(|| AnimationTarget {
+                    id: rsm_mesh.texture_animation_target_id(texture_id),
+                    player: animation_player,
+                });
             let double_sided = primitive.double_sided;
-            let mesh = primitive.into_mesh();
+            let mesh = primitive.into_mesh(mesh_asset_usage);
 
             let texture_count = texture_cache.len();
             let Ok(texture_id) = usize::try_from(texture_id) else {
@@ -374,36 +433,71 @@ impl PrimitiveList {
             );
 
             let transparency = textures[texture_id].ends_with("tga");
+            let alpha = f32::from(rsm.alpha) / 255.;
 
-            let material = texture_cache
-                .entry((texture.clone(), double_sided))
-                .or_insert((
+            let material = if texture_animation.is_some() {
+                // Animated textures can't share their materials
+                (
                     load_context.add_labeled_asset(
-                        format!("Material{}", texture_count),
+                        format!("Mesh{}/Primitive{}/Material", mesh_index, id),
                         RsmMaterial {
                             texture: texture.clone(),
+                            uv_transform: Mat3::IDENTITY,
+                            alpha,
                             double_sided,
                             inverse_scale: false,
                             transparency,
                         },
                     ),
                     load_context.add_labeled_asset(
-                        format!("Material{}/Inverted", texture_count),
+                        format!("Mesh{}/Primitive{}/Material/Inverted", mesh_index, id),
                         RsmMaterial {
                             texture: texture.clone(),
+                            uv_transform: Mat3::IDENTITY,
+                            alpha,
                             double_sided,
                             inverse_scale: true,
                             transparency,
                         },
                     ),
-                ));
+                )
+            } else {
+                texture_cache
+                    .entry((texture.clone(), double_sided))
+                    .or_insert((
+                        load_context.add_labeled_asset(
+                            format!("Material{}", texture_count),
+                            RsmMaterial {
+                                texture: texture.clone(),
+                                uv_transform: Mat3::IDENTITY,
+                                alpha,
+                                double_sided,
+                                inverse_scale: false,
+                                transparency,
+                            },
+                        ),
+                        load_context.add_labeled_asset(
+                            format!("Material{}/Inverted", texture_count),
+                            RsmMaterial {
+                                texture: texture.clone(),
+                                uv_transform: Mat3::IDENTITY,
+                                alpha,
+                                double_sided,
+                                inverse_scale: true,
+                                transparency,
+                            },
+                        ),
+                    ))
+                    .clone()
+            };
 
             primitive_list.push(PrimitiveListItem {
                 name: Name::new(format!("Primitive{}", id)),
                 mesh: load_context
                     .add_labeled_asset(format!("Mesh{}/Primitive{}/Mesh", mesh_index, id), mesh),
-                material: material.0.clone(),
-                inverted_material: material.1.clone(),
+                material: material.0,
+                inverted_material: material.1,
+                texture_animation,
             });
         }
 
@@ -416,22 +510,28 @@ impl PrimitiveList {
 
 impl SpawnableList<ChildOf> for PrimitiveList {
     fn spawn(this: bevy_ecs::ptr::MovingPtr<'_, Self>, world: &mut World, entity: Entity) {
-        world.spawn((
-            ChildOf(entity),
-            Name::new("Primitives"),
-            this.transform,
-            Visibility::default(),
-            Children::spawn(SpawnIter(this.primitives.clone().into_iter().map(|item| {
-                (
-                    item.name,
-                    Mesh3d(item.mesh),
-                    RsmMaterials {
-                        base: item.material,
-                        inverted: item.inverted_material,
-                    },
-                )
-            }))),
-        ));
+        let primitives = world
+            .spawn((
+                ChildOf(entity),
+                Name::new("Primitives"),
+                this.transform,
+                Visibility::default(),
+            ))
+            .id();
+        for item in &this.primitives {
+            let mut primitive = world.spawn((
+                ChildOf(primitives),
+                item.name.clone(),
+                Mesh3d(item.mesh.clone()),
+                RsmMaterials {
+                    base: item.material.clone(),
+                    inverted: item.inverted_material.clone(),
+                },
+            ));
+            if let Some(animation_target) = item.texture_animation {
+                primitive.insert((animation_target, UvTransform::default()));
+            }
+        }
     }
 
     fn size_hint(&self) -> usize {
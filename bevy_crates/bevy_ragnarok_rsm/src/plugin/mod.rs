@@ -1,15 +1,17 @@
 mod loader;
 
+pub use self::loader::AssetLoaderSettings;
+
 use bevy_animation::{AnimationPlayer, AnimationTarget, graph::AnimationGraphHandle};
-use bevy_app::PostUpdate;
-use bevy_asset::{AssetApp, AssetEventSystems};
+use bevy_app::{AnimationSystems, PostUpdate};
+use bevy_asset::{AssetApp, AssetEventSystems, Assets};
 use bevy_camera::visibility::{InheritedVisibility, ViewVisibility, Visibility};
 use bevy_ecs::{
     hierarchy::{ChildOf, Children},
     name::{Name, NameOrEntity},
-    query::{With, Without},
+    query::{Changed, With, Without},
     schedule::IntoScheduleConfigs,
-    system::{Commands, Populated},
+    system::{Commands, Populated, ResMut},
 };
 use bevy_mesh::Mesh3d;
 use bevy_pbr::MeshMaterial3d;
@@ -19,7 +21,10 @@ use bevy_transform::{
 };
 use loader::AssetLoader;
 
-use crate::{Model, RsmMaterials, assets::RsmModel, materials::RsmMaterial};
+use crate::{
+    Model, RsmMaterials, UvTransform, assets::RsmModel, materials::RsmMaterial,
+    volume_box::VolumeBox,
+};
 
 pub struct Plugin {
     pub texture_path_prefix: std::path::PathBuf,
@@ -35,6 +40,9 @@ impl bevy_app::Plugin for Plugin {
         // Materials
         app.add_plugins(crate::materials::Plugin);
 
+        // Instancing
+        app.add_plugins(crate::instancing::Plugin);
+
         // Systems
         app.add_systems(
             PostUpdate,
@@ -42,10 +50,18 @@ impl bevy_app::Plugin for Plugin {
                 .after(TransformSystems::Propagate)
                 .before(AssetEventSystems),
         );
+        app.add_systems(
+            PostUpdate,
+            update_uv_transform
+                .after(AnimationSystems)
+                .before(AssetEventSystems),
+        );
 
         // Types
         app.register_type::<Model>();
         app.register_type::<RsmMaterials>();
+        app.register_type::<UvTransform>();
+        app.register_type::<VolumeBox>();
 
         // Types needed for Scene
         app.register_type::<Name>();
@@ -79,12 +95,22 @@ fn invert_material(
     >,
 ) {
     for (rsm, rsm_materials, global_transform) in rsms.into_inner() {
-        let inverted_axis = global_transform.scale().is_negative_bitmask().count_ones();
-        let material = if inverted_axis % 2 == 0 {
-            rsm_materials.base.clone()
-        } else {
-            rsm_materials.inverted.clone()
-        };
+        let material = rsm_materials.material_for(global_transform).clone();
         commands.entity(rsm.entity).insert(MeshMaterial3d(material));
     }
 }
+
+/// Copies the animated [`UvTransform`] of primitives into their materials.
+fn update_uv_transform(
+    mut materials: ResMut<Assets<RsmMaterial>>,
+    primitives: Populated<(&UvTransform, &RsmMaterials), Changed<UvTransform>>,
+) {
+    for (uv_transform, rsm_materials) in primitives.into_inner() {
+        let matrix = uv_transform.matrix();
+        for material in [&rsm_materials.base, &rsm_materials.inverted] {
+            if let Some(material) = materials.get_mut(material) {
+                material.uv_transform = matrix;
+            }
+        }
+    }
+}
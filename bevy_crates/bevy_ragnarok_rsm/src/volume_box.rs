@@ -0,0 +1,151 @@
+//! Collision boxes of Rsm models
+
+use bevy_camera::primitives::Aabb;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Query, SystemParam},
+};
+use bevy_math::{Ray3d, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
+
+/// A [`VolumeBox`](ragnarok_rsm::VolumeBox) of a Rsm model, present on
+/// children of the root of the [`Scene`](bevy_scene::Scene) generated when
+/// loading a `.rsm`.
+///
+/// The box is centered on the entity and oriented by its [`GlobalTransform`].
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Component, Clone)]
+pub struct VolumeBox {
+    pub half_size: Vec3,
+    /// Flag of the box, only present since Rsm 1.3
+    pub flag: i32,
+}
+
+impl VolumeBox {
+    /// Bounds of the box in local space
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_min_max(-self.half_size, self.half_size)
+    }
+
+    /// Whether the local `point` is inside of the box
+    pub fn local_contains(&self, point: Vec3) -> bool {
+        point.abs().cmple(self.half_size).all()
+    }
+
+    /// Whether the world `point` is inside of the box
+    pub fn world_contains(&self, transform: &GlobalTransform, point: Vec3) -> bool {
+        self.local_contains(transform.affine().inverse().transform_point3(point))
+    }
+
+    /// Intersects a local ray with the box using the slab method.
+    ///
+    /// Returns the distance along the ray, in multiples of `direction`, of the
+    /// first intersection, which is 0 if `origin` is inside of the box.
+    pub fn local_raycast(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let inverse_direction = direction.recip();
+        let near = (-self.half_size - origin) * inverse_direction;
+        let far = (self.half_size - origin) * inverse_direction;
+
+        let enter = near.min(far).max_element().max(0.);
+        let exit = near.max(far).min_element();
+        (enter <= exit).then_some(enter)
+    }
+
+    /// Intersects a world ray with the box, see [`VolumeBox::local_raycast`]
+    pub fn world_raycast(&self, transform: &GlobalTransform, ray: Ray3d) -> Option<VolumeBoxHit> {
+        let inverse = transform.affine().inverse();
+        let distance = self.local_raycast(
+            inverse.transform_point3(ray.origin),
+            inverse.transform_vector3(*ray.direction),
+        )?;
+
+        Some(VolumeBoxHit {
+            distance,
+            position: ray.get_point(distance),
+        })
+    }
+}
+
+/// Intersection of a ray with a [`VolumeBox`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeBoxHit {
+    /// Distance from the origin of the ray
+    pub distance: f32,
+    /// World position of the hit
+    pub position: Vec3,
+}
+
+/// Queries the [`VolumeBox`]es of all spawned Rsm models
+#[derive(SystemParam)]
+pub struct VolumeBoxes<'w, 's> {
+    volume_boxes: Query<'w, 's, (Entity, &'static VolumeBox, &'static GlobalTransform)>,
+}
+
+impl VolumeBoxes<'_, '_> {
+    /// Returns the closest hit of `ray` on all [`VolumeBox`]es, along with
+    /// the entity holding the [`VolumeBox`] that was hit.
+    pub fn raycast(&self, ray: Ray3d) -> Option<(Entity, VolumeBoxHit)> {
+        self.raycast_within(ray, f32::INFINITY)
+    }
+
+    /// Same as [`VolumeBoxes::raycast`], ignoring hits further than
+    /// `max_distance`, e.g. the distance between a camera and its target.
+    pub fn raycast_within(&self, ray: Ray3d, max_distance: f32) -> Option<(Entity, VolumeBoxHit)> {
+        self.volume_boxes
+            .iter()
+            .filter_map(|(entity, volume_box, transform)| {
+                volume_box
+                    .world_raycast(transform, ray)
+                    .filter(|hit| hit.distance <= max_distance)
+                    .map(|hit| (entity, hit))
+            })
+            .min_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Entities holding a [`VolumeBox`] that contains the world `point`
+    pub fn containing(&self, point: Vec3) -> impl Iterator<Item = Entity> {
+        self.volume_boxes
+            .iter()
+            .filter(move |(_, volume_box, transform)| volume_box.world_contains(transform, point))
+            .map(|(entity, _, _)| entity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_math::{Dir3, Quat, Ray3d, Vec3};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::VolumeBox;
+
+    #[test]
+    fn raycasts_rotated_box() {
+        let volume_box = VolumeBox {
+            half_size: Vec3::new(2., 1., 1.),
+            flag: 0,
+        };
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(10., 0., 0.)
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+        );
+
+        let hit = volume_box.world_raycast(&transform, Ray3d::new(Vec3::ZERO, Dir3::X));
+        let Some(hit) = hit else {
+            unreachable!("Ray should hit the box.");
+        };
+        // Rotated, the box is 2 wide on X
+        assert!((hit.distance - 9.).abs() < 1e-5);
+        assert!((hit.position - Vec3::new(9., 0., 0.)).length() < 1e-5);
+
+        assert!(
+            volume_box
+                .world_raycast(&transform, Ray3d::new(Vec3::ZERO, Dir3::NEG_X))
+                .is_none()
+        );
+        assert!(volume_box.world_contains(&transform, Vec3::new(10.5, 0., 1.5)));
+        assert!(!volume_box.world_contains(&transform, Vec3::new(11.5, 0., 0.)));
+    }
+}
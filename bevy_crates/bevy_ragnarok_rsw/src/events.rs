@@ -2,7 +2,10 @@ use std::borrow::Cow;
 
 use bevy_asset::Handle;
 use bevy_audio::AudioSource;
-use bevy_ecs::event::Event;
+use bevy_ecs::{
+    entity::Entity,
+    event::{EntityEvent, Event},
+};
 use bevy_transform::components::Transform;
 
 #[derive(Debug, Event)]
@@ -16,11 +19,42 @@ pub struct UnloadWorld;
 #[derive(Debug, Event)]
 pub struct WorldLoaded;
 
-#[derive(Debug, Event)]
+/// Triggered each time an [`EnvironmentalSound`](crate::EnvironmentalSound)
+/// completes its cycle and should be played.
+#[derive(Debug, EntityEvent)]
 pub struct WorldSound {
+    /// The [`EnvironmentalSound`](crate::EnvironmentalSound)
+    pub entity: Entity,
     pub name: String,
     pub track: Handle<AudioSource>,
     pub position: Transform,
     pub volume: f32,
     pub range: f32,
 }
+
+/// Merges the meshes of the static props of a [`World`](crate::World)
+/// into a [`StaticPropBatch`](crate::StaticPropBatch) per region of its
+/// quad tree and material.
+///
+/// Static props have an `animation_type` of 0 and no key frames, their
+/// models must be spawned and have their meshes kept in the main world.
+#[derive(Debug, EntityEvent)]
+pub struct BatchStaticProps {
+    /// The [`World`](crate::World)
+    pub entity: Entity,
+}
+
+/// Draws the static props of a [`World`](crate::World) whose model is used
+/// at least `min_copies` times with one
+/// [`RsmInstances`](bevy_ragnarok_rsm::instancing::RsmInstances) per model.
+///
+/// Static props are the same as for [`BatchStaticProps`], props that were
+/// already batched are left out. Instanced props do not cast shadows,
+/// unlike the batched ones.
+#[derive(Debug, EntityEvent)]
+pub struct InstanceRepeatedProps {
+    /// The [`World`](crate::World)
+    pub entity: Entity,
+    /// Number of props a model needs to be instanced
+    pub min_copies: usize,
+}
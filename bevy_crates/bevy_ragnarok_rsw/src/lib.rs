@@ -7,7 +7,7 @@ use std::borrow::Cow;
 
 use bevy_asset::Handle;
 use bevy_audio::AudioSource;
-use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
 use bevy_ragnarok_water_plane::WaterPlaneAsset;
 use bevy_reflect::Reflect;
 use bevy_time::Timer;
@@ -30,6 +30,8 @@ pub struct World {
 /// Ground of the [`World`]
 pub struct Ground {
     pub ground_path: Cow<'static, str>,
+    /// Strength of the shadows of the ground's lightmap
+    pub shadow_map_alpha: f32,
 }
 
 #[derive(Debug, Component, Reflect)]
@@ -81,3 +83,12 @@ pub struct EnvironmentalEffect;
 #[reflect(Component)]
 /// The quad tree of the [`World`]
 pub struct WorldQuadTree;
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+/// Merged meshes of the static [`AnimatedProp`]s in a region of the
+/// [`WorldQuadTree`] that use the same material
+pub struct StaticPropBatch {
+    /// Quad tree node covered by the batch
+    pub region: Entity,
+}
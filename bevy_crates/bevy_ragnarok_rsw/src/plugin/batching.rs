@@ -0,0 +1,297 @@
+//! Batching of static props
+
+use bevy_asset::{AssetId, Assets, Handle, RenderAssetUsages};
+use bevy_camera::{primitives::Aabb, visibility::Visibility};
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    name::Name,
+    observer::On,
+    query::With,
+    relationship::{Relationship, RelationshipTarget},
+    system::{Commands, Query, ResMut},
+};
+use bevy_math::Vec3;
+use bevy_mesh::{Mesh, Mesh3d};
+use bevy_pbr::MeshMaterial3d;
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_ragnarok_quad_tree::{QuadTree, TrackedEntity};
+use bevy_ragnarok_rsm::{Model, RsmMaterials, batching::StaticBatch, materials::RsmMaterial};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    AnimatedProp, StaticPropBatch, World, WorldQuadTree, events::BatchStaticProps,
+    relationships::WorldOfModels,
+};
+
+/// Merges the static props of a [`World`] into [`StaticPropBatch`]es.
+///
+/// Only the merged primitives are despawned, the models and their other
+/// children, like their [`VolumeBox`](bevy_ragnarok_rsm::VolumeBox)es,
+/// stay. The merged meshes are then dropped from the main world.
+#[expect(clippy::too_many_arguments, reason = "Batching reads the whole World")]
+pub(super) fn batch_static_props(
+    event: On<BatchStaticProps>,
+    mut commands: Commands,
+    worlds: Query<&WorldOfModels, With<World>>,
+    quad_trees: Query<(Entity, &ChildOf), With<WorldQuadTree>>,
+    quad_tree_nodes: Query<(&Aabb, Option<&QuadTree>)>,
+    animated_props: Query<(&AnimatedProp, &Transform, &Children)>,
+    models: Query<(&Model, &Children)>,
+    descendants: Query<&Children>,
+    primitives: Query<(&Mesh3d, &RsmMaterials, &GlobalTransform)>,
+    global_transforms: Query<&GlobalTransform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let world = event.entity;
+    let Ok(world_of_models) = worlds.get(world) else {
+        log::error!("{world} is not a World with models.");
+        return;
+    };
+    let models_container = *world_of_models.collection();
+    let Some((quad_tree, _)) = quad_trees
+        .iter()
+        .find(|(_, child_of)| child_of.parent() == world)
+    else {
+        log::error!("{world} does not have a quad tree.");
+        return;
+    };
+    let Ok(models_container_transform) = global_transforms.get(models_container) else {
+        log::error!("Models of {world} do not have a GlobalTransform.");
+        return;
+    };
+    let world_from_models = models_container_transform.affine().inverse();
+
+    let mut batches: HashMap<(Entity, Handle<RsmMaterial>), StaticBatch> = HashMap::new();
+    let mut merged_meshes: HashSet<AssetId<Mesh>> = HashSet::new();
+    let mut batched_props = 0;
+
+    let Ok(props) = descendants.get(models_container) else {
+        log::debug!("{world} does not have props to batch.");
+        return;
+    };
+    'prop: for (animated_prop, transform, prop_children) in animated_props.iter_many(props) {
+        if animated_prop.animation_type != 0 {
+            continue;
+        }
+        let Some((model, (rsm_model, _))) = prop_children
+            .iter()
+            .find_map(|child| models.get(child).ok().map(|model| (child, model)))
+        else {
+            continue;
+        };
+        if rsm_model.animation.is_some() {
+            continue;
+        }
+
+        let mut prop_primitives = Vec::new();
+        for descendant in descendants.iter_descendants(model) {
+            let Ok((mesh, rsm_materials, global_transform)) = primitives.get(descendant) else {
+                continue;
+            };
+            let mesh_id = mesh.id();
+            let Some(mesh) = meshes.get(mesh_id) else {
+                log::warn!(
+                    "Meshes of {} are not in the main world, it can't be batched.",
+                    animated_prop.prop_path
+                );
+                continue 'prop;
+            };
+            prop_primitives.push((
+                descendant,
+                mesh_id,
+                mesh,
+                rsm_materials.material_for(global_transform).clone(),
+                world_from_models * global_transform.affine(),
+            ));
+        }
+        // Props that were already instanced don't have primitives
+        if prop_primitives.is_empty() {
+            continue;
+        }
+
+        let region = region_of(quad_tree, transform.translation, &quad_tree_nodes);
+        for (primitive, mesh_id, mesh, material, transform) in prop_primitives {
+            if batches
+                .entry((region, material))
+                .or_default()
+                .push(mesh, &transform)
+            {
+                commands.entity(primitive).despawn();
+                merged_meshes.insert(mesh_id);
+            } else {
+                log::warn!(
+                    "A primitive of {} was not a Rsm primitive.",
+                    animated_prop.prop_path
+                );
+            }
+        }
+        batched_props += 1;
+    }
+
+    // Props that were not batched keep drawing the meshes from the render world
+    for mesh_id in merged_meshes {
+        if let Some(mesh) = meshes.get_mut(mesh_id) {
+            mesh.asset_usage = RenderAssetUsages::RENDER_WORLD;
+        }
+    }
+
+    log::debug!(
+        "Batched {batched_props} static props of {world} into {} batches.",
+        batches.len()
+    );
+    for (i, ((region, material), batch)) in batches.into_iter().enumerate() {
+        let Some(aabb) = batch.aabb() else {
+            continue;
+        };
+        let mesh = meshes.add(batch.into_mesh(RenderAssetUsages::RENDER_WORLD));
+        commands.spawn((
+            Name::new(format!("StaticPropBatch{i}")),
+            StaticPropBatch { region },
+            // Culled along with its region, batches are not moved by the quad tree
+            <TrackedEntity as Relationship>::from(region),
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            aabb,
+            Transform::default(),
+            Visibility::default(),
+            ChildOf(models_container),
+        ));
+    }
+}
+
+/// Smallest node of the quad tree that contains `point` on XZ
+fn region_of(
+    quad_tree: Entity,
+    point: Vec3,
+    quad_tree_nodes: &Query<(&Aabb, Option<&QuadTree>)>,
+) -> Entity {
+    let mut region = quad_tree;
+    while let Ok((_, Some(nodes))) = quad_tree_nodes.get(region) {
+        let Some(node) = nodes.iter().find(|node| {
+            quad_tree_nodes.get(*node).is_ok_and(|(aabb, _)| {
+                let min = aabb.min();
+                let max = aabb.max();
+                (min.x..=max.x).contains(&point.x) && (min.z..=max.z).contains(&point.z)
+            })
+        }) else {
+            break;
+        };
+        region = node;
+    }
+    region
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::{Assets, Handle, RenderAssetUsages};
+    use bevy_camera::primitives::Aabb;
+    use bevy_ecs::{
+        hierarchy::ChildOf,
+        query::With,
+        relationship::Relationship,
+        system::{Query, RunSystemOnce},
+        world::World as EcsWorld,
+    };
+    use bevy_math::{Dir3, Ray3d, Vec3};
+    use bevy_mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology};
+    use bevy_ragnarok_rsm::{
+        Model, RsmMaterials,
+        volume_box::{VolumeBox, VolumeBoxes},
+    };
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::batch_static_props;
+    use crate::{
+        AnimatedProp, StaticPropBatch, World, WorldQuadTree, events::BatchStaticProps,
+        relationships::ModelsOfWorld,
+    };
+
+    fn triangle() -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                vec![[0., 0., 0.], [1., 0., 0.], [0., 0., 1.]],
+            )
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 1., 0.]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0., 0.]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![[1.; 4]; 3])
+            .with_inserted_indices(Indices::U16(vec![0, 1, 2]))
+    }
+
+    #[test]
+    fn keeps_volume_boxes() {
+        let mut ecs = EcsWorld::new();
+        ecs.init_resource::<Assets<Mesh>>();
+        let mesh = ecs.resource_mut::<Assets<Mesh>>().add(triangle());
+        ecs.add_observer(batch_static_props);
+
+        let world = ecs.spawn(World { water_plane: None }).id();
+        ecs.spawn((
+            WorldQuadTree,
+            Aabb::from_min_max(Vec3::splat(-100.), Vec3::splat(100.)),
+            ChildOf(world),
+        ));
+        let models = ecs
+            .spawn((
+                <ModelsOfWorld as Relationship>::from(world),
+                GlobalTransform::IDENTITY,
+                ChildOf(world),
+            ))
+            .id();
+        let prop = ecs
+            .spawn((
+                AnimatedProp::default(),
+                Transform::default(),
+                ChildOf(models),
+            ))
+            .id();
+        let model = ecs.spawn((Model::default(), ChildOf(prop))).id();
+        let primitive = ecs
+            .spawn((
+                Mesh3d(mesh.clone()),
+                RsmMaterials {
+                    base: Handle::default(),
+                    inverted: Handle::default(),
+                },
+                GlobalTransform::IDENTITY,
+                ChildOf(model),
+            ))
+            .id();
+        let volume_box = ecs
+            .spawn((
+                VolumeBox {
+                    half_size: Vec3::ONE,
+                    flag: 0,
+                },
+                GlobalTransform::from_xyz(5., 0., 0.),
+                ChildOf(model),
+            ))
+            .id();
+
+        ecs.trigger(BatchStaticProps { entity: world });
+        ecs.flush();
+
+        assert!(ecs.get_entity(primitive).is_err());
+        assert!(ecs.get_entity(model).is_ok());
+        let Ok(batches) =
+            ecs.run_system_once(|batches: Query<(), With<StaticPropBatch>>| batches.count())
+        else {
+            unreachable!("System is valid.");
+        };
+        assert_eq!(batches, 1);
+
+        let Ok(hit) = ecs.run_system_once(|volume_boxes: VolumeBoxes| {
+            volume_boxes.raycast(Ray3d::new(Vec3::ZERO, Dir3::X))
+        }) else {
+            unreachable!("System is valid.");
+        };
+        assert_eq!(hit.map(|(entity, _)| entity), Some(volume_box));
+
+        // The merged mesh is only kept for the render world
+        let Some(mesh) = ecs.resource::<Assets<Mesh>>().get(&mesh) else {
+            unreachable!("Mesh is only dropped once extracted.");
+        };
+        assert_eq!(mesh.asset_usage, RenderAssetUsages::RENDER_WORLD);
+    }
+}
@@ -0,0 +1,108 @@
+//! Instancing of repeated static props
+
+use bevy_asset::AssetServer;
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    name::Name,
+    observer::On,
+    query::With,
+    relationship::RelationshipTarget,
+    system::{Commands, Query, Res},
+};
+use bevy_mesh::Mesh3d;
+use bevy_platform::collections::HashMap;
+use bevy_ragnarok_rsm::{Model, instancing::RsmInstances};
+use bevy_transform::components::Transform;
+
+use crate::{
+    AnimatedProp, World, events::InstanceRepeatedProps, plugin::ModelPathPrefix,
+    relationships::WorldOfModels,
+};
+
+/// Draws the static props of a [`World`] whose model is repeated enough
+/// times with a [`RsmInstances`] per model.
+///
+/// Only the primitives of the props are despawned, the models and their
+/// other children, like their [`VolumeBox`](bevy_ragnarok_rsm::volume_box::VolumeBox)es,
+/// stay.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "Instancing reads the whole World"
+)]
+pub(super) fn instance_repeated_props(
+    event: On<InstanceRepeatedProps>,
+    mut commands: Commands,
+    worlds: Query<&WorldOfModels, With<World>>,
+    animated_props: Query<(&AnimatedProp, &Transform, &Children)>,
+    models: Query<&Model>,
+    descendants: Query<&Children>,
+    primitives: Query<(), With<Mesh3d>>,
+    asset_server: Res<AssetServer>,
+    model_path_prefix: Res<ModelPathPrefix>,
+) {
+    let world = event.entity;
+    let Ok(world_of_models) = worlds.get(world) else {
+        log::error!("{world} is not a World with models.");
+        return;
+    };
+    let models_container = *world_of_models.collection();
+    let Ok(props) = descendants.get(models_container) else {
+        log::debug!("{world} does not have props to instance.");
+        return;
+    };
+
+    // Transforms and primitives of the static props, by model
+    let mut static_props: HashMap<&str, Vec<(Transform, Vec<Entity>)>> = HashMap::new();
+    for (animated_prop, transform, prop_children) in animated_props.iter_many(props) {
+        if animated_prop.animation_type != 0 {
+            continue;
+        }
+        let Some(model) = prop_children.iter().find(|child| {
+            models
+                .get(*child)
+                .is_ok_and(|model| model.animation.is_none())
+        }) else {
+            continue;
+        };
+
+        let prop_primitives = descendants
+            .iter_descendants(model)
+            .filter(|descendant| primitives.contains(*descendant))
+            .collect::<Vec<_>>();
+        // Props that were already batched or instanced don't have primitives
+        if prop_primitives.is_empty() {
+            continue;
+        }
+        static_props
+            .entry(&animated_prop.prop_path)
+            .or_default()
+            .push((*transform, prop_primitives));
+    }
+
+    let mut instanced_props = 0;
+    for (prop_path, props) in static_props {
+        if props.len() < event.min_copies {
+            continue;
+        }
+        instanced_props += props.len();
+
+        let mut transforms = Vec::with_capacity(props.len());
+        for (transform, prop_primitives) in props {
+            for primitive in prop_primitives {
+                commands.entity(primitive).despawn();
+            }
+            transforms.push(transform);
+        }
+        commands.spawn((
+            Name::new(format!("Instances of {prop_path}")),
+            RsmInstances {
+                model: asset_server.load(model_path_prefix.0.join(prop_path)),
+                transforms,
+            },
+            ChildOf(models_container),
+        ));
+    }
+
+    log::debug!("Instanced {instanced_props} static props of {world}.");
+}
@@ -13,7 +13,7 @@ use bevy_ecs::{
 };
 use bevy_light::{AmbientLight, DirectionalLight, PointLight};
 use bevy_math::{EulerRot, Quat, Vec3, Vec3A};
-use bevy_ragnarok_quad_tree::QuadTreeNode;
+use bevy_ragnarok_quad_tree::{QuadTreeNode, TrackEntity};
 use bevy_ragnarok_water_plane::WaterPlaneAsset;
 use bevy_scene::Scene;
 use bevy_time::Timer;
@@ -184,6 +184,7 @@ impl AssetLoader {
                 Name::new(rsw.gnd_file.to_string()),
                 Ground {
                     ground_path: Cow::Owned(rsw.gnd_file.to_string()),
+                    shadow_map_alpha: rsw.lighting_parameters.shadow_map_alpha,
                 },
                 ChildOf(rsw_world),
                 <GroundOfWorld as Relationship>::from(rsw_world),
@@ -435,6 +436,8 @@ impl SpawnableList<ChildOf> for ModelSpawner {
                 item.animated_prop.clone(),
                 item.name.clone(),
                 item.transform,
+                Visibility::default(),
+                TrackEntity,
             ));
         }
     }
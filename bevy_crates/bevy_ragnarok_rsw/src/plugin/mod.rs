@@ -1,14 +1,24 @@
+mod batching;
+mod instancing;
 // #[cfg(feature = "debug")]
 // mod debug;
 mod loader;
 
 use std::path::PathBuf;
 
+use bevy_app::Update;
 use bevy_asset::AssetApp;
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    entity::Entity,
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_time::Time;
 
 use crate::{
     AnimatedProp, DiffuseLight, EnvironmentalEffect, EnvironmentalLight, EnvironmentalSound,
-    assets::RswAsset,
+    StaticPropBatch, assets::RswAsset, events::WorldSound,
 };
 
 use self::loader::AssetLoader;
@@ -36,12 +46,51 @@ impl bevy_app::Plugin for Plugin {
             .register_type::<EnvironmentalLight>()
             .register_type::<EnvironmentalEffect>()
             .register_type::<EnvironmentalSound>()
+            .register_type::<StaticPropBatch>()
             // Register AssetLoader
             .register_asset_loader(AssetLoader {
                 sound_path_prefix: self.sound_path_prefix.clone(),
             });
 
+        app.insert_resource(ModelPathPrefix(self.model_path_prefix.clone()));
+
+        // Observers
+        app.add_observer(batching::batch_static_props);
+        app.add_observer(instancing::instance_repeated_props);
+
+        // Systems
+        app.add_systems(Update, cycle_environmental_sounds);
+
         // #[cfg(feature = "debug")]
         // app.add_plugins(debug::Plugin);
     }
 }
+
+/// Prefix for .rsm files, see [`Plugin::model_path_prefix`]
+#[derive(Resource)]
+struct ModelPathPrefix(PathBuf);
+
+/// Plays environmental sounds once when spawned and then every time
+/// their cycle completes
+fn cycle_environmental_sounds(
+    mut commands: Commands,
+    mut sounds: Query<(Entity, &mut EnvironmentalSound)>,
+    time: Res<Time>,
+) {
+    for (entity, mut sound) in sounds.iter_mut() {
+        let just_added = sound.is_added();
+        let sound = sound.bypass_change_detection();
+        sound.cycle.tick(time.delta());
+
+        if just_added || sound.cycle.just_finished() {
+            commands.trigger(WorldSound {
+                entity,
+                name: sound.name.clone(),
+                track: sound.source.clone(),
+                position: sound.position,
+                volume: sound.volume,
+                range: sound.range,
+            });
+        }
+    }
+}
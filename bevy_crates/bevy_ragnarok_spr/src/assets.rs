@@ -1,10 +1,44 @@
 use bevy_asset::{Asset, Handle};
 use bevy_image::Image;
+use bevy_mesh::MeshTag;
 use bevy_reflect::Reflect;
+use bevy_render::storage::ShaderStorageBuffer;
 
 #[derive(Debug, Asset, Reflect)]
 pub struct SpriteImages {
-    pub indexed_sprites: Vec<Handle<Image>>,
-    pub true_color_sprites: Vec<Handle<Image>>,
+    pub indexed_sprites: SpriteFrames,
+    pub true_color_sprites: SpriteFrames,
     pub palette: Handle<Image>,
 }
+
+/// Frames of a [`Spr`](ragnarok_spr::Spr) of a single kind
+///
+/// A mesh draws a frame with the image of the frame in its material and
+/// the index of the frame in its [`MeshTag`], so meshes drawing different
+/// frames of a packed image can share the same material.
+#[derive(Debug, Default, Reflect)]
+pub struct SpriteFrames {
+    /// Image of each frame, frames packed together share the same image
+    pub images: Vec<Handle<Image>>,
+    /// [`SprFrame`](crate::material::SprFrame) of each frame
+    pub frames: Handle<ShaderStorageBuffer>,
+}
+
+impl SpriteFrames {
+    /// Number of frames
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Returns `true` if there are no frames
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Image and [`MeshTag`] that must be used to draw the frame at `index`
+    pub fn get(&self, index: usize) -> Option<(Handle<Image>, MeshTag)> {
+        self.images
+            .get(index)
+            .map(|image| (image.clone(), MeshTag(index as u32)))
+    }
+}
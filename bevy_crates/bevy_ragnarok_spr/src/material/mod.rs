@@ -1,6 +1,7 @@
 use bevy_asset::{Asset, AssetApp, Handle, embedded_asset};
 use bevy_color::LinearRgba;
 use bevy_image::Image;
+use bevy_math::Vec2;
 use bevy_mesh::MeshVertexBufferLayoutRef;
 use bevy_pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
 use bevy_reflect::Reflect;
@@ -9,6 +10,7 @@ use bevy_render::{
     render_resource::{
         AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
     },
+    storage::ShaderStorageBuffer,
 };
 use bevy_shader::ShaderRef;
 
@@ -39,14 +41,28 @@ pub struct SprUniform {
     pub tint: LinearRgba,
 }
 
+/// Where a frame of a [`SpriteFrames`](crate::assets::SpriteFrames) is on its image,
+/// the materials read the one at the [`MeshTag`](bevy_mesh::MeshTag) of the mesh
+#[derive(Debug, Clone, Copy, Reflect, ShaderType)]
+pub struct SprFrame {
+    /// Layer of the array texture that contains the frame
+    pub layer: u32,
+    /// Top left of the frame on the layer
+    pub uv_min: Vec2,
+    /// Bottom right of the frame on the layer
+    pub uv_max: Vec2,
+}
+
 #[derive(Clone, Asset, Reflect, AsBindGroup)]
 pub struct SprIndexedMaterial {
     #[uniform(0)]
     pub uniform: SprUniform,
-    #[texture(1, sample_type = "u_int")]
+    #[texture(1, sample_type = "u_int", dimension = "2d_array")]
     pub index_image: Handle<Image>,
     #[texture(2, dimension = "1d")]
     pub palette: Handle<Image>,
+    #[storage(3, read_only)]
+    pub frames: Handle<ShaderStorageBuffer>,
 }
 
 impl Material for SprIndexedMaterial {
@@ -95,9 +111,11 @@ impl Material for SprIndexedMaterial {
 pub struct SprTrueColorMaterial {
     #[uniform(0)]
     pub uniform: SprUniform,
-    #[texture(1)]
+    #[texture(1, dimension = "2d_array")]
     #[sampler(2)]
     pub color: Handle<Image>,
+    #[storage(3, read_only)]
+    pub frames: Handle<ShaderStorageBuffer>,
 }
 
 impl Material for SprTrueColorMaterial {
@@ -106,19 +124,19 @@ impl Material for SprTrueColorMaterial {
     }
 
     fn vertex_shader() -> ShaderRef {
-        "embedded://bevy_ragnarok_spr/assets/shaders/spr_vertex.wgsl".into()
+        "embedded://bevy_ragnarok_spr/material/shaders/spr_vertex.wgsl".into()
     }
 
     fn prepass_vertex_shader() -> ShaderRef {
-        "embedded://bevy_ragnarok_spr/assets/shaders/spr_vertex.wgsl".into()
+        "embedded://bevy_ragnarok_spr/material/shaders/spr_vertex.wgsl".into()
     }
 
     fn fragment_shader() -> ShaderRef {
-        "embedded://bevy_ragnarok_spr/assets/shaders/spr_fragment.wgsl".into()
+        "embedded://bevy_ragnarok_spr/material/shaders/spr_fragment.wgsl".into()
     }
 
     fn prepass_fragment_shader() -> ShaderRef {
-        "embedded://bevy_ragnarok_spr/assets/shaders/spr_prepass_fragment.wgsl".into()
+        "embedded://bevy_ragnarok_spr/material/shaders/spr_prepass_fragment.wgsl".into()
     }
 
     fn specialize(
@@ -1,4 +1,5 @@
 #import bevy_pbr::{
+    mesh_functions,
     pbr_fragment::pbr_input_from_vertex_output,
     pbr_functions::alpha_discard,
     pbr_types::{PbrInput, STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND},
@@ -22,16 +23,24 @@ struct SprUniform {
     tint: vec4<f32>,
 }
 
+struct SprFrame {
+    layer: u32,
+    uv_min: vec2<f32>,
+    uv_max: vec2<f32>,
+}
+
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> spr_uniform: SprUniform;
 
 #ifdef SPR_INDEXED_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<u32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<u32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_palette: texture_1d<f32>;
 #else ifdef SPR_TRUE_COLOR_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_sampler: sampler;
 #endif
 
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<storage> spr_frames: array<SprFrame>;
+
 fn spr_default_material(in: VertexOutput, is_front: bool) -> PbrInput {
     var pbr_input = pbr_input_from_vertex_output(in, is_front, false);
 
@@ -47,11 +56,12 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     var pbr_input = spr_default_material(in, is_front);
+    let spr_frame = spr_frames[mesh_functions::get_tag(in.instance_index)];
 
 #ifdef SPR_INDEXED_PIPELINE
     let index_texture_dimensions = textureDimensions(spr_texture);
     let index_texture_coords = vec2<u32>(vec2<f32>(index_texture_dimensions) * in.uv);
-    let index = textureLoad(spr_texture, index_texture_coords, 0).x;
+    let index = textureLoad(spr_texture, index_texture_coords, spr_frame.layer, 0).x;
 
     if index == 0 {
         discard;
@@ -65,7 +75,7 @@ fn fragment(
         pbr_input.material.base_color.a = 1.;
     }
 #else ifdef SPR_TRUE_COLOR_PIPELINE
-    pbr_input.material.base_color = textureSample(spr_texture, spr_sampler, in.uv);
+    pbr_input.material.base_color = textureSample(spr_texture, spr_sampler, in.uv, spr_frame.layer);
 #endif
 
     pbr_input.material.base_color = pbr_input.material.base_color * spr_uniform.tint;
@@ -1,4 +1,7 @@
-#import bevy_pbr::prepass_io::VertexOutput
+#import bevy_pbr::{
+    mesh_functions,
+    prepass_io::VertexOutput,
+}
 #ifdef PREPASS_FRAGMENT
 #import bevy_pbr::prepass_io::FragmentOutput
 #endif
@@ -8,16 +11,24 @@ struct SprUniform {
     tint: vec4<f32>,
 }
 
+struct SprFrame {
+    layer: u32,
+    uv_min: vec2<f32>,
+    uv_max: vec2<f32>,
+}
+
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> spr_uniform: SprUniform;
 
 #ifdef SPR_INDEXED_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<u32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<u32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_palette: texture_1d<f32>;
 #else ifdef SPR_TRUE_COLOR_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_sampler: sampler;
 #endif
 
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<storage> spr_frames: array<SprFrame>;
+
 @fragment
 fn fragment(
     in: VertexOutput,
@@ -26,11 +37,12 @@ fn fragment(
 #else 
 ) {
 #endif
+    let spr_frame = spr_frames[mesh_functions::get_tag(in.instance_index)];
     var color: vec4<f32>;
 #ifdef SPR_INDEXED_PIPELINE
     let index_texture_dimensions = textureDimensions(spr_texture);
     let index_texture_coords = vec2<u32>(vec2<f32>(index_texture_dimensions) * in.uv);
-    let index = textureLoad(spr_texture, index_texture_coords, 0).x;
+    let index = textureLoad(spr_texture, index_texture_coords, spr_frame.layer, 0).x;
 
     if index == 0 {
         discard;
@@ -42,7 +54,7 @@ fn fragment(
         discard;
     }
 #else ifdef SPR_TRUE_COLOR_PIPELINE
-    color = textureSample(spr_texture, spr_sampler, in.uv);
+    color = textureSample(spr_texture, spr_sampler, in.uv, spr_frame.layer);
 #endif
 
 #ifdef PREPASS_FRAGMENT
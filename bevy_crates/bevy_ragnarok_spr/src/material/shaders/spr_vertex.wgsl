@@ -18,21 +18,32 @@ struct SprUniform {
     tint: vec4<f32>,
 }
 
+struct SprFrame {
+    layer: u32,
+    uv_min: vec2<f32>,
+    uv_max: vec2<f32>,
+}
+
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> spr_uniform: SprUniform;
 
 #ifdef SPR_INDEXED_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<u32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<u32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_palette: texture_1d<f32>;
 #else ifdef SPR_TRUE_COLOR_PIPELINE
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var spr_texture: texture_2d_array<f32>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(2) var spr_sampler: sampler;
 #endif
 
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<storage> spr_frames: array<SprFrame>;
+
 @vertex
 fn vertex(in: Vertex) -> VertexOutput {
     var vertex_output: VertexOutput;
 
-    var dimensions = vec2<f32>(textureDimensions(spr_texture));
+    vertex_output.instance_index = in.instance_index;
+    let spr_frame = spr_frames[mesh_functions::get_tag(in.instance_index)];
+    let frame_extent = spr_frame.uv_max - spr_frame.uv_min;
+    var dimensions = vec2<f32>(textureDimensions(spr_texture)) * frame_extent;
     let position = vec4<f32>(
         in.position.xy * dimensions,
         0.0,
@@ -57,11 +68,11 @@ fn vertex(in: Vertex) -> VertexOutput {
     );
 #endif
 #endif
+    var uv = in.uv;
     if spr_uniform.uv_flip == 1u {
-        vertex_output.uv = vec2<f32>(1. - in.uv.x, in.uv.y);
-    } else {
-        vertex_output.uv = in.uv;
+        uv = vec2<f32>(1. - in.uv.x, in.uv.y);
     }
+    vertex_output.uv = spr_frame.uv_min + uv * frame_extent;
 
     return vertex_output;
 }
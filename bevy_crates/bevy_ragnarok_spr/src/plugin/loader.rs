@@ -1,31 +1,71 @@
+use std::cmp::Reverse;
+
 use bevy_asset::{Handle, LoadContext, RenderAssetUsages};
 use bevy_image::{Image, ImageSampler};
+use bevy_math::Vec2;
 use bevy_ragnarok_pal::pal_to_image;
-use bevy_render::render_resource::{
-    Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+use bevy_render::{
+    render_resource::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureViewDescriptor, TextureViewDimension,
+    },
+    storage::ShaderStorageBuffer,
 };
 use ragnarok_spr::{Error, IndexedSprite, Spr, TrueColorSprite};
+use serde::{Deserialize, Serialize};
 
-use crate::assets::SpriteImages;
+use crate::{
+    assets::{SpriteFrames, SpriteImages},
+    material::SprFrame,
+};
 
+/// How the frames of a [`Spr`] are stored
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FramePacking {
+    /// One [`Image`] per frame
+    #[default]
+    Separate,
+    /// All frames of the same kind in the layers of a single `D2Array` [`Image`],
+    /// each frame at the top left of its layer
+    TextureArray,
+    /// All frames of the same kind side by side in a single [`Image`]
+    Atlas,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AssetLoaderSettings {
+    pub frame_packing: FramePacking,
+}
+
+/// Asset loader for [`SpriteImages`].
+///
+/// ## Labeled assets
+///
+/// * `IndexedSprite{n}`: [`Image`] = Indexed frame `n`, when using [`FramePacking::Separate`].
+/// * `TrueColorSprite{n}`: [`Image`] = True color frame `n`, when using [`FramePacking::Separate`].
+/// * `IndexedSprites`: [`Image`] = All indexed frames, when packing frames.
+/// * `TrueColorSprites`: [`Image`] = All true color frames, when packing frames.
+/// * `IndexedFrames`: [`ShaderStorageBuffer`] = [`SprFrame`] of each indexed frame.
+/// * `TrueColorFrames`: [`ShaderStorageBuffer`] = [`SprFrame`] of each true color frame.
+/// * `Palette`: [`Image`] = Palette of the indexed frames.
 pub struct AssetLoader;
 
 impl bevy_asset::AssetLoader for AssetLoader {
     type Asset = SpriteImages;
-    type Settings = ();
+    type Settings = AssetLoaderSettings;
     type Error = Error;
 
     async fn load(
         &self,
         reader: &mut dyn bevy_asset::io::Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut data: Vec<u8> = vec![];
         reader.read_to_end(&mut data).await?;
         let sprite = Spr::from_reader(&mut data.as_slice())?;
 
-        Ok(Self::generate_sprite(load_context, sprite))
+        Ok(Self::generate_sprite(load_context, sprite, settings))
     }
 
     fn extensions(&self) -> &[&str] {
@@ -33,11 +73,26 @@ impl bevy_asset::AssetLoader for AssetLoader {
     }
 }
 
+/// Pixel data of a single frame before being turned into an [`Image`]
+struct Frame {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
 impl AssetLoader {
-    fn generate_sprite(load_context: &mut LoadContext, sprite: Spr) -> SpriteImages {
-        let indexed_sprites = Self::load_indexed_sprites(load_context, &sprite.bitmap_images);
-        let true_color_sprites =
-            Self::load_true_color_sprites(load_context, &sprite.true_color_images);
+    fn generate_sprite(
+        load_context: &mut LoadContext,
+        sprite: Spr,
+        settings: &AssetLoaderSettings,
+    ) -> SpriteImages {
+        let indexed_sprites =
+            Self::load_indexed_sprites(load_context, &sprite.bitmap_images, settings.frame_packing);
+        let true_color_sprites = Self::load_true_color_sprites(
+            load_context,
+            &sprite.true_color_images,
+            settings.frame_packing,
+        );
         let palette =
             load_context.add_labeled_asset("Palette".to_owned(), pal_to_image(sprite.palette));
 
@@ -51,84 +106,301 @@ impl AssetLoader {
     fn load_indexed_sprites(
         load_context: &mut LoadContext,
         indexed_sprites: &[IndexedSprite],
-    ) -> Vec<Handle<Image>> {
-        indexed_sprites
+        frame_packing: FramePacking,
+    ) -> SpriteFrames {
+        let frames = indexed_sprites
             .iter()
-            .enumerate()
-            .map(|(index, sprite)| {
-                let image = Image {
-                    data: Some(sprite.indexes.to_vec()),
-                    texture_descriptor: TextureDescriptor {
-                        label: Some("indexed_sprite"),
-                        size: Extent3d {
-                            width: u32::from(sprite.width),
-                            height: u32::from(sprite.height),
-                            depth_or_array_layers: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: 1,
-                        dimension: TextureDimension::D2,
-                        format: TextureFormat::R8Uint,
-                        usage: TextureUsages::TEXTURE_BINDING,
-                        view_formats: &[],
-                    },
-                    sampler: ImageSampler::Default,
-                    texture_view_descriptor: None,
-                    asset_usage: if cfg!(feature = "debug") {
-                        RenderAssetUsages::all()
-                    } else {
-                        RenderAssetUsages::RENDER_WORLD
-                    },
-                    ..Default::default()
-                };
-
-                load_context.add_labeled_asset(format!("IndexedSprite{}", index), image)
+            .map(|sprite| Frame {
+                width: u32::from(sprite.width),
+                height: u32::from(sprite.height),
+                data: sprite.indexes.to_vec(),
             })
-            .collect()
+            .collect::<Vec<_>>();
+
+        Self::sprite_frames(
+            load_context,
+            &frames,
+            "Indexed",
+            TextureFormat::R8Uint,
+            frame_packing,
+        )
     }
 
     fn load_true_color_sprites(
         load_context: &mut LoadContext,
         true_color_sprites: &[TrueColorSprite],
-    ) -> Vec<Handle<Image>> {
-        true_color_sprites
+        frame_packing: FramePacking,
+    ) -> SpriteFrames {
+        let frames = true_color_sprites
+            .iter()
+            .map(|sprite| Frame {
+                width: u32::from(sprite.width),
+                height: u32::from(sprite.height),
+                data: sprite
+                    .pixels
+                    .iter()
+                    .flat_map(|pixel| [pixel.red, pixel.green, pixel.blue, pixel.alpha])
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        Self::sprite_frames(
+            load_context,
+            &frames,
+            "TrueColor",
+            TextureFormat::Rgba8UnormSrgb,
+            frame_packing,
+        )
+    }
+
+    fn sprite_frames(
+        load_context: &mut LoadContext,
+        frames: &[Frame],
+        kind: &str,
+        format: TextureFormat,
+        frame_packing: FramePacking,
+    ) -> SpriteFrames {
+        if frames.is_empty() {
+            // Neither images nor storage buffers can be empty
+            return SpriteFrames::default();
+        }
+
+        let (images, spr_frames) = match frame_packing {
+            FramePacking::Separate => Self::separate_frames(load_context, frames, kind, format),
+            FramePacking::TextureArray => {
+                Self::texture_array_frames(load_context, frames, kind, format)
+            }
+            FramePacking::Atlas => Self::atlas_frames(load_context, frames, kind, format),
+        };
+
+        let mut buffer = ShaderStorageBuffer::new(&[], RenderAssetUsages::RENDER_WORLD);
+        buffer.set_data(spr_frames);
+
+        SpriteFrames {
+            images,
+            frames: load_context.add_labeled_asset(format!("{}Frames", kind), buffer),
+        }
+    }
+
+    fn separate_frames(
+        load_context: &mut LoadContext,
+        frames: &[Frame],
+        kind: &str,
+        format: TextureFormat,
+    ) -> (Vec<Handle<Image>>, Vec<SprFrame>) {
+        frames
             .iter()
             .enumerate()
-            .map(|(index, sprite)| {
-                let image = Image {
-                    data: Some(
-                        sprite
-                            .pixels
-                            .iter()
-                            .flat_map(|pixel| [pixel.red, pixel.green, pixel.blue, pixel.alpha])
-                            .collect(),
-                    ),
-                    texture_descriptor: TextureDescriptor {
-                        label: Some("true_color_sprite"),
-                        size: Extent3d {
-                            width: u32::from(sprite.width),
-                            height: u32::from(sprite.height),
-                            depth_or_array_layers: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: 1,
-                        dimension: TextureDimension::D2,
-                        format: TextureFormat::Rgba8UnormSrgb,
-                        usage: TextureUsages::TEXTURE_BINDING,
-                        view_formats: &[],
-                    },
-                    sampler: ImageSampler::Default,
-                    texture_view_descriptor: None,
-                    asset_usage: if cfg!(feature = "debug") {
-                        RenderAssetUsages::all()
-                    } else {
-                        RenderAssetUsages::RENDER_WORLD
-                    },
-                    ..Default::default()
+            .map(|(index, frame)| {
+                let image = load_context.add_labeled_asset(
+                    format!("{}Sprite{}", kind, index),
+                    Self::frame_image(frame.data.clone(), frame.width, frame.height, 1, format),
+                );
+                let spr_frame = SprFrame {
+                    layer: 0,
+                    uv_min: Vec2::ZERO,
+                    uv_max: Vec2::ONE,
                 };
-
-                load_context.add_labeled_asset(format!("TrueColorSprite{}", index), image)
+                (image, spr_frame)
             })
-            .collect()
+            .unzip()
+    }
+
+    fn texture_array_frames(
+        load_context: &mut LoadContext,
+        frames: &[Frame],
+        kind: &str,
+        format: TextureFormat,
+    ) -> (Vec<Handle<Image>>, Vec<SprFrame>) {
+        let pixel_size = Self::pixel_size(format);
+
+        let layer_width = frames.iter().map(|frame| frame.width).fold(1, u32::max);
+        let layer_height = frames.iter().map(|frame| frame.height).fold(1, u32::max);
+        let layer_stride = layer_width as usize * pixel_size;
+        let layer_size = layer_stride * layer_height as usize;
+
+        let mut data = vec![0; layer_size * frames.len()];
+        let mut spr_frames = Vec::with_capacity(frames.len());
+        for (layer, frame) in frames.iter().enumerate() {
+            Self::copy_frame(
+                &mut data[(layer * layer_size)..],
+                layer_stride,
+                frame,
+                pixel_size,
+            );
+
+            spr_frames.push(SprFrame {
+                layer: layer as u32,
+                uv_min: Vec2::ZERO,
+                uv_max: Vec2::new(
+                    frame.width as f32 / layer_width as f32,
+                    frame.height as f32 / layer_height as f32,
+                ),
+            });
+        }
+
+        let image = Self::frame_image(data, layer_width, layer_height, frames.len() as u32, format);
+        let image = load_context.add_labeled_asset(format!("{}Sprites", kind), image);
+
+        (vec![image; frames.len()], spr_frames)
+    }
+
+    fn atlas_frames(
+        load_context: &mut LoadContext,
+        frames: &[Frame],
+        kind: &str,
+        format: TextureFormat,
+    ) -> (Vec<Handle<Image>>, Vec<SprFrame>) {
+        let pixel_size = Self::pixel_size(format);
+
+        let (width, height, positions) = Self::shelf_pack(frames);
+
+        let stride = width as usize * pixel_size;
+        let mut data = vec![0; stride * height as usize];
+        let mut spr_frames = Vec::with_capacity(frames.len());
+        for (frame, &(x, y)) in frames.iter().zip(&positions) {
+            Self::copy_frame(
+                &mut data[(y as usize * stride + x as usize * pixel_size)..],
+                stride,
+                frame,
+                pixel_size,
+            );
+
+            spr_frames.push(SprFrame {
+                layer: 0,
+                uv_min: Vec2::new(x as f32 / width as f32, y as f32 / height as f32),
+                uv_max: Vec2::new(
+                    (x + frame.width) as f32 / width as f32,
+                    (y + frame.height) as f32 / height as f32,
+                ),
+            });
+        }
+
+        let image = Self::frame_image(data, width, height, 1, format);
+        let image = load_context.add_labeled_asset(format!("{}Sprites", kind), image);
+
+        (vec![image; frames.len()], spr_frames)
+    }
+
+    /// Width and height of an atlas holding `frames`, and the top left of each frame on it
+    ///
+    /// Frames are put on shelves from the tallest to the shortest, with a pixel
+    /// between them so that sampling a frame does not bleed into its neighbours.
+    fn shelf_pack(frames: &[Frame]) -> (u32, u32, Vec<(u32, u32)>) {
+        let area = frames
+            .iter()
+            .map(|frame| (frame.width + 1) * (frame.height + 1))
+            .sum::<u32>();
+        let width = frames
+            .iter()
+            .map(|frame| frame.width + 1)
+            .fold((area as f32).sqrt().ceil() as u32, u32::max);
+
+        let mut order = (0..frames.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| Reverse(frames[index].height));
+
+        let mut positions = vec![(0, 0); frames.len()];
+        let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+        for index in order {
+            let frame = &frames[index];
+            if x + frame.width > width {
+                x = 0;
+                y += shelf_height + 1;
+                shelf_height = 0;
+            }
+            positions[index] = (x, y);
+            x += frame.width + 1;
+            shelf_height = shelf_height.max(frame.height);
+        }
+
+        (width, (y + shelf_height).max(1), positions)
+    }
+
+    /// Copies the rows of `frame` into `data`, whose rows are `stride` bytes long
+    fn copy_frame(data: &mut [u8], stride: usize, frame: &Frame, pixel_size: usize) {
+        let frame_stride = frame.width as usize * pixel_size;
+        if frame_stride != 0 {
+            for (row, pixels) in frame.data.chunks_exact(frame_stride).enumerate() {
+                let start = row * stride;
+                data[start..(start + frame_stride)].copy_from_slice(pixels);
+            }
+        }
+    }
+
+    fn pixel_size(format: TextureFormat) -> usize {
+        let Some(pixel_size) = format.block_copy_size(None) else {
+            unreachable!("Sprite formats are not compressed.");
+        };
+        pixel_size as usize
+    }
+
+    fn frame_image(
+        data: Vec<u8>,
+        width: u32,
+        height: u32,
+        layers: u32,
+        format: TextureFormat,
+    ) -> Image {
+        Image {
+            data: Some(data),
+            texture_descriptor: TextureDescriptor {
+                label: Some("sprite_frames"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: layers,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            sampler: ImageSampler::Default,
+            // Materials always sample frames from an array, even if
+            // it has a single layer
+            texture_view_descriptor: Some(TextureViewDescriptor {
+                dimension: Some(TextureViewDimension::D2Array),
+                ..Default::default()
+            }),
+            asset_usage: if cfg!(feature = "debug") {
+                RenderAssetUsages::all()
+            } else {
+                RenderAssetUsages::RENDER_WORLD
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AssetLoader, Frame};
+
+    #[test]
+    fn shelf_pack() {
+        let frames = [(3, 2), (1, 4), (5, 1), (0, 0), (2, 2)].map(|(width, height)| Frame {
+            width,
+            height,
+            data: vec![],
+        });
+
+        let (width, height, positions) = AssetLoader::shelf_pack(&frames);
+
+        for (index, (frame, &(x, y))) in frames.iter().zip(&positions).enumerate() {
+            assert!(x + frame.width <= width && y + frame.height <= height);
+            for (other, &(other_x, other_y)) in frames.iter().zip(&positions).skip(index + 1) {
+                assert!(
+                    x + frame.width < other_x
+                        || other_x + other.width < x
+                        || y + frame.height < other_y
+                        || other_y + other.height < y,
+                    "Frames at {:?} and {:?} touch.",
+                    (x, y),
+                    (other_x, other_y)
+                );
+            }
+        }
     }
 }
@@ -2,6 +2,8 @@ mod loader;
 
 use bevy_asset::AssetApp;
 
+pub use self::loader::{AssetLoaderSettings, FramePacking};
+
 use crate::{Sprite, assets::SpriteImages, material};
 
 pub struct Plugin;
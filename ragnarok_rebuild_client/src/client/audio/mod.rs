@@ -1,16 +1,18 @@
 use bevy::{
     audio::{AudioPlayer, PlaybackMode, PlaybackSettings, SpatialScale, Volume},
     ecs::observer::On,
-    prelude::{ChildOf, Commands, Query, Res, Transform},
+    prelude::{ChildOf, Commands, GlobalTransform, Name, Query, Res, Transform},
 };
 use bevy_ragnarok_act::events::ActorSound;
+use bevy_ragnarok_rsw::events::WorldSound;
 use ragnarok_rebuild_bevy::audio::{AudioSettings, Sound};
 
 pub struct Plugin;
 
 impl bevy::app::Plugin for Plugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_observer(play_actor_sound);
+        app.add_observer(play_actor_sound)
+            .add_observer(play_world_sound);
     }
 }
 
@@ -44,3 +46,34 @@ fn play_actor_sound(
         },
     ));
 }
+
+fn play_world_sound(
+    world_sound: On<WorldSound>,
+    mut commands: Commands,
+    global_transforms: Query<&GlobalTransform>,
+    audio_settings: Res<AudioSettings>,
+) {
+    // The world is scaled, `range` is in Rsw units
+    let world_scale = global_transforms
+        .get(world_sound.entity)
+        .map(|transform| transform.scale().x)
+        .unwrap_or(1.);
+
+    // Spawned as a child so that it stops when the world unloads
+    commands.spawn((
+        Name::new(world_sound.name.clone()),
+        Sound,
+        Transform::default(),
+        AudioPlayer(world_sound.track.clone()),
+        PlaybackSettings {
+            mode: PlaybackMode::Despawn,
+            volume: Volume::Linear(world_sound.volume * audio_settings.effects_volume),
+            spatial: true,
+            spatial_scale: Some(SpatialScale::new(
+                5. / (world_sound.range * world_scale).max(f32::EPSILON),
+            )),
+            ..Default::default()
+        },
+        ChildOf(world_sound.entity),
+    ));
+}
@@ -32,6 +32,7 @@ use bevy_enhanced_input::{
 use bevy_ragnarok_camera::{
     CameraOfOrbitalCamera, OrbitalCamera, OrbitalCameraLimits, OrbitalCameraSettings, TrackedEntity,
 };
+use bevy_ragnarok_quad_tree::QuadTreeCulling;
 
 use crate::client::camera::{
     CameraPitch, CameraYaw, CameraZoom, OrbitalCameraPrimaryContext, OrbitalCameraSecondaryContext,
@@ -42,6 +43,9 @@ use crate::client::camera::{
 const TAP_TIMER: f32 = 0.15;
 /// Time between [`Tap`] to be interpreted as double tap
 const DOUBLE_TAP_INTERVAL: f32 = 0.2;
+/// Distance after which the props of the map are culled. The game is scaled
+/// so a GAT tile is 1 unit and a GND cube is 2, this is 200 tiles
+const DRAW_DISTANCE: f32 = 200.;
 
 pub struct Plugin;
 
@@ -113,6 +117,9 @@ fn setup_orbital_camera(mut commands: Commands) {
         },
         Bloom::NATURAL,
         SpatialListener::default(),
+        QuadTreeCulling {
+            max_distance: DRAW_DISTANCE,
+        },
         ChildOf(orbital_camera),
         <CameraOfOrbitalCamera as Relationship>::from(orbital_camera),
     ));
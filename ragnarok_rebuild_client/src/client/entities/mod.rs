@@ -3,9 +3,12 @@ mod components;
 use bevy::{
     animation::AnimationTarget,
     app::First,
-    asset::{AssetServer, Assets},
+    asset::{AssetId, AssetServer, Assets, Handle},
+    image::Image,
+    mesh::MeshTag,
     pbr::MeshMaterial3d,
-    prelude::{Changed, ChildOf, Commands, Query, Res, With},
+    platform::collections::HashMap,
+    prelude::{Changed, ChildOf, Commands, Query, Res, ResMut, Resource, With},
 };
 use bevy_ragnarok_act::{Actor, ActorLayer, ActorPlayer, SpritesheetIndex};
 use bevy_ragnarok_pal::Palette;
@@ -24,11 +27,24 @@ impl bevy::app::Plugin for Plugin {
         app
             // Types
             .register_type::<Entity>()
+            // Resources
+            .init_resource::<SpriteMaterials>()
             // Systems
             .add_systems(First, update_sprite);
     }
 }
 
+/// Key of the materials of sprite layers, the flip and the bits of the tint
+type LayerKey = (u32, [u32; 4]);
+
+/// Materials of sprite layers, shared by all layers drawing frames of the same
+/// image with the same palette, flip, and tint
+#[derive(Default, Resource)]
+struct SpriteMaterials {
+    indexed: HashMap<(AssetId<Image>, AssetId<Image>, LayerKey), Handle<SprIndexedMaterial>>,
+    true_color: HashMap<(AssetId<Image>, LayerKey), Handle<SprTrueColorMaterial>>,
+}
+
 fn update_sprite(
     mut commands: Commands,
     actor_layers: Query<
@@ -39,6 +55,7 @@ fn update_sprite(
     actors: Query<&Sprite, With<Actor>>,
     palettes: Query<&Palette, With<Actor>>,
     sprites_images: Res<Assets<SpriteImages>>,
+    mut sprite_materials: ResMut<SpriteMaterials>,
     asset_server: Res<AssetServer>,
 ) {
     for (actor_layer, layer, target) in actor_layers.iter() {
@@ -46,6 +63,7 @@ fn update_sprite(
         layer_commands.remove::<(
             MeshMaterial3d<SprIndexedMaterial>,
             MeshMaterial3d<SprTrueColorMaterial>,
+            MeshTag,
         )>();
 
         let Ok(player) = actor_players.get(target.player) else {
@@ -61,23 +79,51 @@ fn update_sprite(
                 uv_flip: layer.uv_flip as u32,
                 tint: layer.tint,
             };
+            let layer_key = (uniform.uv_flip, layer.tint.to_f32_array().map(f32::to_bits));
             match layer.spritesheet_index {
                 SpritesheetIndex::Indexed(index) => {
                     let palette = palettes
                         .get(player.parent())
                         .map(|palette| palette.0.clone())
                         .unwrap_or_else(|_| sprite_images.palette.clone());
-                    layer_commands.insert(MeshMaterial3d(asset_server.add(SprIndexedMaterial {
-                        uniform,
-                        index_image: sprite_images.indexed_sprites[index].clone(),
-                        palette,
-                    })));
+                    let Some((index_image, tag)) = sprite_images.indexed_sprites.get(index)
+                    else {
+                        bevy::log::error!("Sprite {:?} has no indexed frame {}.", sprite.0, index);
+                        return;
+                    };
+                    let material = sprite_materials
+                        .indexed
+                        .entry((index_image.id(), palette.id(), layer_key))
+                        .or_insert_with(|| {
+                            asset_server.add(SprIndexedMaterial {
+                                uniform,
+                                index_image,
+                                palette,
+                                frames: sprite_images.indexed_sprites.frames.clone(),
+                            })
+                        });
+                    layer_commands.insert((MeshMaterial3d(material.clone()), tag));
                 }
                 SpritesheetIndex::TrueColor(index) => {
-                    layer_commands.insert(MeshMaterial3d(asset_server.add(SprTrueColorMaterial {
-                        uniform,
-                        color: sprite_images.true_color_sprites[index].clone(),
-                    })));
+                    let Some((color, tag)) = sprite_images.true_color_sprites.get(index) else {
+                        bevy::log::error!(
+                            "Sprite {:?} has no true color frame {}.",
+                            sprite.0,
+                            index
+                        );
+                        return;
+                    };
+                    let material = sprite_materials
+                        .true_color
+                        .entry((color.id(), layer_key))
+                        .or_insert_with(|| {
+                            asset_server.add(SprTrueColorMaterial {
+                                uniform,
+                                color,
+                                frames: sprite_images.true_color_sprites.frames.clone(),
+                            })
+                        });
+                    layer_commands.insert((MeshMaterial3d(material.clone()), tag));
                 }
                 SpritesheetIndex::None => (),
             }
@@ -18,13 +18,16 @@ use bevy::{
     },
     log::{debug, error, trace},
     math::Vec3,
+    platform::collections::HashSet,
     scene::{Scene, SceneInstanceReady, SceneSpawner},
     state::{app::AppExtStates, commands::CommandsStatesExt, condition::in_state, state::OnEnter},
     transform::components::Transform,
 };
-use bevy_ragnarok_gnd::Ground as GndGround;
-use bevy_ragnarok_rsm::Model;
+use bevy_ragnarok_gat::plugin::AssetLoaderSettings as GatLoaderSettings;
+use bevy_ragnarok_gnd::{plugin::AssetLoaderSettings as GndLoaderSettings, Ground as GndGround};
+use bevy_ragnarok_rsm::{plugin::AssetLoaderSettings as RsmLoaderSettings, Model};
 use bevy_ragnarok_rsw::{
+    events::{BatchStaticProps, InstanceRepeatedProps},
     relationships::{
         AltitudeOfWorld, GroundOfWorld, ModelsOfWorld, WorldOfAltitude, WorldOfGround,
         WorldOfModels,
@@ -82,7 +85,12 @@ impl bevy::app::Plugin for Plugin {
         );
         app.add_systems(
             OnEnter(MapChangeStates::Loaded),
-            (start_animations, update_game_transform).in_set(WorldSystems::Cleanup),
+            (
+                start_animations,
+                (instance_repeated_props, batch_static_props).chain(),
+                update_game_transform,
+            )
+                .in_set(WorldSystems::Cleanup),
         );
 
         app.add_observer(map_change);
@@ -151,15 +159,28 @@ fn load_ground(
         return;
     };
 
-    let Ok((ground, ground_path)) = children.get(*world_of_models.collection()) else {
+    let Ok((
+        ground,
+        Ground {
+            ground_path,
+            shadow_map_alpha,
+        },
+    )) = children.get(*world_of_models.collection())
+    else {
         error!("{world} does not ground.");
         commands.write_message(AppExit::from_code(1));
         return;
     };
 
-    commands.entity(ground.entity).insert(LoadingGround(
-        asset_server.load(format!("data/{}#Scene", ground_path.ground_path)),
-    ));
+    let shadow_map_alpha = *shadow_map_alpha;
+    commands
+        .entity(ground.entity)
+        .insert(LoadingGround(asset_server.load_with_settings(
+            format!("data/{}#Scene", ground_path),
+            move |settings: &mut GndLoaderSettings| {
+                settings.shadow_map_alpha = shadow_map_alpha;
+            },
+        )));
 }
 
 /// Load altitude tiles of [`World`]
@@ -186,8 +207,8 @@ fn load_altitude(
         .entity(altitude.entity)
         .insert(LoadingAltitude(asset_server.load_with_settings(
             format!("data/{}#Scene", altitude_path),
-            |settings: &mut f32| {
-                *settings = 5.;
+            |settings: &mut GatLoaderSettings| {
+                settings.tile_scale = 5.;
             },
         )));
 }
@@ -245,14 +266,28 @@ fn load_models(
         return;
     };
 
+    // Meshes of models used by static props are kept in the main world to
+    // be batched, batching drops them from the main world afterwards
+    let static_models = animated_props
+        .iter_many(children)
+        .filter(|animated_prop| animated_prop.animation_type == 0)
+        .map(|animated_prop| animated_prop.prop_path.clone())
+        .collect::<HashSet<_>>();
+
     for child in children {
         let Ok(animated_prop) = animated_props.get(*child) else {
             unreachable!("All children of {models} must be AnimatedProp.");
         };
 
-        commands.entity(*child).insert(LoadingModel(
-            asset_server.load(format!("data/model/{}#Scene", animated_prop.prop_path)),
-        ));
+        let keep_meshes = static_models.contains(&animated_prop.prop_path);
+        commands
+            .entity(*child)
+            .insert(LoadingModel(asset_server.load_with_settings(
+                format!("data/model/{}#Scene", animated_prop.prop_path),
+                move |settings: &mut RsmLoaderSettings| {
+                    settings.keep_meshes = keep_meshes;
+                },
+            )));
     }
 }
 
@@ -425,6 +460,24 @@ fn start_animations(
     }
 }
 
+/// Instance the static [`AnimatedProp`]s of the [`World`] that share a model
+/// with many others.
+fn instance_repeated_props(mut commands: Commands, worlds: Query<Entity, With<World>>) {
+    for world in worlds {
+        commands.trigger(InstanceRepeatedProps {
+            entity: world,
+            min_copies: 8,
+        });
+    }
+}
+
+/// Merge the static [`AnimatedProp`]s of the [`World`].
+fn batch_static_props(mut commands: Commands, worlds: Query<Entity, With<World>>) {
+    for world in worlds {
+        commands.trigger(BatchStaticProps { entity: world });
+    }
+}
+
 /// Update the [`Transform`] of [`Game`] to include the new [`GndGround::scale`].
 fn update_game_transform(
     mut commands: Commands,
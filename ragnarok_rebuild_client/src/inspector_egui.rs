@@ -20,7 +20,10 @@ use bevy_inspector_egui::{
 };
 
 use bevy_ragnarok_act::Actor;
-use bevy_ragnarok_spr::Sprite;
+use bevy_ragnarok_spr::{
+    plugin::{AssetLoaderSettings as SprLoaderSettings, FramePacking},
+    Sprite,
+};
 use ragnarok_rebuild_bevy::assets::paths;
 
 use crate::client::{entities, world::ChangeMap};
@@ -122,6 +125,17 @@ fn check_loading_font(
     };
 }
 
+/// Loads a [`Sprite`] with its frames packed in an atlas, so that its layers
+/// can share materials
+fn load_sprite(asset_server: &AssetServer, path: &str) -> Sprite {
+    Sprite(asset_server.load_with_settings(
+        format!("{}{}", paths::SPR_FILES_FOLDER, path),
+        |settings: &mut SprLoaderSettings| {
+            settings.frame_packing = FramePacking::Atlas;
+        },
+    ))
+}
+
 fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         Name::new("anubis"),
@@ -135,11 +149,7 @@ fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
                 "몬스터/anubis.act"
             )),
         },
-        Sprite(asset_server.load(format!(
-            "{}{}",
-            paths::SPR_FILES_FOLDER,
-            "몬스터/anubis.spr"
-        ))),
+        load_sprite(&asset_server, "몬스터/anubis.spr"),
     ));
     commands.spawn((
         Name::new("poring"),
@@ -153,11 +163,7 @@ fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
                 "몬스터/poring.act"
             )),
         },
-        Sprite(asset_server.load(format!(
-            "{}{}",
-            paths::SPR_FILES_FOLDER,
-            "몬스터/poring.spr"
-        ))),
+        load_sprite(&asset_server, "몬스터/poring.spr"),
     ));
 
     commands.spawn((
@@ -172,11 +178,7 @@ fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
                 "몬스터/ill_ghostring.act"
             )),
         },
-        Sprite(asset_server.load(format!(
-            "{}{}",
-            paths::SPR_FILES_FOLDER,
-            "몬스터/ill_ghostring.spr"
-        ))),
+        load_sprite(&asset_server, "몬스터/ill_ghostring.spr"),
     ));
     commands.spawn((
         Name::new("ice_titan"),
@@ -190,11 +192,7 @@ fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
                 "몬스터/ice_titan.act"
             )),
         },
-        Sprite(asset_server.load(format!(
-            "{}{}",
-            paths::SPR_FILES_FOLDER,
-            "몬스터/ice_titan.spr"
-        ))),
+        load_sprite(&asset_server, "몬스터/ice_titan.spr"),
     ));
     commands.spawn((
         Name::new("4_f_01"),
@@ -204,6 +202,6 @@ fn spawn_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
         Actor {
             actor: asset_server.load(format!("{}{}", paths::SPR_FILES_FOLDER, "npc/4_f_01.act")),
         },
-        Sprite(asset_server.load(format!("{}{}", paths::SPR_FILES_FOLDER, "npc/4_f_01.spr"))),
+        load_sprite(&asset_server, "npc/4_f_01.spr"),
     ));
 }
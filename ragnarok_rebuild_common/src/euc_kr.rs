@@ -1,4 +1,4 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use crate::reader_ext::ReaderExt;
 
@@ -47,3 +47,57 @@ pub fn read_euc_kr_string(
         }
     }
 }
+
+/// Writes `string` as EUC-KR padded with `0` up to `length` bytes.
+///
+/// Paths are read with `/` as separator, they are written back
+/// with `\` like the client expects.
+pub fn write_euc_kr_string(
+    writer: &mut dyn Write,
+    string: &str,
+    length: usize,
+) -> Result<(), std::io::Error> {
+    let encoded = encode_euc_kr_string(string)?;
+    if encoded.len() > length {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("'{string}' does not fit in {length} bytes."),
+        ));
+    }
+
+    writer.write_all(&encoded)?;
+    writer.write_all(&vec![0; length - encoded.len()])
+}
+
+/// Writes `strings` the way [`read_n_euc_kr_strings`] reads them, padded to
+/// `fixed_len` or prefixed with their length. The count is not written.
+pub fn write_n_euc_kr_strings(
+    writer: &mut dyn Write,
+    strings: &[Box<str>],
+    fixed_len: Option<usize>,
+) -> Result<(), io::Error> {
+    for string in strings {
+        if let Some(len) = fixed_len {
+            write_euc_kr_string(writer, string, len)?;
+        } else {
+            let encoded = encode_euc_kr_string(string)?;
+            let len = u32::try_from(encoded.len())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            writer.write_all(&len.to_le_bytes())?;
+            writer.write_all(&encoded)?;
+        }
+    }
+    Ok(())
+}
+
+fn encode_euc_kr_string(string: &str) -> Result<Vec<u8>, io::Error> {
+    let string = string.replace('/', "\\");
+    let encode = encoding_rs::EUC_KR.encode(&string);
+    if encode.2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Could not encode '{string}' to EUC_KR."),
+        ));
+    }
+    Ok(encode.0.into_owned())
+}